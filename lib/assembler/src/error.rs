@@ -0,0 +1,45 @@
+use std::{error, fmt, io};
+
+use diagnostics::{ErrorKind, StageError};
+
+#[derive(Debug)]
+pub enum Error {
+    /// gcc couldn't be started
+    Spawn(io::Error),
+    /// gcc was started but we couldn't wait for it to finish
+    Wait(io::Error),
+    /// gcc exited with a nonzero code, it will have reported why on stderr
+    Failed(i32),
+    /// gcc was killed by a signal
+    Signalled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Spawn(_) => write!(f, "unable to run the assembler"),
+            Error::Wait(_) => write!(f, "failed to wait on the assembler"),
+            Error::Failed(code) => write!(f, "assembler exited with nonzero code: {code}"),
+            Error::Signalled => write!(f, "assembler terminated due to signal"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Spawn(e) | Error::Wait(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl StageError for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            // we wrote the assembly so gcc rejecting it is on us
+            Error::Failed(_) => ErrorKind::Internal,
+            Error::Spawn(_) | Error::Wait(_) | Error::Signalled => ErrorKind::Environment,
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
-    fs::{self, File},
-    io::{self, Read, Write},
+    io,
     path::Path,
-    process::{Child, Command, Stdio},
-    thread,
+    process::{Child, Command},
 };
 
+mod error;
+
+pub use error::Error;
+
 trait CompilerProc {
     fn wrap_and_spawn(&self, assembled_path: &Path, out_path: &Path) -> Result<Child, io::Error>;
 }
@@ -28,23 +30,23 @@ fn assemble_internal(
     assembled_path: &Path,
     out_path: &Path,
     compiler: &impl CompilerProc,
-) -> io::Result<()> {
+) -> Result<(), Error> {
     let mut assembler = compiler
         .wrap_and_spawn(assembled_path, out_path)
-        .expect("Error spawning assembler");
+        .map_err(Error::Spawn)?;
 
-    let exit = assembler.wait().expect("Failed to wait on preprocessor");
+    let exit = assembler.wait().map_err(Error::Wait)?;
 
     match &exit.code() {
-        Some(0) => println!("Preprocessor ran successfully"),
-        Some(c) => panic!("Preprocessor exited with nonzero code: {c}"),
-        None => panic!("Preprocessor terminated due to signal"),
+        Some(0) => println!("Assembler ran successfully"),
+        Some(c) => return Err(Error::Failed(*c)),
+        None => return Err(Error::Signalled),
     };
 
     Ok(())
 }
 
-pub fn assemble(assembled_path: &Path, out_path: &Path) -> io::Result<()> {
+pub fn assemble(assembled_path: &Path, out_path: &Path) -> Result<(), Error> {
     let default_impl = DefaultImpl;
     assemble_internal(assembled_path, out_path, &default_impl)
 }
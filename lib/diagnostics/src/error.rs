@@ -0,0 +1,42 @@
+use std::{error::Error, fmt};
+
+use crate::Diagnostic;
+
+/// who is responsible for a stage failing, the driver picks its exit code from this
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorKind {
+    /// the C source (or the command line) is wrong, the diagnostics say how
+    User,
+    /// something outside the compiler went wrong, e.g. gcc is missing or a file is unreadable
+    Environment,
+    /// a bug in the compiler, or something it doesn't implement yet
+    Internal,
+}
+
+/// implemented by the error type of each stage
+pub trait StageError: Error {
+    fn kind(&self) -> ErrorKind;
+
+    /// the diagnostics which caused the stage to fail, if it failed on the source
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &[]
+    }
+}
+
+/// the error of a stage we haven't written yet, naming what the stage does
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Unimplemented(pub &'static str);
+
+impl fmt::Display for Unimplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not implemented yet", self.0)
+    }
+}
+
+impl Error for Unimplemented {}
+
+impl StageError for Unimplemented {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Internal
+    }
+}
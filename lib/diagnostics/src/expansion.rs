@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::Span;
+
+/// the macro expansion a token came out of: which macro, where its name was when it was
+/// expanded and where it was defined, along with the expansion that invocation itself came
+/// out of (if the name was in another macro's replacement list or arguments)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Expansion {
+    name: String,
+    invocation: Span,
+    definition: Span,
+    parent: Option<Rc<Expansion>>,
+}
+
+impl Expansion {
+    pub fn new(
+        name: &str,
+        invocation: Span,
+        definition: Span,
+        parent: Option<Rc<Expansion>>,
+    ) -> Self {
+        Expansion {
+            name: name.to_string(),
+            invocation,
+            definition,
+            parent,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn invocation(&self) -> &Span {
+        &self.invocation
+    }
+
+    pub fn definition(&self) -> &Span {
+        &self.definition
+    }
+
+    pub fn parent(&self) -> Option<&Rc<Expansion>> {
+        self.parent.as_ref()
+    }
+
+    /// this expansion and those it is inside, innermost first
+    pub fn chain(&self) -> impl Iterator<Item = &Expansion> {
+        std::iter::successors(Some(self), |e| e.parent.as_deref())
+    }
+
+    /// the expansion of a token which was already `inner` and is now part of the expansion
+    /// `outer` too, e.g. a macro argument: `inner` is kept up to where it meets the
+    /// expansions `outer` is inside, from there on it is `outer`
+    pub fn within(inner: Option<&Rc<Expansion>>, outer: &Rc<Expansion>) -> Rc<Expansion> {
+        match inner {
+            None => outer.clone(),
+            Some(inner) if outer.chain().any(|e| std::ptr::eq(e, &**inner)) => outer.clone(),
+            Some(inner) => Rc::new(Expansion {
+                parent: Some(Expansion::within(inner.parent(), outer)),
+                ..(**inner).clone()
+            }),
+        }
+    }
+}
+
+/// the expansions of the tokens in some preprocessed text, by the byte offset each token
+/// starts at, tokens which weren't produced by a macro have none
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Expansions {
+    by_offset: BTreeMap<usize, Rc<Expansion>>,
+}
+
+impl Expansions {
+    pub fn new() -> Self {
+        Expansions::default()
+    }
+
+    pub fn insert(&mut self, offset: usize, expansion: Rc<Expansion>) {
+        self.by_offset.insert(offset, expansion);
+    }
+
+    pub fn get(&self, offset: usize) -> Option<&Rc<Expansion>> {
+        self.by_offset.get(&offset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_offset.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_offset.is_empty()
+    }
+}
@@ -0,0 +1,320 @@
+mod error;
+mod expansion;
+mod render;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::ErrorKind;
+pub use error::StageError;
+pub use error::Unimplemented;
+
+pub use expansion::Expansion;
+pub use expansion::Expansions;
+
+pub use render::render;
+pub use render::FileSources;
+pub use render::Format;
+pub use render::SourceLines;
+
+use std::cell::RefCell;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Note => write!(f, "note"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+            Severity::Fatal => write!(f, "fatal error"),
+        }
+    }
+}
+
+/// a run of `length` columns starting at `file:line:column`, columns count from 1
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Span {
+    file: String,
+    line: u32,
+    column: usize,
+    length: usize,
+}
+
+impl Span {
+    pub fn new(file: &str, line: u32, column: usize, length: usize) -> Self {
+        Span {
+            file: file.to_string(),
+            line,
+            column,
+            length,
+        }
+    }
+
+    pub fn point(file: &str, line: u32, column: usize) -> Self {
+        Span::new(file, line, column, 1)
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+/// an additional span which is relevant to a diagnostic
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Label {
+    span: Span,
+    message: String,
+}
+
+impl Label {
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// a suggested edit: replace the text covered by `span` with `replacement`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FixIt {
+    span: Span,
+    replacement: String,
+}
+
+impl FixIt {
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    primary: Option<Span>,
+    secondary: Vec<Label>,
+    notes: Vec<String>,
+    fixits: Vec<FixIt>,
+    system_header: bool,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &'static str, message: &str) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message: message.to_string(),
+            primary: None,
+            secondary: vec![],
+            notes: vec![],
+            fixits: vec![],
+            system_header: false,
+        }
+    }
+
+    pub fn error(code: &'static str, message: &str) -> Self {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &'static str, message: &str) -> Self {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    pub fn note(code: &'static str, message: &str) -> Self {
+        Diagnostic::new(Severity::Note, code, message)
+    }
+
+    pub fn fatal(code: &'static str, message: &str) -> Self {
+        Diagnostic::new(Severity::Fatal, code, message)
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.primary = Some(span);
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// a note for each macro expansion the diagnostic's location is inside, innermost
+    /// first, the way clang explains where a token written in a macro ended up
+    pub fn with_expansion(mut self, expansion: &Expansion) -> Self {
+        for e in expansion.chain() {
+            let definition = e.definition();
+            let message = format!(
+                "in expansion of macro \"{}\" defined at {}:{}",
+                e.name(),
+                definition.file(),
+                definition.line()
+            );
+            self = self.with_label(e.invocation().clone(), &message);
+        }
+        self
+    }
+
+    pub fn with_fixit(mut self, span: Span, replacement: &str) -> Self {
+        self.fixits.push(FixIt {
+            span,
+            replacement: replacement.to_string(),
+        });
+        self
+    }
+
+    /// the diagnostic is about something in a system header, where as in gcc anything
+    /// short of an error is only reported with `-Wsystem-headers`
+    pub fn in_system_header(mut self) -> Self {
+        self.system_header = true;
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn primary(&self) -> Option<&Span> {
+        self.primary.as_ref()
+    }
+
+    pub fn secondary(&self) -> &[Label] {
+        &self.secondary
+    }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn fixits(&self) -> &[FixIt] {
+        &self.fixits
+    }
+
+    pub fn is_in_system_header(&self) -> bool {
+        self.system_header
+    }
+}
+
+/// the sink diagnostics are reported into, reporting only needs a shared reference so the
+/// sink can be handed to every component of a stage
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Diagnostics {
+    reported: RefCell<Vec<Diagnostic>>,
+    /// keep the warnings and notes about system headers rather than dropping them
+    system_headers: bool,
+    /// the number of errors after which compilation stops, if there is a limit
+    max_errors: Option<usize>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Diagnostics::default()
+    }
+
+    /// keep what is reported about system headers if `system_headers` (`-Wsystem-headers`)
+    pub fn with_system_headers(mut self, system_headers: bool) -> Self {
+        self.system_headers = system_headers;
+        self
+    }
+
+    /// stop after `max_errors` errors, or never if it is 0 (`-fmax-errors=`)
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors).filter(|max| *max > 0);
+        self
+    }
+
+    pub fn report(&self, diagnostic: Diagnostic) {
+        let hidden = diagnostic.system_header && diagnostic.severity < Severity::Error;
+        if (hidden && !self.system_headers) || self.limit_reached() {
+            return;
+        }
+        let error = diagnostic.severity >= Severity::Error;
+        self.reported.borrow_mut().push(diagnostic);
+        if let Some(max) = self.max_errors.filter(|_| error && self.limit_reached()) {
+            let message = format!("compilation terminated due to -fmax-errors={max}");
+            self.reported
+                .borrow_mut()
+                .push(Diagnostic::fatal("max-errors", &message));
+        }
+    }
+
+    /// as many errors have been reported as `-fmax-errors` allows, so the stages should
+    /// stop and nothing more is reported
+    pub fn limit_reached(&self) -> bool {
+        self.max_errors.is_some_and(|max| self.error_count() >= max)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.count(|s| s >= Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(|s| s == Severity::Warning)
+    }
+
+    fn count(&self, f: impl Fn(Severity) -> bool) -> usize {
+        self.reported
+            .borrow()
+            .iter()
+            .filter(|d| f(d.severity))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reported.borrow().is_empty()
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.reported.borrow().clone()
+    }
+
+    pub fn take(&self) -> Vec<Diagnostic> {
+        self.reported.take()
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+
+use crate::{Diagnostic, FixIt, Span};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// one gcc style `file:line:col: severity: message` line per diagnostic
+    Text,
+    /// as `Text` but followed by the source line and a caret under the location
+    Caret,
+    /// a JSON array with one object per diagnostic
+    Json,
+}
+
+/// where the caret renderer gets the text of source lines from
+pub trait SourceLines {
+    fn line(&self, file: &str, line: u32) -> Option<String>;
+}
+
+/// reads source lines from the files on disk, each file is only read once
+#[derive(Debug, Default)]
+pub struct FileSources {
+    cache: RefCell<HashMap<String, Option<Vec<String>>>>,
+}
+
+impl FileSources {
+    pub fn new() -> Self {
+        FileSources::default()
+    }
+}
+
+impl SourceLines for FileSources {
+    fn line(&self, file: &str, line: u32) -> Option<String> {
+        let mut cache = self.cache.borrow_mut();
+        let lines = cache.entry(file.to_string()).or_insert_with(|| {
+            fs::read_to_string(file)
+                .ok()
+                .map(|s| s.lines().map(|l| l.trim_end_matches('\r').to_string()).collect())
+        });
+        match (lines, line) {
+            (Some(lines), 1..) => lines.get(line as usize - 1).cloned(),
+            _ => None,
+        }
+    }
+}
+
+pub fn render(diagnostics: &[Diagnostic], format: Format, sources: &dyn SourceLines) -> String {
+    match format {
+        Format::Text => diagnostics.iter().map(|d| render_text(d, None)).collect(),
+        Format::Caret => diagnostics
+            .iter()
+            .map(|d| render_text(d, Some(sources)))
+            .collect(),
+        Format::Json => render_json(diagnostics),
+    }
+}
+
+fn location_prefix(span: Option<&Span>) -> String {
+    match span {
+        // somewhere without lines, like gcc's `<command-line>`
+        Some(s) if s.line == 0 => format!("{}: ", s.file),
+        Some(s) => format!("{}:{}:{}: ", s.file, s.line, s.column),
+        None => String::new(),
+    }
+}
+
+fn render_text(diagnostic: &Diagnostic, sources: Option<&dyn SourceLines>) -> String {
+    let mut out = String::new();
+    let primary = diagnostic.primary.as_ref();
+
+    let _ = writeln!(
+        out,
+        "{}{}: {} [{}]",
+        location_prefix(primary),
+        diagnostic.severity,
+        diagnostic.message,
+        diagnostic.code
+    );
+    let on_line = |span: &Span, fixit: &FixIt| {
+        fixit.span.file == span.file && fixit.span.line == span.line
+    };
+    if let (Some(sources), Some(span)) = (sources, primary) {
+        let fixits: Vec<&FixIt> = diagnostic.fixits.iter().filter(|f| on_line(span, f)).collect();
+        out += &excerpt(sources, span, &fixits);
+    }
+
+    for fixit in &diagnostic.fixits {
+        match (sources, primary) {
+            (Some(_), Some(span)) if on_line(span, fixit) => (),
+            (Some(sources), _) => out += &excerpt(sources, &fixit.span, &[fixit]),
+            (None, _) => {
+                // the same form as gcc's -fdiagnostics-parseable-fixits
+                let _ = writeln!(
+                    out,
+                    "fix-it:\"{}\":{{{}:{}-{}:{}}}:\"{}\"",
+                    fixit.span.file,
+                    fixit.span.line,
+                    fixit.span.column,
+                    fixit.span.line,
+                    fixit.span.column + fixit.span.length,
+                    escape(&fixit.replacement)
+                );
+            }
+        }
+    }
+
+    for label in &diagnostic.secondary {
+        let _ = writeln!(out, "{}note: {}", location_prefix(Some(&label.span)), label.message);
+        if let Some(sources) = sources {
+            out += &excerpt(sources, &label.span, &[]);
+        }
+    }
+
+    for note in &diagnostic.notes {
+        let _ = writeln!(out, "{}note: {}", location_prefix(primary), note);
+    }
+
+    out
+}
+
+/// the source line with a `^~~~` marker under the span, and the replacement text of any
+/// fix-its for that line under that
+fn excerpt(sources: &dyn SourceLines, span: &Span, fixits: &[&FixIt]) -> String {
+    let Some(line) = sources.line(&span.file, span.line) else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+    let indent = |column: usize| " ".repeat(column.saturating_sub(1));
+    let marker = "^".to_string() + &"~".repeat(span.length.saturating_sub(1));
+
+    let _ = writeln!(out, "{:>5} | {}", span.line, line);
+    let _ = writeln!(out, "{:>5} | {}{}", "", indent(span.column), marker);
+    for fixit in fixits {
+        let _ = writeln!(out, "{:>5} | {}{}", "", indent(fixit.span.column), fixit.replacement);
+    }
+    out
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn json_position(file: &str, line: u32, column: usize) -> String {
+    format!(
+        "{{\"file\": \"{}\", \"line\": {}, \"column\": {}}}",
+        escape(file),
+        line,
+        column
+    )
+}
+
+fn json_span(span: &Span) -> String {
+    format!(
+        "\"caret\": {}, \"finish\": {}",
+        json_position(&span.file, span.line, span.column),
+        json_position(&span.file, span.line, span.column + span.length.saturating_sub(1))
+    )
+}
+
+fn render_json(diagnostics: &[Diagnostic]) -> String {
+    let objects: Vec<String> = diagnostics
+        .iter()
+        .map(|d| {
+            let mut locations = vec![];
+            if let Some(span) = &d.primary {
+                locations.push(format!("{{{}}}", json_span(span)));
+            }
+            for label in &d.secondary {
+                locations.push(format!(
+                    "{{{}, \"label\": \"{}\"}}",
+                    json_span(&label.span),
+                    escape(&label.message)
+                ));
+            }
+
+            let children: Vec<String> = d
+                .notes
+                .iter()
+                .map(|n| format!("{{\"kind\": \"note\", \"message\": \"{}\"}}", escape(n)))
+                .collect();
+
+            let fixits: Vec<String> = d
+                .fixits
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{{\"start\": {}, \"next\": {}, \"string\": \"{}\"}}",
+                        json_position(&f.span.file, f.span.line, f.span.column),
+                        json_position(&f.span.file, f.span.line, f.span.column + f.span.length),
+                        escape(&f.replacement)
+                    )
+                })
+                .collect();
+
+            format!(
+                "{{\"kind\": \"{}\", \"code\": \"{}\", \"message\": \"{}\", \"locations\": [{}], \"children\": [{}], \"fixits\": [{}]}}",
+                d.severity,
+                escape(d.code),
+                escape(&d.message),
+                locations.join(", "),
+                children.join(", "),
+                fixits.join(", ")
+            )
+        })
+        .collect();
+
+    format!("[{}]\n", objects.join(",\n "))
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+use std::rc::Rc;
+
+use crate::{Diagnostics, Expansion};
+
+struct TestSources;
+impl SourceLines for TestSources {
+    fn line(&self, file: &str, line: u32) -> Option<String> {
+        match (file, line) {
+            ("test.c", 1) => Some("int x = 'ab;".to_string()),
+            ("test.c", 2) => Some("int y = 0".to_string()),
+            _ => None,
+        }
+    }
+}
+
+fn unterminated() -> Diagnostic {
+    Diagnostic::error("unterminated-char-literal", "unterminated char literal")
+        .with_span(Span::new("test.c", 1, 9, 3))
+}
+
+#[test]
+fn test_render_text() {
+    let exp = "test.c:1:9: error: unterminated char literal [unterminated-char-literal]\n";
+    let act = render(&[unterminated()], Format::Text, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_text_no_span() {
+    let diagnostic = Diagnostic::fatal("no-input", "no input files");
+    let exp = "fatal error: no input files [no-input]\n";
+    let act = render(&[diagnostic], Format::Text, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_text_no_line() {
+    let diagnostic = Diagnostic::error("invalid-define", "macro names must be identifiers")
+        .with_span(Span::point("<command-line>", 0, 9));
+    let exp = "<command-line>: error: macro names must be identifiers [invalid-define]\n";
+    let act = render(&[diagnostic], Format::Caret, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_text_notes_and_fixits() {
+    let diagnostic = Diagnostic::error("expected-semi", "expected ';'")
+        .with_span(Span::point("test.c", 2, 10))
+        .with_fixit(Span::new("test.c", 2, 10, 0), ";")
+        .with_label(Span::point("test.c", 1, 1), "previous declaration is here")
+        .with_note("declarations end with ';'");
+    let exp = concat!(
+        "test.c:2:10: error: expected ';' [expected-semi]\n",
+        "fix-it:\"test.c\":{2:10-2:10}:\";\"\n",
+        "test.c:1:1: note: previous declaration is here\n",
+        "test.c:2:10: note: declarations end with ';'\n",
+    );
+    let act = render(&[diagnostic], Format::Text, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_caret() {
+    let exp = concat!(
+        "test.c:1:9: error: unterminated char literal [unterminated-char-literal]\n",
+        "    1 | int x = 'ab;\n",
+        "      |         ^~~\n",
+    );
+    let act = render(&[unterminated()], Format::Caret, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_caret_fixit() {
+    let diagnostic = Diagnostic::error("expected-semi", "expected ';'")
+        .with_span(Span::point("test.c", 2, 10))
+        .with_fixit(Span::new("test.c", 2, 10, 0), ";");
+    let exp = concat!(
+        "test.c:2:10: error: expected ';' [expected-semi]\n",
+        "    2 | int y = 0\n",
+        "      |          ^\n",
+        "      |          ;\n",
+    );
+    let act = render(&[diagnostic], Format::Caret, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_caret_missing_source() {
+    let diagnostic = Diagnostic::warning("multichar", "multi-char char literal")
+        .with_span(Span::point("missing.c", 1, 1));
+    let exp = "missing.c:1:1: warning: multi-char char literal [multichar]\n";
+    let act = render(&[diagnostic], Format::Caret, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_json() {
+    let diagnostic = unterminated().with_note("a \"quoted\" note");
+    let exp = concat!(
+        "[{\"kind\": \"error\", \"code\": \"unterminated-char-literal\", ",
+        "\"message\": \"unterminated char literal\", ",
+        "\"locations\": [{\"caret\": {\"file\": \"test.c\", \"line\": 1, \"column\": 9}, ",
+        "\"finish\": {\"file\": \"test.c\", \"line\": 1, \"column\": 11}}], ",
+        "\"children\": [{\"kind\": \"note\", \"message\": \"a \\\"quoted\\\" note\"}], ",
+        "\"fixits\": []}]\n",
+    );
+    let act = render(&[diagnostic], Format::Json, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_render_json_empty() {
+    assert_eq!("[]\n", render(&[], Format::Json, &TestSources));
+}
+
+#[test]
+fn test_diagnostics_counts() {
+    let diagnostics = Diagnostics::new();
+    diagnostics.report(unterminated());
+    diagnostics.report(Diagnostic::warning("multichar", "multi-char char literal"));
+    diagnostics.report(Diagnostic::note("note", "a note"));
+
+    assert!(diagnostics.has_errors());
+    assert_eq!(1, diagnostics.error_count());
+    assert_eq!(1, diagnostics.warning_count());
+    assert_eq!(3, diagnostics.take().len());
+    assert!(diagnostics.is_empty());
+}
+
+/// `y` in `#define G y` at x.h:2, `G` used in `#define F G` at x.h:1, `F` used at test.c:2
+fn expansions() -> (Rc<Expansion>, Rc<Expansion>) {
+    let f = Rc::new(Expansion::new(
+        "F",
+        Span::new("test.c", 2, 9, 1),
+        Span::point("x.h", 1, 9),
+        None,
+    ));
+    let g = Rc::new(Expansion::new(
+        "G",
+        Span::new("x.h", 1, 11, 1),
+        Span::point("x.h", 2, 9),
+        Some(f.clone()),
+    ));
+    (f, g)
+}
+
+#[test]
+fn test_render_text_expansion() {
+    let (_, g) = expansions();
+    let diagnostic = Diagnostic::error("undeclared", "'y' undeclared")
+        .with_span(Span::point("test.c", 2, 9))
+        .with_expansion(&g);
+    let exp = concat!(
+        "test.c:2:9: error: 'y' undeclared [undeclared]\n",
+        "x.h:1:11: note: in expansion of macro \"G\" defined at x.h:2\n",
+        "test.c:2:9: note: in expansion of macro \"F\" defined at x.h:1\n",
+    );
+    let act = render(&[diagnostic], Format::Text, &TestSources);
+
+    assert_eq!(exp, act);
+}
+
+#[test]
+fn test_expansion_within() {
+    let (f, g) = expansions();
+    let h = Rc::new(Expansion::new(
+        "H",
+        Span::new("test.c", 2, 1, 1),
+        Span::point("x.h", 3, 9),
+        None,
+    ));
+
+    // an argument already from inside `F` stays as it was when it ends up in `G`
+    assert!(Rc::ptr_eq(&g, &Expansion::within(Some(&f), &g)));
+    assert!(Rc::ptr_eq(&g, &Expansion::within(None, &g)));
+
+    // one from an unrelated expansion keeps that and goes on to `G`
+    let within = Expansion::within(Some(&h), &g);
+    let names: Vec<&str> = within.chain().map(|e| e.name()).collect();
+    assert_eq!(vec!["H", "G", "F"], names);
+}
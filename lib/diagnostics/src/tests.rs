@@ -0,0 +1,64 @@
+use crate::{Diagnostic, Diagnostics, ErrorKind, Severity, StageError, Unimplemented};
+
+fn codes(diagnostics: &Diagnostics) -> Vec<&'static str> {
+    diagnostics.diagnostics().iter().map(|d| d.code()).collect()
+}
+
+#[test]
+fn test_system_header_diagnostics() {
+    let report = |diagnostics: &Diagnostics| {
+        diagnostics.report(Diagnostic::warning("a", "a").in_system_header());
+        diagnostics.report(Diagnostic::note("b", "b").in_system_header());
+        diagnostics.report(Diagnostic::error("c", "c").in_system_header());
+        diagnostics.report(Diagnostic::warning("d", "d"));
+    };
+
+    let diagnostics = Diagnostics::new();
+    report(&diagnostics);
+    assert_eq!(vec!["c", "d"], codes(&diagnostics));
+
+    let diagnostics = Diagnostics::new().with_system_headers(true);
+    report(&diagnostics);
+    assert_eq!(vec!["a", "b", "c", "d"], codes(&diagnostics));
+}
+
+#[test]
+fn test_max_errors() {
+    let diagnostics = Diagnostics::new().with_max_errors(2);
+    diagnostics.report(Diagnostic::error("a", "a"));
+    diagnostics.report(Diagnostic::warning("b", "b"));
+    assert!(!diagnostics.limit_reached());
+    diagnostics.report(Diagnostic::error("c", "c"));
+    assert!(diagnostics.limit_reached());
+    diagnostics.report(Diagnostic::error("d", "d"));
+    diagnostics.report(Diagnostic::warning("e", "e"));
+
+    assert_eq!(vec!["a", "b", "c", "max-errors"], codes(&diagnostics));
+    let last = diagnostics
+        .diagnostics()
+        .pop()
+        .expect("should have stopped");
+    assert_eq!(Severity::Fatal, last.severity());
+    assert_eq!(
+        "compilation terminated due to -fmax-errors=2",
+        last.message()
+    );
+}
+
+#[test]
+fn test_no_max_errors() {
+    let diagnostics = Diagnostics::new().with_max_errors(0);
+    for _ in 0..100 {
+        diagnostics.report(Diagnostic::error("a", "a"));
+    }
+    assert!(!diagnostics.limit_reached());
+    assert_eq!(100, diagnostics.error_count());
+}
+
+#[test]
+fn test_unimplemented() {
+    let error = Unimplemented("code generation");
+    assert_eq!("code generation is not implemented yet", error.to_string());
+    assert_eq!(ErrorKind::Internal, error.kind());
+    assert!(error.diagnostics().is_empty());
+}
@@ -1,9 +1,13 @@
 use std::path::Path;
 
+use diagnostics::Unimplemented;
 use generator::Generated;
 
-pub fn emit<'a>(_generated: &Generated, _assembly_path: &Path) -> Result<(), ()> {
-    todo!()
+/// there's no code emission yet, so this is all that can go wrong
+pub type Error = Unimplemented;
+
+pub fn emit(_generated: &Generated, _assembly_path: &Path) -> Result<(), Error> {
+    Err(Unimplemented("code emission"))
 }
 
 #[cfg(test)]
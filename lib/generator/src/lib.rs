@@ -1,9 +1,13 @@
+use diagnostics::Unimplemented;
 use parser::{self, AbstractSyntaxTree};
 
+/// there's no code generation yet, so this is all that can go wrong
+pub type Error = Unimplemented;
+
 pub type Generated = ();
 
-pub fn generate<'a>(_ast: &AbstractSyntaxTree) -> Result<Generated, ()> {
-    todo!()
+pub fn generate(_ast: &AbstractSyntaxTree) -> Result<Generated, Error> {
+    Err(Unimplemented("code generation"))
 }
 
 #[cfg(test)]
@@ -0,0 +1,127 @@
+//! The language options from the command line (`-std=`, `-pedantic`) which every stage
+//! consults to decide what the source may use.
+
+#[cfg(test)]
+mod tests;
+
+use std::{fmt::Display, str::FromStr};
+
+use diagnostics::Severity;
+
+/// the version of ISO C the source is written in, ordered so later standards compare
+/// greater
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Standard {
+    C89,
+    C99,
+    #[default]
+    C11,
+    C17,
+    C23,
+}
+
+impl Standard {
+    /// the value of `__STDC_VERSION__`, C89 didn't define it
+    pub fn version(&self) -> Option<&'static str> {
+        match self {
+            Standard::C89 => None,
+            Standard::C99 => Some("199901L"),
+            Standard::C11 => Some("201112L"),
+            Standard::C17 => Some("201710L"),
+            Standard::C23 => Some("202311L"),
+        }
+    }
+
+    /// the value of `-std=` which selects the standard
+    pub fn option(&self) -> &'static str {
+        match self {
+            Standard::C89 => "c89",
+            Standard::C99 => "c99",
+            Standard::C11 => "c11",
+            Standard::C17 => "c17",
+            Standard::C23 => "c23",
+        }
+    }
+}
+
+/// the name in gcc's messages, where C89 is ISO C90
+impl Display for Standard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Standard::C89 => write!(f, "C90"),
+            Standard::C99 => write!(f, "C99"),
+            Standard::C11 => write!(f, "C11"),
+            Standard::C17 => write!(f, "C17"),
+            Standard::C23 => write!(f, "C23"),
+        }
+    }
+}
+
+/// the value of `-std=`, with the other names gcc accepts for each standard
+impl FromStr for Standard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c89" | "c90" | "iso9899:1990" => Ok(Standard::C89),
+            "c99" | "c9x" | "iso9899:1999" => Ok(Standard::C99),
+            "c11" | "c1x" | "iso9899:2011" => Ok(Standard::C11),
+            "c17" | "c18" | "iso9899:2017" | "iso9899:2018" => Ok(Standard::C17),
+            "c23" | "c2x" | "iso9899:2024" => Ok(Standard::C23),
+            _ => Err(format!("unrecognized standard '{s}'")),
+        }
+    }
+}
+
+/// what to do when the source uses something its standard doesn't have but which we
+/// accept anyway, like `_Bool` in C89
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Pedantic {
+    /// accept it quietly, as gcc does without `-pedantic`
+    #[default]
+    Off,
+    /// `-pedantic`
+    Warn,
+    /// `-pedantic-errors`
+    Error,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct LangOptions {
+    pub standard: Standard,
+    pub pedantic: Pedantic,
+}
+
+impl LangOptions {
+    pub fn new(standard: Standard) -> Self {
+        LangOptions {
+            standard,
+            ..Default::default()
+        }
+    }
+
+    /// the standard is `standard` or a later one
+    pub fn at_least(&self, standard: Standard) -> bool {
+        self.standard >= standard
+    }
+
+    /// `//` comments, C99 6.4.9
+    pub fn line_comments(&self) -> bool {
+        self.at_least(Standard::C99)
+    }
+
+    /// `\u` and `\U` in identifiers, character constants and string literals, C99 6.4.3
+    pub fn universal_character_names(&self) -> bool {
+        self.at_least(Standard::C99)
+    }
+
+    /// how to report something the standard doesn't support which we accept anyway, if
+    /// it should be reported at all
+    pub fn extension_severity(&self) -> Option<Severity> {
+        match self.pedantic {
+            Pedantic::Off => None,
+            Pedantic::Warn => Some(Severity::Warning),
+            Pedantic::Error => Some(Severity::Error),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn test_standard_from_str() {
+    assert_eq!(Ok(Standard::C89), "c89".parse());
+    assert_eq!(Ok(Standard::C89), "c90".parse());
+    assert_eq!(Ok(Standard::C99), "c99".parse());
+    assert_eq!(Ok(Standard::C11), "c11".parse());
+    assert_eq!(Ok(Standard::C17), "c17".parse());
+    assert_eq!(Ok(Standard::C17), "c18".parse());
+    assert_eq!(Ok(Standard::C23), "c23".parse());
+    assert_eq!(Ok(Standard::C23), "c2x".parse());
+    assert_eq!(
+        Err("unrecognized standard 'gnu11'".to_string()),
+        "gnu11".parse::<Standard>()
+    );
+}
+
+#[test]
+fn test_standard_order() {
+    assert!(Standard::C89 < Standard::C99);
+    assert!(Standard::C99 < Standard::C11);
+    assert!(Standard::C11 < Standard::C17);
+    assert!(Standard::C17 < Standard::C23);
+    assert_eq!(Standard::C11, Standard::default());
+}
+
+#[test]
+fn test_version() {
+    assert_eq!(None, Standard::C89.version());
+    assert_eq!(Some("199901L"), Standard::C99.version());
+    assert_eq!(Some("201112L"), Standard::C11.version());
+    assert_eq!(Some("201710L"), Standard::C17.version());
+    assert_eq!(Some("202311L"), Standard::C23.version());
+}
+
+#[test]
+fn test_option_round_trips() {
+    for standard in [
+        Standard::C89,
+        Standard::C99,
+        Standard::C11,
+        Standard::C17,
+        Standard::C23,
+    ] {
+        assert_eq!(Ok(standard), standard.option().parse());
+    }
+}
+
+#[test]
+fn test_features() {
+    let c89 = LangOptions::new(Standard::C89);
+    assert!(!c89.line_comments());
+    assert!(!c89.universal_character_names());
+
+    let c99 = LangOptions::new(Standard::C99);
+    assert!(c99.line_comments());
+    assert!(c99.universal_character_names());
+    assert!(c99.at_least(Standard::C99));
+    assert!(!c99.at_least(Standard::C11));
+}
+
+#[test]
+fn test_extension_severity() {
+    let mut lang = LangOptions::default();
+    assert_eq!(None, lang.extension_severity());
+    lang.pedantic = Pedantic::Warn;
+    assert_eq!(Some(Severity::Warning), lang.extension_severity());
+    lang.pedantic = Pedantic::Error;
+    assert_eq!(Some(Severity::Error), lang.extension_severity());
+}
@@ -0,0 +1,33 @@
+#![no_main]
+
+//! `cargo fuzz run lex` from lib/lexer, feeding arbitrary text to the lexer. Whatever it
+//! is given it mustn't panic, and the tokens it finds must be in order, must not overlap
+//! and must only leave whitespace and linemarkers between them
+
+use lang::{LangOptions, Standard};
+use libfuzzer_sys::fuzz_target;
+
+const STANDARDS: [Standard; 5] = [
+    Standard::C89,
+    Standard::C99,
+    Standard::C11,
+    Standard::C17,
+    Standard::C23,
+];
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&first, rest)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(rest) else {
+        return;
+    };
+    let lang = LangOptions {
+        standard: STANDARDS[first as usize % STANDARDS.len()],
+        ..Default::default()
+    };
+
+    if let Err(problem) = lexer::check_spans(input, &lang) {
+        panic!("{problem}");
+    }
+});
@@ -2,3 +2,84 @@ mod internal;
 
 pub use internal::char_literal_impl;
 pub use internal::CharLiteral;
+
+use crate::text::char_escape::char_esc_impl;
+use crate::text::hex_escape::hex_esc_impl;
+use crate::text::oct_escape::oct_esc_impl;
+use crate::text::univ_esc::univ_esc_impl;
+use crate::text::{text_state_impl_i16, text_state_impl_i32, text_state_impl_i8};
+use crate::text::{Encoding, TextState};
+use crate::LocationState;
+use crate::Token;
+
+/// consume a char literal from the start of `input` (which must be the opening `'`,
+/// any encoding prefix has already been stripped) returning the token and the number
+/// of chars consumed
+pub fn consume_char_literal(
+    location: &dyn LocationState,
+    input: &str,
+    encoding: Encoding,
+) -> (Token, usize) {
+    let (token, n) = match encoding {
+        Encoding::Char => {
+            let text = text_state_impl_i8(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+        Encoding::Utf8 => {
+            let text = text_state_impl_i8(input.chars().peekable());
+            let token = consume_with(location, text.as_ref());
+            let n = text.chars_consumed();
+            let spelling = input.chars().take(n).collect();
+            let code_units = text.get_output().len();
+            (utf8_char(location, token, code_units, spelling), n)
+        }
+        Encoding::Char16 => {
+            let text = text_state_impl_i16(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+        Encoding::Char32 | Encoding::WChar => {
+            let text = text_state_impl_i32(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+    };
+
+    (encoding.with_prefix(token), n)
+}
+
+/// C23 6.4.4.5, a `u8` character constant is an `unsigned char` and its value must be a
+/// single UTF-8 code unit
+fn utf8_char(
+    location: &dyn LocationState,
+    token: Token,
+    code_units: usize,
+    spelling: String,
+) -> Token {
+    match token {
+        Token::CharLit(_) if code_units > 1 => {
+            location.error(
+                "u8-char-too-large",
+                "character not encodable in a single code unit",
+            );
+            Token::Unknown(spelling)
+        }
+        Token::CharLit(v) => Token::CharLit_u8(v as u8 as i32),
+        t => t,
+    }
+}
+
+fn consume_with<C>(location: &dyn LocationState, text: &dyn TextState<Ch = C>) -> Token {
+    let hex_escape = hex_esc_impl(location, text);
+    let oct_escape = oct_esc_impl(location, text);
+    let univ_escape = univ_esc_impl(location, text);
+
+    let char_escape = char_esc_impl(
+        location,
+        text,
+        hex_escape.as_ref(),
+        oct_escape.as_ref(),
+        univ_escape.as_ref(),
+    );
+
+    let char_literal = char_literal_impl(location, text, char_escape.as_ref());
+    char_literal.consume_char_literal()
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests;
 
+use diagnostics::Diagnostic;
+
 use crate::text::char_escape::CharEsc;
 use crate::text::TextState;
 use crate::LocationState;
@@ -55,12 +57,8 @@ impl<'input, C> CharLiteral<C> for CharLiteralImpl<'input, C> {
         loop {
             match self.text.peek() {
                 Some('\n') | None => {
-                    eprintln!(
-                        "{}:{}:{} - error - unterminated char literal",
-                        self.location.f(),
-                        self.location.l(),
-                        self.location.c()
-                    );
+                    self.location
+                        .error("unterminated-char-literal", "unterminated char literal");
                     break self.text.emit_unknown();
                 }
                 _ => (),
@@ -74,7 +72,13 @@ impl<'input, C> CharLiteral<C> for CharLiteralImpl<'input, C> {
                 ),
                 Some('\'') => {
                     if self.text.seen_error() {
-                        eprintln!("{}:{}:{} - warn - seen error in processing, returning Unknown token not CharLit", self.location.f(), self.location.l(), self.location.c());
+                        self.location.report(
+                            Diagnostic::note(
+                                "invalid-token",
+                                "seen error in processing, returning Unknown token not CharLit",
+                            )
+                            .with_span(self.location.span()),
+                        );
                         break self.text.emit_unknown();
                     }
                     break self.text.emit_char_lit(self.location);
@@ -11,7 +11,7 @@ use crate::Token;
 use crate::Token::CharLit_u;
 
 fn actual(input: &str) -> Token {
-    let location = Box::new(TestLocation);
+    let location = Box::new(TestLocation::default());
     let text = text_state_impl_i16(input.chars().peekable());
 
     let hex_escape = hex_esc_impl::<i16>(location.as_ref(), text.as_ref());
@@ -11,7 +11,7 @@ use crate::Token;
 use crate::Token::CharLit_U;
 
 fn actual(input: &str) -> Token {
-    let location = Box::new(TestLocation);
+    let location = Box::new(TestLocation::default());
     let text = text_state_impl_i32(input.chars().peekable());
 
     let hex_escape = hex_esc_impl::<i32>(location.as_ref(), text.as_ref());
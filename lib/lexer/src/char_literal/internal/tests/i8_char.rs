@@ -10,7 +10,12 @@ use crate::Token;
 use crate::Token::CharLit;
 
 fn actual(input: &str) -> Token {
-    let location = Box::new(TestLocation);
+    actual_and_codes(input).0
+}
+
+/// the token along with the codes of any diagnostics reported while lexing it
+fn actual_and_codes(input: &str) -> (Token, Vec<&'static str>) {
+    let location = Box::new(TestLocation::default());
     let text = text_state_impl_i8(input.chars().peekable());
 
     let hex_escape = hex_esc_impl::<i8>(location.as_ref(), text.as_ref());
@@ -26,7 +31,9 @@ fn actual(input: &str) -> Token {
     );
 
     let char_lit = char_literal_impl(location.as_ref(), text.as_ref(), char_escape.as_ref());
-    char_lit.consume_char_literal()
+    let token = char_lit.consume_char_literal();
+    let codes = location.diagnostics().iter().map(|d| d.code()).collect();
+    (token, codes)
 }
 
 fn unknown_and_actual(input: &str) -> (Token, Token) {
@@ -55,6 +62,20 @@ fn test_i8_char_literal_empty() {
     assert_eq!(expected, actual)
 }
 
+#[test]
+fn test_i8_char_literal_empty_reported() {
+    let (_, codes) = actual_and_codes("''");
+
+    assert_eq!(vec!["empty-char-literal"], codes)
+}
+
+#[test]
+fn test_i8_char_literal_multichar_reported() {
+    let (_, codes) = actual_and_codes("'ab'");
+
+    assert_eq!(vec!["multichar"], codes)
+}
+
 #[test]
 fn test_i8_char_literals_unterminated_eoi() {
     let (expected, actual) = unknown_and_actual("'");
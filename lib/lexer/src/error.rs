@@ -0,0 +1,49 @@
+use std::{error, fmt};
+
+use diagnostics::{Diagnostic, ErrorKind, Severity, StageError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// the input had errors, which were also reported to the `Diagnostics` passed to `lex`
+    Diagnosed(Vec<Diagnostic>),
+}
+
+impl Error {
+    /// the errors among `reported`, if there were any
+    pub(crate) fn from_reported(reported: &[Diagnostic]) -> Option<Self> {
+        let errors: Vec<Diagnostic> = reported
+            .iter()
+            .filter(|d| d.severity() >= Severity::Error)
+            .cloned()
+            .collect();
+        if errors.is_empty() {
+            None
+        } else {
+            Some(Error::Diagnosed(errors))
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Diagnosed(errors) => write!(f, "lexing failed with {} error(s)", errors.len()),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl StageError for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Diagnosed(_) => ErrorKind::User,
+        }
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Error::Diagnosed(errors) => errors,
+        }
+    }
+}
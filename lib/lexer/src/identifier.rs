@@ -1,4 +1,25 @@
+mod extended;
 mod internal;
 
+pub use extended::{extended_continue, extended_start};
 pub use internal::identifier_impl;
+pub use internal::identifier_or_keyword;
 pub use internal::Identifier;
+
+use crate::text::text_state_impl_i8;
+use crate::text::univ_esc::univ_esc_impl;
+use crate::text::TextState;
+use crate::LocationState;
+use crate::Token;
+
+/// consume an identifier or keyword from the start of `input` returning the token and
+/// the number of chars consumed
+pub fn consume_identifier(location: &dyn LocationState, input: &str) -> (Token, usize) {
+    let text = text_state_impl_i8(input.chars().peekable());
+    let univ_escape = univ_esc_impl(location, text.as_ref());
+
+    let identifier = identifier_impl(location, text.as_ref(), univ_escape.as_ref());
+    let token = identifier.consume_identifier();
+
+    (token, text.chars_consumed())
+}
@@ -0,0 +1,85 @@
+use lang::{LangOptions, Standard};
+
+/// C11 Annex D.1, the ranges of characters allowed in an identifier
+const ALLOWED: &[(u32, u32)] = &[
+    (0x00a8, 0x00a8),
+    (0x00aa, 0x00aa),
+    (0x00ad, 0x00ad),
+    (0x00af, 0x00af),
+    (0x00b2, 0x00b5),
+    (0x00b7, 0x00ba),
+    (0x00bc, 0x00be),
+    (0x00c0, 0x00d6),
+    (0x00d8, 0x00f6),
+    (0x00f8, 0x00ff),
+    (0x0100, 0x167f),
+    (0x1681, 0x180d),
+    (0x180f, 0x1fff),
+    (0x200b, 0x200d),
+    (0x202a, 0x202e),
+    (0x203f, 0x2040),
+    (0x2054, 0x2054),
+    (0x2060, 0x206f),
+    (0x2070, 0x218f),
+    (0x2460, 0x24ff),
+    (0x2776, 0x2793),
+    (0x2c00, 0x2dff),
+    (0x2e80, 0x2fff),
+    (0x3004, 0x3007),
+    (0x3021, 0x302f),
+    (0x3031, 0x303f),
+    (0x3040, 0xd7ff),
+    (0xf900, 0xfd3d),
+    (0xfd40, 0xfdcf),
+    (0xfdf0, 0xfe44),
+    (0xfe47, 0xfffd),
+    (0x10000, 0x1fffd),
+    (0x20000, 0x2fffd),
+    (0x30000, 0x3fffd),
+    (0x40000, 0x4fffd),
+    (0x50000, 0x5fffd),
+    (0x60000, 0x6fffd),
+    (0x70000, 0x7fffd),
+    (0x80000, 0x8fffd),
+    (0x90000, 0x9fffd),
+    (0xa0000, 0xafffd),
+    (0xb0000, 0xbfffd),
+    (0xc0000, 0xcfffd),
+    (0xd0000, 0xdfffd),
+    (0xe0000, 0xefffd),
+];
+
+/// C11 Annex D.2, the ranges of characters (combining marks) not allowed to start an
+/// identifier
+const NOT_INITIAL: &[(u32, u32)] = &[
+    (0x0300, 0x036f),
+    (0x1dc0, 0x1dff),
+    (0x20d0, 0x20ff),
+    (0xfe20, 0xfe2f),
+];
+
+fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let u = c as u32;
+    ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&u))
+}
+
+/// whether `c`, from outside the basic character set, may start an identifier. C11 and
+/// C17 have the Annex D ranges, which we also accept for C99 rather than carry its own
+/// narrower list, C23 has XID_Start and C89 had no extended characters at all
+pub fn extended_start(c: char, lang: &LangOptions) -> bool {
+    match lang.standard {
+        Standard::C89 => false,
+        Standard::C23 => unicode_ident::is_xid_start(c),
+        _ => in_ranges(c, ALLOWED) && !in_ranges(c, NOT_INITIAL),
+    }
+}
+
+/// whether `c`, from outside the basic character set, may appear in an identifier after
+/// the first character
+pub fn extended_continue(c: char, lang: &LangOptions) -> bool {
+    match lang.standard {
+        Standard::C89 => false,
+        Standard::C23 => unicode_ident::is_xid_continue(c),
+        _ => in_ranges(c, ALLOWED),
+    }
+}
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests;
 
-use crate::{text::univ_esc::UnivEsc, text::TextState, LocationState, Token};
+use diagnostics::Diagnostic;
+use lang::Standard;
+
+use super::extended::{extended_continue, extended_start};
+use crate::{text::univ_esc::UnivEsc, text::TextState, LocationState, Symbol, Token};
 
 pub trait Identifier {
     fn consume_identifier(&self) -> Token;
@@ -33,6 +37,135 @@ impl<'iter> IdentifierImpl<'iter> {
             univ,
         }
     }
+
+    /// universal character names are allowed in identifiers, C89 didn't have them
+    fn ucns(&self) -> bool {
+        self.location.lang().universal_character_names()
+    }
+}
+
+/// `ident` as a token, a keyword if `location`'s standard has it as one
+pub fn identifier_or_keyword(location: &dyn LocationState, ident: &str) -> Token {
+    let keyword = match ident {
+        "alignas" => Token::KwAlignas,
+        "alignof" => Token::KwAlignof,
+        "auto" => Token::KwAuto,
+        "bool" => Token::KwBool,
+        "break" => Token::KwBreak,
+        "case" => Token::KwCase,
+        "char" => Token::KwChar,
+        "const" => Token::KwConst,
+        "constexpr" => Token::KwConstexpr,
+        "continue" => Token::KwContinue,
+        "default" => Token::KwDefault,
+        "do" => Token::KwDo,
+        "double" => Token::KwDouble,
+        "else" => Token::KwElse,
+        "enum" => Token::KwEnum,
+        "extern" => Token::KwExtern,
+        "false" => Token::KwFalse,
+        "float" => Token::KwFloat,
+        "for" => Token::KwFor,
+        "goto" => Token::KwGoto,
+        "if" => Token::KwIf,
+        "inline" => Token::KwInline,
+        "int" => Token::KwInt,
+        "long" => Token::KwLong,
+        "nullptr" => Token::KwNullptr,
+        "register" => Token::KwRegister,
+        "restrict" => Token::KwRestrict,
+        "return" => Token::KwReturn,
+        "short" => Token::KwShort,
+        "signed" => Token::KwSigned,
+        "sizeof" => Token::KwSizeof,
+        "static" => Token::KwStatic,
+        "static_assert" => Token::KwStatic_assert,
+        "struct" => Token::KwStruct,
+        "switch" => Token::KwSwitch,
+        "thread_local" => Token::KwThread_local,
+        "true" => Token::KwTrue,
+        "typedef" => Token::KwTypedef,
+        "typeof" => Token::KwTypeof,
+        "typeof_unqual" => Token::KwTypeof_unqual,
+        "union" => Token::KwUnion,
+        "unsigned" => Token::KwUnsigned,
+        "void" => Token::KwVoid,
+        "volatile" => Token::KwVolatile,
+        "while" => Token::KwWhile,
+        "_Alignas" => Token::Kw_Alignas,
+        "_Alignof" => Token::Kw_Alignof,
+        "_Atomic" => Token::Kw_Atomic,
+        "_BitInt" => Token::Kw_BitInt,
+        "_Bool" => Token::Kw_Bool,
+        "_Complex" => Token::Kw_Complex,
+        "_Generic" => Token::Kw_Generic,
+        "_Imaginary" => Token::Kw_Imaginary,
+        "_Noreturn" => Token::Kw_Noreturn,
+        "_Static_assert" => Token::Kw_Static_assert,
+        "_Thread_local" => Token::Kw_Thread_local,
+        // GNU extensions, spelt as reserved identifiers so there in every standard
+        "__asm" | "__asm__" => Token::Kw__asm__,
+        "__attribute" | "__attribute__" => Token::Kw__attribute__,
+        "__builtin_va_list" => Token::Kw__builtin_va_list,
+        "__extension__" => Token::Kw__extension__,
+        "__inline" | "__inline__" => Token::Kw__inline,
+        "__int128" => Token::Kw__int128,
+        "__label__" => Token::Kw__label__,
+        "__restrict" | "__restrict__" => Token::Kw__restrict,
+        "__typeof" | "__typeof__" => Token::Kw__typeof__,
+        _ => return Token::Identifier(Symbol::intern(ident)),
+    };
+    keyword_as_standard(location, ident, keyword)
+}
+
+/// `keyword` as the standard has it: a keyword from a later standard spelt as a
+/// reserved identifier (`_Bool`) is still a keyword but is reported with `-pedantic`,
+/// any other (`inline` in C89) is an ordinary identifier
+fn keyword_as_standard(location: &dyn LocationState, ident: &str, keyword: Token) -> Token {
+    let since = since(&keyword);
+    let standard = location.lang().standard;
+    if standard >= since {
+        keyword
+    } else if ident.starts_with('_') {
+        location.extension(
+            "pedantic",
+            &format!("ISO {standard} does not support '{ident}'"),
+        );
+        keyword
+    } else {
+        Token::Identifier(Symbol::intern(ident))
+    }
+}
+
+/// the standard which added `keyword`
+fn since(keyword: &Token) -> Standard {
+    match keyword {
+        Token::KwInline
+        | Token::KwRestrict
+        | Token::Kw_Bool
+        | Token::Kw_Complex
+        | Token::Kw_Imaginary => Standard::C99,
+        Token::Kw_Alignas
+        | Token::Kw_Alignof
+        | Token::Kw_Atomic
+        | Token::Kw_Generic
+        | Token::Kw_Noreturn
+        | Token::Kw_Static_assert
+        | Token::Kw_Thread_local => Standard::C11,
+        Token::KwAlignas
+        | Token::KwAlignof
+        | Token::KwBool
+        | Token::KwConstexpr
+        | Token::KwFalse
+        | Token::KwNullptr
+        | Token::KwStatic_assert
+        | Token::KwThread_local
+        | Token::KwTrue
+        | Token::KwTypeof
+        | Token::KwTypeof_unqual
+        | Token::Kw_BitInt => Standard::C23,
+        _ => Standard::C89,
+    }
 }
 
 impl<'iter> Identifier for IdentifierImpl<'iter> {
@@ -42,18 +175,26 @@ impl<'iter> Identifier for IdentifierImpl<'iter> {
                 self.text.push_char(c);
                 self.text.next();
             }
+            Some(c) if extended_start(c, self.location.lang()) => {
+                self.text.push_char(c);
+                self.text.next();
+            }
+            Some(c) if extended_continue(c, self.location.lang()) => {
+                self.location.error(
+                    "invalid-identifier-start",
+                    &format!("'{c}' is not valid at the start of an identifier"),
+                );
+                self.text.next();
+                self.text.report_error()
+            }
             Some('\\') => {
                 self.text.next();
                 match self.text.peek() {
-                    Some('u') => self.univ.consume_universal_short_identifier(),
-                    Some('U') => self.univ.consume_universal_long_identifier(),
+                    Some('u') if self.ucns() => self.univ.consume_universal_short_identifier(true),
+                    Some('U') if self.ucns() => self.univ.consume_universal_long_identifier(true),
                     _ => {
-                        eprintln!(
-                            "{}:{}:{} - error - stray \\ in program",
-                            self.location.f(),
-                            self.location.l(),
-                            self.location.c()
-                        );
+                        self.location
+                            .error("stray-backslash", "stray '\\' in program");
                         self.text.report_error()
                     }
                 }
@@ -73,18 +214,18 @@ impl<'iter> Identifier for IdentifierImpl<'iter> {
                     self.text.push_char(c);
                     self.text.next();
                 }
+                Some(c) if extended_continue(c, self.location.lang()) => {
+                    self.text.push_char(c);
+                    self.text.next();
+                }
                 Some('\\') => {
                     self.text.next();
                     match self.text.peek() {
-                        Some('u') => self.univ.consume_universal_short_identifier(),
-                        Some('U') => self.univ.consume_universal_long_identifier(),
+                        Some('u') if self.ucns() => self.univ.consume_universal_short_identifier(false),
+                        Some('U') if self.ucns() => self.univ.consume_universal_long_identifier(false),
                         _ => {
-                            eprintln!(
-                                "{}:{}:{} - error - stray '\' in program",
-                                self.location.f(),
-                                self.location.l(),
-                                self.location.c()
-                            );
+                            self.location
+                                .error("stray-backslash", "stray '\\' in program");
                             self.text.report_error()
                         }
                     }
@@ -94,57 +235,18 @@ impl<'iter> Identifier for IdentifierImpl<'iter> {
         };
 
         if self.text.seen_error() {
-            eprintln!("{}:{}:{} - warn - seen error in processing identifier, returning Unknown token not Identifier", self.location.f(), self.location.l(), self.location.c());
+            self.location.report(
+                Diagnostic::note(
+                    "invalid-token",
+                    "seen error in processing identifier, returning Unknown token not Identifier",
+                )
+                .with_span(self.location.span()),
+            );
             self.text.emit_unknown()
         } else {
             let ident_u8s = ident_i8s.iter().map(|i| *i as u8).collect();
             let ident = String::from_utf8(ident_u8s).expect("We should only have valid UTF-8 here");
-            match ident.as_str() {
-                "auto" => Token::KwAuto,
-                "break" => Token::KwBreak,
-                "case" => Token::KwCase,
-                "char" => Token::KwChar,
-                "const" => Token::KwConst,
-                "continue" => Token::KwContinue,
-                "default" => Token::KwDefault,
-                "do" => Token::KwDo,
-                "double" => Token::KwDouble,
-                "else" => Token::KwElse,
-                "enum" => Token::KwEnum,
-                "extern" => Token::KwExtern,
-                "float" => Token::KwFloat,
-                "for" => Token::KwFor,
-                "goto" => Token::KwGoto,
-                "if" => Token::KwIf,
-                "inline" => Token::KwInline,
-                "int" => Token::KwInt,
-                "long" => Token::KwLong,
-                "register" => Token::KwRegister,
-                "restrict" => Token::KwRestrict,
-                "return" => Token::KwReturn,
-                "short" => Token::KwShort,
-                "signed" => Token::KwSigned,
-                "sizeof" => Token::KwSizeof,
-                "static" => Token::KwStatic,
-                "struct" => Token::KwStruct,
-                "switch" => Token::KwSwitch,
-                "typedef" => Token::KwTypedef,
-                "union" => Token::KwUnion,
-                "unsigned" => Token::KwUnsigned,
-                "void" => Token::KwVoid,
-                "while" => Token::KwWhile,
-                "_Alignas" => Token::Kw_Alignas,
-                "_Alignof" => Token::Kw_Alignof,
-                "_Atomic" => Token::Kw_Atomic,
-                "_Bool" => Token::Kw_Bool,
-                "_Complex" => Token::Kw_Complex,
-                "_Generic" => Token::Kw_Generic,
-                "_Imaginary" => Token::Kw_Imaginary,
-                "_Noreturn" => Token::Kw_Noreturn,
-                "_Static_assert" => Token::Kw_Static_assert,
-                "_Thread_local" => Token::Kw_Thread_local,
-                _ => Token::Identifier(ident),
-            }
+            identifier_or_keyword(self.location, &ident)
         }
     }
 }
@@ -1,14 +1,21 @@
 use super::*;
 
+use lang::{LangOptions, Pedantic};
+
 use crate::tests::TestLocation;
 use crate::text::text_state_impl_i8;
 use crate::text::univ_esc::univ_esc_impl;
 
+use crate::Symbol;
 use crate::Token::Identifier;
 use crate::Token::Unknown;
 
 fn actual(input: &str) -> (Token, usize) {
-    let location = Box::new(TestLocation);
+    actual_as(input, LangOptions::default())
+}
+
+fn actual_as(input: &str, lang: LangOptions) -> (Token, usize) {
+    let location = Box::new(TestLocation::with_lang(lang));
     let text = text_state_impl_i8(input.chars().peekable());
     let univ_escape = univ_esc_impl::<i8>(location.as_ref(), text.as_ref());
 
@@ -346,6 +353,16 @@ fn test_kw_void() {
     assert_eq!(exp_sz, sz);
 }
 
+#[test]
+fn test_kw_volatile() {
+    let exp_token = Token::KwVolatile;
+    let exp_sz = 8;
+    let (actual, sz) = actual("volatile");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
 #[test]
 fn test_kw_while() {
     let exp_token = Token::KwWhile;
@@ -469,7 +486,7 @@ fn test_kw__Thread_local() {
 macro_rules! ascii_exp_and_actual {
     ($input: literal) => {
         (
-            (Identifier($input.to_string()), $input.len()),
+            (Identifier(Symbol::intern($input)), $input.len()),
             actual($input),
         )
     };
@@ -731,6 +748,14 @@ fn test_identifier_uppercase_void() {
     assert_eq!(exp_sz, act_sz);
 }
 
+#[test]
+fn test_identifier_uppercase_volatile() {
+    let ((exp_token, exp_sz), (act_token, act_sz)) = ascii_exp_and_actual!("VOLATILE");
+
+    assert_eq!(exp_token, act_token);
+    assert_eq!(exp_sz, act_sz);
+}
+
 #[test]
 fn test_identifier_uppercase_while() {
     let ((exp_token, exp_sz), (act_token, act_sz)) = ascii_exp_and_actual!("WHILE");
@@ -915,9 +940,9 @@ fn test_identifier_univ_escape_short_3digits() {
 
 #[test]
 fn test_identifier_univ_escape_short_4digits() {
-    let exp_token = Identifier("\u{00a0}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{00c0}"));
     let exp_sz = 6;
-    let (act_token, act_sz) = actual(r"\u00a0");
+    let (act_token, act_sz) = actual(r"\u00c0");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -925,9 +950,9 @@ fn test_identifier_univ_escape_short_4digits() {
 
 #[test]
 fn test_identifier_misleading_univ_escape_short_5digits() {
-    let exp_token = Identifier("\u{00a0}0".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{00c0}0"));
     let exp_sz = 7;
-    let (act_token, act_sz) = actual(r"\u00a00");
+    let (act_token, act_sz) = actual(r"\u00c00");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1007,9 +1032,9 @@ fn test_identifier_univ_escape_long_7digits() {
 
 #[test]
 fn test_identifier_univ_escape_long_8digits() {
-    let exp_token = Identifier("\u{00a0}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{00c0}"));
     let exp_sz = 10;
-    let (act_token, act_sz) = actual(r"\U000000a0");
+    let (act_token, act_sz) = actual(r"\U000000c0");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1017,9 +1042,9 @@ fn test_identifier_univ_escape_long_8digits() {
 
 #[test]
 fn test_identifier_misleading_univ_escape_long_9digits() {
-    let exp_token = Identifier("\u{00a0}0".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{00c0}0"));
     let exp_sz = 11;
-    let (act_token, act_sz) = actual(r"\U000000a00");
+    let (act_token, act_sz) = actual(r"\U000000c00");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1036,7 +1061,7 @@ fn test_identifier_forbidden_univ_escape_short_0x0000() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x0024() {
-    let exp_token = Identifier("\u{0024}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{0024}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u0024");
 
@@ -1045,20 +1070,18 @@ fn test_identifier_allowed_univ_escape_short_0x0024() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_short_0x0040() {
-    let exp_token = Identifier("\u{0040}".to_string());
+fn test_identifier_forbidden_univ_escape_short_0x0040() {
     let exp_sz = 6;
-    let (act_token, act_sz) = actual(r"\u0040");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\u0040");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_short_0x0060() {
-    let exp_token = Identifier("\u{0060}".to_string());
+fn test_identifier_forbidden_univ_escape_short_0x0060() {
     let exp_sz = 6;
-    let (act_token, act_sz) = actual(r"\u0060");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\u0060");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1074,10 +1097,9 @@ fn test_identifier_forbidden_univ_escape_short_0x009f() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_short_0x00a0() {
-    let exp_token = Identifier("\u{00a0}".to_string());
+fn test_identifier_forbidden_univ_escape_short_0x00a0() {
     let exp_sz = 6;
-    let (act_token, act_sz) = actual(r"\u00a0");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\u00a0");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1085,7 +1107,7 @@ fn test_identifier_allowed_univ_escape_short_0x00a0() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x02ff() {
-    let exp_token = Identifier("\u{02ff}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{02ff}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u02ff");
 
@@ -1113,7 +1135,7 @@ fn test_identifier_forbidden_univ_escape_short_0x036f() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x0370() {
-    let exp_token = Identifier("\u{0370}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{0370}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u0370");
 
@@ -1123,7 +1145,7 @@ fn test_identifier_allowed_univ_escape_short_0x0370() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x1dbf() {
-    let exp_token = Identifier("\u{1dbf}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{1dbf}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u1dbf");
 
@@ -1151,7 +1173,7 @@ fn test_identifier_forbidden_univ_escape_short_0x1dff() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x1e00() {
-    let exp_token = Identifier("\u{1e00}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{1e00}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u1e00");
 
@@ -1161,7 +1183,7 @@ fn test_identifier_allowed_univ_escape_short_0x1e00() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x20cf() {
-    let exp_token = Identifier("\u{20cf}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{20cf}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u20cf");
 
@@ -1189,7 +1211,7 @@ fn test_identifier_forbidden_univ_escape_short_0x20ff() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0x2100() {
-    let exp_token = Identifier("\u{2100}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{2100}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\u2100");
 
@@ -1199,7 +1221,7 @@ fn test_identifier_allowed_univ_escape_short_0x2100() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0xd7ff() {
-    let exp_token = Identifier("\u{d7ff}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{d7ff}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\ud7ff");
 
@@ -1226,10 +1248,9 @@ fn test_identifier_forbidden_univ_escape_short_0xdfff() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_short_0xe000() {
-    let exp_token = Identifier("\u{e000}".to_string());
+fn test_identifier_forbidden_univ_escape_short_0xe000() {
     let exp_sz = 6;
-    let (act_token, act_sz) = actual(r"\ue000");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\ue000");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1237,7 +1258,7 @@ fn test_identifier_allowed_univ_escape_short_0xe000() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0xfe1f() {
-    let exp_token = Identifier("\u{fe1f}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{fe1f}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\ufe1f");
 
@@ -1265,7 +1286,7 @@ fn test_identifier_forbidden_univ_escape_short_0xfe2f() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_short_0xfe30() {
-    let exp_token = Identifier("\u{fe30}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{fe30}"));
     let exp_sz = 6;
     let (act_token, act_sz) = actual(r"\ufe30");
 
@@ -1284,7 +1305,7 @@ fn test_identifier_forbidden_univ_escape_long_0x0000() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x0024() {
-    let exp_token = Identifier("\u{0024}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{0024}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U00000024");
 
@@ -1293,20 +1314,18 @@ fn test_identifier_allowed_univ_escape_long_0x0024() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_long_0x0040() {
-    let exp_token = Identifier("\u{0040}".to_string());
+fn test_identifier_forbidden_univ_escape_long_0x0040() {
     let exp_sz = 10;
-    let (act_token, act_sz) = actual(r"\U00000040");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\U00000040");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_long_0x0060() {
-    let exp_token = Identifier("\u{0060}".to_string());
+fn test_identifier_forbidden_univ_escape_long_0x0060() {
     let exp_sz = 10;
-    let (act_token, act_sz) = actual(r"\U00000060");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\U00000060");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1322,10 +1341,9 @@ fn test_identifier_forbidden_univ_escape_long_0x009f() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_long_0x00a0() {
-    let exp_token = Identifier("\u{00a0}".to_string());
+fn test_identifier_forbidden_univ_escape_long_0x00a0() {
     let exp_sz = 10;
-    let (act_token, act_sz) = actual(r"\U000000a0");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\U000000a0");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1333,7 +1351,7 @@ fn test_identifier_allowed_univ_escape_long_0x00a0() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x02ff() {
-    let exp_token = Identifier("\u{02ff}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{02ff}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U000002ff");
 
@@ -1361,7 +1379,7 @@ fn test_identifier_forbidden_univ_escape_long_0x036f() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x0370() {
-    let exp_token = Identifier("\u{0370}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{0370}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U00000370");
 
@@ -1371,7 +1389,7 @@ fn test_identifier_allowed_univ_escape_long_0x0370() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x1dbf() {
-    let exp_token = Identifier("\u{1dbf}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{1dbf}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U00001dbf");
 
@@ -1399,7 +1417,7 @@ fn test_identifier_forbidden_univ_escape_long_0x1dff() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x1e00() {
-    let exp_token = Identifier("\u{1e00}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{1e00}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U00001e00");
 
@@ -1409,7 +1427,7 @@ fn test_identifier_allowed_univ_escape_long_0x1e00() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x20cf() {
-    let exp_token = Identifier("\u{20cf}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{20cf}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U000020cf");
 
@@ -1437,7 +1455,7 @@ fn test_identifier_forbidden_univ_escape_long_0x20ff() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0x2100() {
-    let exp_token = Identifier("\u{2100}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{2100}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U00002100");
 
@@ -1447,7 +1465,7 @@ fn test_identifier_allowed_univ_escape_long_0x2100() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0xd7ff() {
-    let exp_token = Identifier("\u{d7ff}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{d7ff}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U0000d7ff");
 
@@ -1474,10 +1492,9 @@ fn test_identifier_forbidden_univ_escape_long_0xdfff() {
 }
 
 #[test]
-fn test_identifier_allowed_univ_escape_long_0xe000() {
-    let exp_token = Identifier("\u{e000}".to_string());
+fn test_identifier_forbidden_univ_escape_long_0xe000() {
     let exp_sz = 10;
-    let (act_token, act_sz) = actual(r"\U0000e000");
+    let (exp_token, act_token, act_sz) = unknown_and_actual(r"\U0000e000");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
@@ -1485,7 +1502,7 @@ fn test_identifier_allowed_univ_escape_long_0xe000() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0xfe1f() {
-    let exp_token = Identifier("\u{fe1f}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{fe1f}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U0000fe1f");
 
@@ -1513,10 +1530,480 @@ fn test_identifier_forbidden_univ_escape_long_0xfe2f() {
 
 #[test]
 fn test_identifier_allowed_univ_escape_long_0xfe30() {
-    let exp_token = Identifier("\u{fe30}".to_string());
+    let exp_token = Identifier(Symbol::intern("\u{fe30}"));
     let exp_sz = 10;
     let (act_token, act_sz) = actual(r"\U0000fe30");
 
     assert_eq!(exp_token, act_token);
     assert_eq!(exp_sz, act_sz);
 }
+
+#[test]
+fn test_kw_inline_is_identifier_in_c89() {
+    let exp_token = Identifier(Symbol::intern("inline"));
+    let exp_sz = 6;
+    let (actual, sz) = actual_as("inline", LangOptions::new(Standard::C89));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_restrict_is_identifier_in_c89() {
+    let exp_token = Identifier(Symbol::intern("restrict"));
+    let exp_sz = 8;
+    let (actual, sz) = actual_as("restrict", LangOptions::new(Standard::C89));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw__Bool_in_c89() {
+    let exp_token = Token::Kw_Bool;
+    let exp_sz = 5;
+    let (actual, sz) = actual_as("_Bool", LangOptions::new(Standard::C89));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw__Thread_local_in_c99_pedantic() {
+    let lang = LangOptions {
+        standard: Standard::C99,
+        pedantic: Pedantic::Warn,
+    };
+    let location = TestLocation::with_lang(lang);
+    let text = text_state_impl_i8("_Thread_local".chars().peekable());
+    let univ_escape = univ_esc_impl::<i8>(&location, text.as_ref());
+    let identifier = identifier_impl(&location, text.as_ref(), univ_escape.as_ref());
+
+    assert_eq!(Token::Kw_Thread_local, identifier.consume_identifier());
+    let diagnostics = location.diagnostics();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!("ISO C99 does not support '_Thread_local'", diagnostics[0].message());
+}
+
+#[test]
+fn test_kw_alignas_in_c23() {
+    let exp_token = Token::KwAlignas;
+    let exp_sz = 7;
+    let (actual, sz) = actual_as("alignas", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_alignof_in_c23() {
+    let exp_token = Token::KwAlignof;
+    let exp_sz = 7;
+    let (actual, sz) = actual_as("alignof", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_bool_in_c23() {
+    let exp_token = Token::KwBool;
+    let exp_sz = 4;
+    let (actual, sz) = actual_as("bool", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_constexpr_in_c23() {
+    let exp_token = Token::KwConstexpr;
+    let exp_sz = 9;
+    let (actual, sz) = actual_as("constexpr", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_false_in_c23() {
+    let exp_token = Token::KwFalse;
+    let exp_sz = 5;
+    let (actual, sz) = actual_as("false", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_nullptr_in_c23() {
+    let exp_token = Token::KwNullptr;
+    let exp_sz = 7;
+    let (actual, sz) = actual_as("nullptr", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_static_assert_in_c23() {
+    let exp_token = Token::KwStatic_assert;
+    let exp_sz = 13;
+    let (actual, sz) = actual_as("static_assert", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_thread_local_in_c23() {
+    let exp_token = Token::KwThread_local;
+    let exp_sz = 12;
+    let (actual, sz) = actual_as("thread_local", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_true_in_c23() {
+    let exp_token = Token::KwTrue;
+    let exp_sz = 4;
+    let (actual, sz) = actual_as("true", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_typeof_in_c23() {
+    let exp_token = Token::KwTypeof;
+    let exp_sz = 6;
+    let (actual, sz) = actual_as("typeof", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_typeof_unqual_in_c23() {
+    let exp_token = Token::KwTypeof_unqual;
+    let exp_sz = 13;
+    let (actual, sz) = actual_as("typeof_unqual", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw__BitInt_in_c23() {
+    let exp_token = Token::Kw_BitInt;
+    let exp_sz = 7;
+    let (actual, sz) = actual_as("_BitInt", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_bool_is_identifier_in_c17() {
+    let exp_token = Identifier(Symbol::intern("bool"));
+    let exp_sz = 4;
+    let (actual, sz) = actual_as("bool", LangOptions::new(Standard::C17));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_kw_nullptr_is_identifier_in_c11() {
+    let exp_token = Identifier(Symbol::intern("nullptr"));
+    let exp_sz = 7;
+    let (actual, sz) = actual("nullptr");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___asm() {
+    let exp_token = Token::Kw__asm__;
+    let exp_sz = 5;
+    let (actual, sz) = actual("__asm");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___asm__() {
+    let exp_token = Token::Kw__asm__;
+    let exp_sz = 7;
+    let (actual, sz) = actual("__asm__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___attribute() {
+    let exp_token = Token::Kw__attribute__;
+    let exp_sz = 11;
+    let (actual, sz) = actual("__attribute");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___attribute__() {
+    let exp_token = Token::Kw__attribute__;
+    let exp_sz = 13;
+    let (actual, sz) = actual("__attribute__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___builtin_va_list() {
+    let exp_token = Token::Kw__builtin_va_list;
+    let exp_sz = 17;
+    let (actual, sz) = actual("__builtin_va_list");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___extension__() {
+    let exp_token = Token::Kw__extension__;
+    let exp_sz = 13;
+    let (actual, sz) = actual("__extension__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___inline() {
+    let exp_token = Token::Kw__inline;
+    let exp_sz = 8;
+    let (actual, sz) = actual("__inline");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___inline__() {
+    let exp_token = Token::Kw__inline;
+    let exp_sz = 10;
+    let (actual, sz) = actual("__inline__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___int128() {
+    let exp_token = Token::Kw__int128;
+    let exp_sz = 8;
+    let (actual, sz) = actual("__int128");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___label__() {
+    let exp_token = Token::Kw__label__;
+    let exp_sz = 9;
+    let (actual, sz) = actual("__label__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___restrict() {
+    let exp_token = Token::Kw__restrict;
+    let exp_sz = 10;
+    let (actual, sz) = actual("__restrict");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___restrict__() {
+    let exp_token = Token::Kw__restrict;
+    let exp_sz = 12;
+    let (actual, sz) = actual("__restrict__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___typeof() {
+    let exp_token = Token::Kw__typeof__;
+    let exp_sz = 8;
+    let (actual, sz) = actual("__typeof");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___typeof__() {
+    let exp_token = Token::Kw__typeof__;
+    let exp_sz = 10;
+    let (actual, sz) = actual("__typeof__");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___attribute___in_c89() {
+    let exp_token = Token::Kw__attribute__;
+    let exp_sz = 13;
+    let (actual, sz) = actual_as("__attribute__", LangOptions::new(Standard::C89));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_kw___int128_t_is_identifier() {
+    let exp_token = Identifier(Symbol::intern("__int128_t"));
+    let exp_sz = 10;
+    let (actual, sz) = actual("__int128_t");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8() {
+    let exp_token = Identifier(Symbol::intern("café"));
+    let exp_sz = 4;
+    let (actual, sz) = actual("café");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8_start() {
+    let exp_token = Identifier(Symbol::intern("αβ_1"));
+    let exp_sz = 4;
+    let (actual, sz) = actual("αβ_1 ");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8_ends_at_disallowed() {
+    // U+00A0 NO-BREAK SPACE isn't allowed in an identifier
+    let exp_token = Identifier(Symbol::intern("a"));
+    let exp_sz = 1;
+    let (actual, sz) = actual("a\u{a0}b");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8_combining_not_initial() {
+    let exp_token = Identifier(Symbol::intern("e\u{301}"));
+    let exp_sz = 2;
+    let (actual, sz) = actual("e\u{301}");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8_combining_initial() {
+    let exp_sz = 2;
+    let (exp_token, act_token, act_sz) = unknown_and_actual("\u{301}e");
+
+    assert_eq!(exp_token, act_token);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_identifier_univ_escape_combining_not_initial() {
+    let exp_token = Identifier(Symbol::intern("e\u{300}"));
+    let exp_sz = 7;
+    let (actual, sz) = actual(r"e\u0300");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_utf8_in_c89() {
+    let exp_token = Identifier(Symbol::intern("caf"));
+    let exp_sz = 3;
+    let (actual, sz) = actual_as("café", LangOptions::new(Standard::C89));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_superscript_in_c11() {
+    // U+00B2 SUPERSCRIPT TWO is in C11 Annex D
+    let exp_token = Identifier(Symbol::intern("x²"));
+    let exp_sz = 2;
+    let (actual, sz) = actual("x²");
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_superscript_in_c23() {
+    // but isn't XID_Continue
+    let exp_token = Identifier(Symbol::intern("x"));
+    let exp_sz = 1;
+    let (actual, sz) = actual_as("x²", LangOptions::new(Standard::C23));
+
+    assert_eq!(exp_token, actual);
+    assert_eq!(exp_sz, sz);
+}
+
+#[test]
+fn test_identifier_univ_escape_xid_in_c23() {
+    // U+0E33 THAI CHARACTER SARA AM is in Annex D but isn't XID_Start
+    let lang = LangOptions::new(Standard::C23);
+    let (actual, sz) = actual_as(r"\u0e33", lang);
+
+    assert_eq!(Unknown(r"\u0e33".to_string()), actual);
+    assert_eq!(6, sz);
+    let (actual, _) = actual_as(r"\u0e33", LangOptions::default());
+    assert_eq!(Identifier(Symbol::intern("\u{e33}")), actual);
+}
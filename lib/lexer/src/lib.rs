@@ -1,58 +1,151 @@
 mod char_literal;
+mod error;
 mod identifier;
 mod numeric_literal;
+mod source_map;
 mod string_literal;
+mod symbol;
 mod text;
+mod token_stream;
 mod tokens;
 
 #[cfg(test)]
 pub mod tests;
 
-pub use tokens::Token;
+pub use error::Error;
+pub use source_map::{Location, SourceMap, Span, SystemHeader};
+pub use symbol::Symbol;
+pub use token_stream::TokenStream;
+pub use tokens::{Token, F80};
+
+use std::rc::Rc;
+
+use diagnostics::{Diagnostic, Diagnostics, Expansion, Expansions, Severity};
+use lang::{LangOptions, Standard};
+use text::Encoding;
 
 trait LocationState {
     fn f(&self) -> &str;
     fn l(&self) -> u32;
     fn c(&self) -> usize;
+
+    fn lang(&self) -> &LangOptions;
+
+    /// whether we're in a system header, where only errors are reported by default
+    fn in_system_header(&self) -> bool {
+        false
+    }
+
+    fn report(&self, diagnostic: Diagnostic);
+
+    fn span(&self) -> diagnostics::Span {
+        diagnostics::Span::point(self.f(), self.l(), self.c())
+    }
+
+    fn error(&self, code: &'static str, message: &str) {
+        self.report(Diagnostic::error(code, message).with_span(self.span()))
+    }
+
+    fn warning(&self, code: &'static str, message: &str) {
+        self.report(Diagnostic::warning(code, message).with_span(self.span()))
+    }
+
+    /// something the standard doesn't have which we accept anyway, only reported if
+    /// `-pedantic` asked for it. As in gcc, `-pedantic-errors` only warns about system
+    /// headers (which are then only shown with `-Wsystem-headers`)
+    fn extension(&self, code: &'static str, message: &str) {
+        if let Some(severity) = self.lang().extension_severity() {
+            let severity = match self.in_system_header() {
+                true => severity.min(Severity::Warning),
+                false => severity,
+            };
+            self.report(Diagnostic::new(severity, code, message).with_span(self.span()))
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct LocatedToken<'a> {
-    locations: Vec<Location<'a>>,
+pub struct LocatedToken {
     token: Token,
+    span: Span,
+    expansion: Option<Rc<Expansion>>,
 }
 
-impl<'a> LocatedToken<'a> {
-    pub fn locations(&'a self) -> &'a Vec<Location<'a>> {
-        &self.locations
+impl LocatedToken {
+    pub fn token(&self) -> &Token {
+        &self.token
     }
 
-    pub fn current_location(&'a self) -> &Location<'a> {
-        &self.locations.last().expect("must have a location")
+    /// where the token is in the preprocessed unit, the `SourceMap` says where that is in
+    /// the source
+    pub fn span(&self) -> Span {
+        self.span
     }
 
-    pub fn token(&'a self) -> &Token {
-        &self.token
+    /// the macro expansion the token came out of, if the preprocessor said it came from one
+    pub fn expansion(&self) -> Option<&Expansion> {
+        self.expansion.as_deref()
     }
 }
 
+/// the tokens of a preprocessed unit along with the map from their spans back to the
+/// source they came from
 #[derive(Debug, PartialEq, Clone)]
-pub struct Location<'a> {
-    line: u32,
-    column: usize,
-    file: &'a str,
-    input: &'a str,
+pub struct Lexed<'a> {
+    tokens: Vec<LocatedToken>,
+    source_map: SourceMap<'a>,
+}
+
+impl<'a> Lexed<'a> {
+    pub fn tokens(&self) -> &[LocatedToken] {
+        &self.tokens
+    }
+
+    pub fn source_map(&self) -> &SourceMap<'a> {
+        &self.source_map
+    }
+
+    /// a line for each token with its kind, spelling and location, followed by the
+    /// `#include`s which led to it, much as `clang -Xclang -dump-tokens` prints them
+    pub fn dump(&self) -> String {
+        let mut dump = String::new();
+        for token in &self.tokens {
+            let location = self.source_map.location(token.span);
+            dump += &format!(
+                "{} '{}'\tLoc=<{}:{}:{}>",
+                token.token.name(),
+                token.token,
+                location.file(),
+                location.line(),
+                location.column()
+            );
+            for (file, line) in self.source_map.included_from(token.span) {
+                dump += &format!(" included from <{file}:{line}>");
+            }
+            dump.push('\n');
+        }
+        dump
+    }
 }
 
 mod state {
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
     use super::process_linemarker;
+    use super::Diagnostic;
+    use super::Diagnostics;
+    use super::Expansion;
+    use super::Expansions;
+    use super::LangOptions;
     use super::LocatedToken;
-    use super::Location;
+    use super::LocationState;
+    use super::SourceMap;
+    use super::Span;
+    use super::SystemHeader;
     use super::Token;
 
     pub trait Lex {
-        fn len(&self) -> usize;
-
         fn is_empty(&self) -> bool;
 
         fn peek(&self) -> char;
@@ -62,18 +155,24 @@ mod state {
 
     #[derive(Debug, PartialEq, Clone)]
     pub struct LexStruct<'input> {
+        /// all of the input, `input` is what is left of it
+        source: &'input str,
         input: &'input str,
+        expansions: Option<&'input Expansions>,
         column: usize,
         file_line: u32,
         file_name: &'input str,
-        seen_error: bool,
-        located_tokens: Vec<LocatedToken<'input>>,
-        file_hist: Vec<Location<'input>>,
+        system_header: SystemHeader,
+        diagnostics: &'input Diagnostics,
+        lang: LangOptions,
+        /// lexed but not yet taken by `next_lexed`
+        located_tokens: VecDeque<LocatedToken>,
+        source_map: SourceMap<'input>,
     }
 
     impl<'input> super::LocationState for LexStruct<'input> {
         fn f(&self) -> &'input str {
-            &self.file_name
+            self.file_name
         }
 
         fn l(&self) -> u32 {
@@ -83,13 +182,29 @@ mod state {
         fn c(&self) -> usize {
             self.column
         }
-    }
 
-    impl<'input> Lex for LexStruct<'input> {
-        fn len(&self) -> usize {
-            self.input.len()
+        fn lang(&self) -> &LangOptions {
+            &self.lang
+        }
+
+        fn in_system_header(&self) -> bool {
+            self.system_header.is_system()
+        }
+
+        fn report(&self, diagnostic: Diagnostic) {
+            let diagnostic = match self.expansion() {
+                Some(expansion) => diagnostic.with_expansion(&expansion),
+                None => diagnostic,
+            };
+            let diagnostic = match self.in_system_header() {
+                true => diagnostic.in_system_header(),
+                false => diagnostic,
+            };
+            self.diagnostics.report(diagnostic)
         }
+    }
 
+    impl<'input> Lex for LexStruct<'input> {
         fn is_empty(&self) -> bool {
             self.input.is_empty()
         }
@@ -97,143 +212,174 @@ mod state {
         fn peek(&self) -> char {
             self.input
                 .chars()
-                .nth(0)
+                .next()
                 .expect("Expect at least one char in input")
         }
 
         fn skip(&mut self, n: usize) {
+            self.column += self.input[..n].chars().count();
             self.input = &self.input[n..];
         }
     }
 
     impl<'a> LexStruct<'a> {
-        pub fn new(input: &'a str) -> Self {
+        pub fn new(
+            input: &'a str,
+            expansions: Option<&'a Expansions>,
+            lang: &LangOptions,
+            diagnostics: &'a Diagnostics,
+        ) -> Self {
             // we assume that the input starts with a gcc preprocessor linemarker (e.g.):
             // ^# 0 "some_input.c"$
             // see: https://gcc.gnu.org/onlinedocs/cpp/Preprocessor-Output.html
             //
-            let eol = input.find('\n').or(Some(0)).unwrap();
-            let linemarker = &input[..=eol];
-
-            let ((file_line, file_name, _), skip) = match process_linemarker(linemarker) {
-                Err(e) => ((0, "TOPLEVEL", (false, false, false, false)), 0),
-                Ok(o) => (o, linemarker.len() + 1),
+            let linemarker = match input.find('\n') {
+                Some(eol) => &input[..=eol],
+                None => input,
             };
 
-            let input = &input[skip..];
-            let column = 1usize;
-            let located_tokens: Vec<LocatedToken> = vec![];
-            let file_hist = vec![Location {
-                line: file_line,
-                column,
-                file: file_name,
-                input: linemarker,
-            }];
+            let ((file_line, file_name, (_, _, three, four)), skip) = match process_linemarker(linemarker) {
+                Err(_) => ((0, "TOPLEVEL", (false, false, false, false)), 0),
+                Ok(o) => (o, linemarker.len()),
+            };
+            let system_header = SystemHeader::from_flags(three, four);
 
             LexStruct {
-                input,
-                column,
+                source: input,
+                input: &input[skip..],
+                expansions,
+                column: 1,
                 file_line,
                 file_name,
-                seen_error: false,
-                located_tokens,
-                file_hist,
+                system_header,
+                diagnostics,
+                lang: *lang,
+                located_tokens: VecDeque::new(),
+                source_map: SourceMap::new(input, file_name, file_line, skip, system_header),
             }
         }
 
+        /// how far into the source the input is
+        fn offset(&self) -> usize {
+            self.input.as_ptr() as usize - self.source.as_ptr() as usize
+        }
+
+        /// the expansion of the token starting where the input is
+        fn expansion(&self) -> Option<Rc<Expansion>> {
+            self.expansions?.get(self.offset()).cloned()
+        }
+
+        /// the char `n` bytes into the input if that byte is ASCII, which is all the
+        /// lookahead for punctuators and literal prefixes needs
         pub fn peek_nth(&self, n: usize) -> Option<char> {
-            self.input.chars().nth(n)
+            self.input
+                .as_bytes()
+                .get(n)
+                .filter(|b| b.is_ascii())
+                .map(|b| *b as char)
         }
 
         pub fn consume(&mut self, n: usize, token: Token) {
-            let mut locations = self.file_hist.clone();
-            locations.push(Location {
-                line: self.file_line,
-                column: self.column,
-                file: &self.file_name,
-                input: &self.input[..n],
+            let offset = self.offset();
+            let expansion = self.expansion();
+            self.located_tokens.push_back(LocatedToken {
+                token,
+                span: Span::new(offset, offset + n),
+                expansion,
             });
-            self.located_tokens.push(LocatedToken { locations, token });
 
+            // columns count chars, as the caret under the source line does
+            self.column += self.input[..n].chars().count();
             self.input = &self.input[n..];
-            self.column += n;
         }
 
-        pub fn error(&mut self, n: usize) {
-            self.seen_error = true;
-            let mut locations = self.file_hist.clone();
-            locations.push(Location {
-                line: self.file_line,
-                column: self.column,
-                file: &self.file_name,
-                input: &self.input[..n],
-            });
-            self.located_tokens.push(LocatedToken {
-                locations,
-                token: Token::Unknown(self.input[..n].to_string()),
-            });
+        /// as `consume` but `n` counts chars rather than bytes, which is what the
+        /// literal / identifier state machines report via `chars_consumed()`
+        pub fn consume_chars(&mut self, n: usize, token: Token) {
+            let n = self.byte_len(n);
+            self.consume(n, token)
+        }
 
-            self.input = &self.input[n..];
-            self.column += n;
+        fn byte_len(&self, n_chars: usize) -> usize {
+            match self.input.char_indices().nth(n_chars) {
+                Some((n, _)) => n,
+                None => self.input.len(),
+            }
+        }
+
+        /// a character which can't start any token, report it and skip over it. As in gcc
+        /// it is shown as itself if it is printable ASCII, otherwise as its first byte in
+        /// octal
+        pub fn stray(&mut self, n: usize) {
+            let stray = &self.input[..n];
+            let shown = match stray.as_bytes() {
+                [b] if b.is_ascii_graphic() => stray.to_string(),
+                [b, ..] => format!("\\{b:o}"),
+                [] => unreachable!("a stray char is at least one byte"),
+            };
+            self.error("stray-char", &format!("stray '{shown}' in program"));
+            let token = Token::Unknown(self.input[..n].to_string());
+            self.consume(n, token)
         }
 
         pub fn newline(&mut self) {
+            self.source_map.newline(self.offset());
             self.file_line += 1;
             self.column = 1;
             self.input = &self.input[1..];
         }
 
-        pub fn enter_file(&mut self, file: &'a str, line: u32, skip: usize) {
-            self.file_hist.push(Location {
-                line: self.file_line,
-                column: self.column,
-                file: &self.file_name,
-                input: &self.input[..skip],
-            });
-
+        /// skip the `skip` bytes of a linemarker, after which we're at `line` of `file`
+        fn relocate(&mut self, file: &'a str, line: u32, system: SystemHeader, skip: usize) {
             self.file_name = file;
             self.file_line = line;
+            self.system_header = system;
             self.column = 1;
             self.input = &self.input[skip..];
         }
 
-        pub fn exit_file(&mut self, file: &'a str, line: u32, skip: usize) {
-            self.file_hist.pop().expect("Expect at least one file");
+        pub fn enter_file(&mut self, file: &'a str, line: u32, system: SystemHeader, skip: usize) {
+            let from_line = self.file_line;
+            self.relocate(file, line, system, skip);
+            self.source_map.enter(self.offset(), file, line, from_line, system);
+        }
 
-            self.file_name = file;
-            self.file_line = line;
-            self.column = 1;
-            self.input = &self.input[skip..];
+        pub fn exit_file(&mut self, file: &'a str, line: u32, system: SystemHeader, skip: usize) {
+            self.relocate(file, line, system, skip);
+            self.source_map.exit(self.offset(), file, line, system);
         }
 
-        pub fn move_in_file(&mut self, line: u32, skip: usize) {
-            self.file_line = line;
-            self.column = 1;
-            self.input = &self.input[skip..];
+        /// a linemarker without flag 1 or 2, which can also rename the current file (e.g.
+        /// when leaving `<built-in>` / `<command-line>` for the main file)
+        pub fn move_in_file(&mut self, file: &'a str, line: u32, system: SystemHeader, skip: usize) {
+            self.relocate(file, line, system, skip);
+            self.source_map.move_to(self.offset(), file, line, system);
         }
 
         pub fn input(&self) -> &'a str {
-            &self.input
+            self.input
         }
 
-        pub fn file_name(&self) -> &str {
-            &self.file_name
+        pub fn column(&self) -> usize {
+            self.column
         }
 
-        pub fn file_line(&self) -> u32 {
-            self.file_line
+        pub fn diagnostics(&self) -> &'a Diagnostics {
+            self.diagnostics
         }
 
-        pub fn column(&self) -> usize {
-            self.column
+        /// the oldest token lexed which hasn't been taken yet
+        pub fn next_lexed(&mut self) -> Option<LocatedToken> {
+            self.located_tokens.pop_front()
         }
 
-        pub fn seen_error(&self) -> bool {
-            self.seen_error
+        /// where the tokens lexed so far came from
+        pub fn source_map(&self) -> &SourceMap<'a> {
+            &self.source_map
         }
 
-        pub fn tokens(&self) -> Vec<LocatedToken<'a>> {
-            self.located_tokens.clone()
+        pub fn into_source_map(self) -> SourceMap<'a> {
+            self.source_map
         }
     }
 }
@@ -250,7 +396,7 @@ fn process_linemarker(linemarker: &str) -> Result<(u32, &str, (bool, bool, bool,
     let line_str = &processing[..line_end];
     let line: u32 = line_str
         .parse()
-        .or_else(|_| Err(format!("Expected to parse u32 from line_str: {line_str}")))?;
+        .map_err(|_| format!("Expected to parse u32 from line_str: {line_str}"))?;
 
     let processing = &processing[line_end..];
     let quote_start = processing.find('"').ok_or(format!(
@@ -280,350 +426,479 @@ fn process_linemarker(linemarker: &str) -> Result<(u32, &str, (bool, bool, bool,
         None => (false, processing),
     };
 
-    if processing.len() > 2 {
-        eprintln!("Unexpected trailing input on linemarker: [{processing}]");
+    if !processing.trim_end().is_empty() {
+        return Err(format!(
+            "Unexpected trailing input on linemarker: [{processing}]"
+        ));
     }
     Ok((line, file, (one, two, three, four)))
 }
 
-fn lex_char_literal<'a>(state: &'a mut LexStruct) {
-    todo!()
-    // match char_literal::consume_char_literal(state) {
-    //     Ok((t, n)) => state.consume(n, t),
-    //     Err(()) => panic!("{}:{}:{} - FATAL - we haven't found a character literal: {}", state.file_name(), state.file_name(), state.column(), &state.input()[..10]),
-    // }
+fn lex_numeric_literal(state: &mut LexStruct) {
+    // a decimal constant without a suffix is by far the commonest, so doesn't need the DFA
+    let input = state.input();
+    let n = input.bytes().position(|b| !b.is_ascii_digit()).unwrap_or(input.len());
+    let plain = n > 0 && (!input.starts_with('0') || n == 1);
+    let follows = input.as_bytes().get(n);
+    if plain && !follows.is_some_and(|b| b.is_ascii_alphanumeric() || b"_.'".contains(b)) {
+        let token = numeric_literal::parse_dec_int_no_suffix(state, &input[..n]);
+        state.consume(n, token);
+        return;
+    }
+
+    let (token, n) = {
+        let numeric = numeric_literal::numeric_state_impl(state.input().chars().peekable());
+        let numeric_literal = numeric_literal::numeric_literal_impl(state, numeric.as_ref());
+        let token = numeric_literal.consume_numeric_literal();
+        (token, numeric.chars_consumed())
+    };
+    state.consume_chars(n, token);
 }
 
-fn lex_identifier<'a>(state: &'a mut LexStruct) {
-    #[cfg(none)]
-    match identifier::consume_identifier(state) {
-        Ok((t, n)) => state.consume(n, t),
-        Err(()) => panic!(
-            "{}:{}:{} - FATAL - we haven't found an identifier or keyword: {}",
-            state.file_name(),
-            state.file_name(),
-            state.column(),
-            &state.input()[..10]
-        ),
+/// the encoding prefix, its length and the opening quote if the input is at the start of
+/// a prefixed string or char literal, `u8` char literals are new in C23
+fn literal_prefix(state: &LexStruct) -> Option<(Encoding, usize, char)> {
+    let utf8_char = state.lang().at_least(Standard::C23);
+    match (state.peek_nth(0), state.peek_nth(1), state.peek_nth(2)) {
+        (Some('u'), Some('8'), Some('"')) => Some((Encoding::Utf8, 2, '"')),
+        (Some('u'), Some('8'), Some('\'')) if utf8_char => Some((Encoding::Utf8, 2, '\'')),
+        (Some('u'), Some(q @ ('"' | '\'')), _) => Some((Encoding::Char16, 1, q)),
+        (Some('U'), Some(q @ ('"' | '\'')), _) => Some((Encoding::Char32, 1, q)),
+        (Some('L'), Some(q @ ('"' | '\'')), _) => Some((Encoding::WChar, 1, q)),
+        _ => None,
     }
-    todo!()
 }
 
-pub fn lex<'a>(input: &'a str) -> Result<Vec<LocatedToken<'a>>, ()> {
-    let mut state = LexStruct::new(input);
-    println!("state: {state:?}");
-    while !state.is_empty() {
-        let c = state.peek();
-
-        match c {
-            '#' => {
-                // either a linemarker (which we can deal with)
-                // or a #pragma or an #ident (which we ?ignore?)
-                let hashline = match state.input().find('\n') {
-                    Some(n) => &state.input()[..n + 1],
-                    None => {
-                        eprintln!(
-                            "{}:{}:{} - warn - Found hashline without terminating newline",
-                            state.file_name(),
-                            state.file_line(),
-                            state.column()
-                        );
-                        state.input()
-                    }
-                };
-                match process_linemarker(hashline) {
-                    Ok((line, file, (one, two, _, _))) => {
-                        if one {
-                            println!("enter: {file} at {line}");
-                            state.enter_file(file, line, hashline.len());
-                        } else if two {
-                            println!("exit: from {} into {} at {}", state.file_name(), file, line);
-                            state.exit_file(file, line, hashline.len());
-                        } else {
-                            println!("move: {} at {}", file, line);
-                            state.move_in_file(line, hashline.len());
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "{}:{}:{} - warn - Didn't match a linemarker with err: [{}]",
-                            state.file_name(),
-                            state.file_line(),
-                            state.column(),
-                            e
-                        );
-                        eprintln!(
-                            "{}:{}:{} - warn - linemarker input was: [{}]",
-                            state.file_name(),
-                            state.file_line(),
-                            state.column(),
-                            hashline
-                        );
-                        state.skip(hashline.len());
+fn lex_string_literal(state: &mut LexStruct, encoding: Encoding, prefix_len: usize) {
+    let body = &state.input()[prefix_len..];
+    let (token, n) = string_literal::consume_string_literal(state, body, encoding);
+    state.consume_chars(prefix_len + n, token);
+}
+
+fn lex_char_literal(state: &mut LexStruct, encoding: Encoding, prefix_len: usize) {
+    let body = &state.input()[prefix_len..];
+    let (token, n) = char_literal::consume_char_literal(state, body, encoding);
+    state.consume_chars(prefix_len + n, token);
+}
+
+fn lex_identifier(state: &mut LexStruct) {
+    let input = state.input();
+    let n = input
+        .bytes()
+        .position(|b| !(b.is_ascii_alphanumeric() || b == b'_'))
+        .unwrap_or(input.len());
+    match input.as_bytes().get(n) {
+        // an escape or an extended character, which the state machine deals with
+        Some(b'\\' | 0x80..) => {
+            let (token, n) = identifier::consume_identifier(state, input);
+            state.consume_chars(n, token);
+        }
+        _ => {
+            let token = identifier::identifier_or_keyword(state, &input[..n]);
+            state.consume(n, token);
+        }
+    }
+}
+
+/// the tokens of the preprocessed `input`, with `lang` deciding which spellings are
+/// keywords and which features of later standards are reported
+pub fn lex<'a>(
+    input: &'a str,
+    lang: &LangOptions,
+    diagnostics: &'a Diagnostics,
+) -> Result<Lexed<'a>, Error> {
+    let (lexed, errors) = lex_internal(input, None, lang, diagnostics);
+    errors.map(|()| lexed)
+}
+
+/// as `lex`, with `expansions` saying which macro expansion each token of `input` came out
+/// of so the tokens (and anything reported about them) carry it
+pub fn lex_with_expansions<'a>(
+    input: &'a str,
+    expansions: &'a Expansions,
+    lang: &LangOptions,
+    diagnostics: &'a Diagnostics,
+) -> Result<Lexed<'a>, Error> {
+    let (lexed, errors) = lex_recovering(input, expansions, lang, diagnostics);
+    errors.map(|()| lexed)
+}
+
+/// as `lex_with_expansions`, but the tokens are returned whether or not the input had
+/// errors. Each error is reported and lexing resumes after it (with the bad text as an
+/// `Unknown` token), so later stages can carry on and report errors of their own
+pub fn lex_recovering<'a>(
+    input: &'a str,
+    expansions: &'a Expansions,
+    lang: &LangOptions,
+    diagnostics: &'a Diagnostics,
+) -> (Lexed<'a>, Result<(), Error>) {
+    lex_internal(input, Some(expansions), lang, diagnostics)
+}
+
+/// the invariant the property tests and the fuzz target check, that whatever `input` is
+/// the tokens found in it are in order, aren't empty or overlapping, and leave nothing but
+/// whitespace and the linemarkers the lexer skips between them. Returns what is wrong
+#[doc(hidden)]
+pub fn check_spans(input: &str, lang: &LangOptions) -> Result<(), String> {
+    let diagnostics = Diagnostics::new();
+    let mut end = 0;
+    for token in TokenStream::new(input, lang, &diagnostics) {
+        let span = token.span();
+        if span.start() < end {
+            return Err(format!("{token:?} overlaps the token before it"));
+        }
+        if span.start() >= span.end() {
+            return Err(format!("{token:?} is empty"));
+        }
+        if span.end() > input.len() {
+            return Err(format!("{token:?} runs past the input"));
+        }
+        if !input.is_char_boundary(span.start()) || !input.is_char_boundary(span.end()) {
+            return Err(format!("{token:?} isn't on char boundaries"));
+        }
+        check_skipped(input, end, span.start())?;
+        end = span.end();
+    }
+    check_skipped(input, end, input.len())
+}
+
+/// the text of `input` from `start` to `end` which no token covers is all whitespace or
+/// linemarkers
+fn check_skipped(input: &str, start: usize, end: usize) -> Result<(), String> {
+    for (i, c) in input[start..end].char_indices() {
+        let line = input[..start + i].rfind('\n').map_or(0, |n| n + 1);
+        if !c.is_whitespace() && !input[line..].starts_with('#') {
+            return Err(format!("{c:?} skipped at {}", start + i));
+        }
+    }
+    Ok(())
+}
+
+/// lex whatever starts at the input, which may be whitespace or a linemarker as well as
+/// a token (or two, for `..`)
+fn lex_step(state: &mut LexStruct) {
+    let c = state.peek();
+
+    match c {
+        '#' if state.column() == 1 => {
+            // either a linemarker (which we can deal with)
+            // or a #pragma or an #ident (which we ?ignore?)
+            let hashline = match state.input().find('\n') {
+                Some(n) => &state.input()[..n + 1],
+                None => {
+                    state.warning(
+                        "hashline-no-newline",
+                        "found hashline without terminating newline",
+                    );
+                    state.input()
+                }
+            };
+            match process_linemarker(hashline) {
+                Ok((line, file, (one, two, three, four))) => {
+                    let system = SystemHeader::from_flags(three, four);
+                    if one {
+                        state.enter_file(file, line, system, hashline.len());
+                    } else if two {
+                        state.exit_file(file, line, system, hashline.len());
+                    } else {
+                        state.move_in_file(file, line, system, hashline.len());
                     }
                 }
+                Err(e) => {
+                    state.report(
+                        Diagnostic::warning(
+                            "invalid-linemarker",
+                            "ignoring unrecognised hashline",
+                        )
+                        .with_span(state.span())
+                        .with_note(&e)
+                        .with_note(&format!(
+                            "hashline was: [{}]",
+                            hashline.trim_end_matches('\n')
+                        )),
+                    );
+                    // leave the newline for the '\n' arm so the line count stays correct
+                    state.skip(hashline.trim_end_matches('\n').len());
+                }
             }
-            '\n' => {
-                state.newline();
+        }
+        '\n' => {
+            state.newline();
+        }
+        '\t' | '\r' | ' ' => {
+            // non-newline whitespace
+            let whitespaces = match state.input().find(|c| c != '\t' && c != '\r' && c != ' ') {
+                Some(n) => n,
+                None => {
+                    state.warning("trailing-whitespace", "trailing whitespace at end of file");
+                    state.input().len()
+                }
+            };
+            state.skip(whitespaces);
+        }
+        'L' | 'u' | 'U' if literal_prefix(state).is_some() => {
+            // prefixed string or char literal
+            match literal_prefix(state).expect("We just checked for a prefix") {
+                (encoding, prefix_len, '"') => {
+                    lex_string_literal(state, encoding, prefix_len)
+                }
+                (encoding, prefix_len, _) => lex_char_literal(state, encoding, prefix_len),
             }
-            '\t' | '\r' | ' ' => {
-                // non-newline whitespace
-                let whitespaces = match state.input().find(|c| c != '\t' && c != '\r' && c != ' ') {
-                    Some(n) => n,
-                    None => {
-                        eprintln!(
-                            "{}:{}:{} - warn trailing whitespace at end of file",
-                            state.file_name(),
-                            state.file_line(),
-                            state.column()
-                        );
-                        state.input().len()
+        }
+        'a'..='z' | 'A'..='Z' | '_' | '\\' => lex_identifier(state),
+        '0' => {
+            // octal or hex numeric literal
+            lex_numeric_literal(state)
+        }
+        '1'..='9' => {
+            // decimal numeric literal
+            lex_numeric_literal(state)
+        }
+        '.' => {
+            // one of
+            // decimal numeric float literal: .0 .1 etc.
+            // or . ...
+            match state.peek_nth(1) {
+                Some(c) => match c {
+                    '0'..='9' => {
+                        // decimal float literal
+                        lex_numeric_literal(state)
                     }
-                };
-                state.skip(whitespaces);
-            }
-            'a'..='z' | 'A'..='Z' | '_' | '\\' => lex_identifier(&mut state),
-            '0' => {
-                // octal or hex numeric literal
-                todo!()
-            }
-            '1'..='9' => {
-                // decimal numeric literal
-                todo!()
-            }
-            '.' => {
-                // one of
-                // decimal numeric float literal: .0 .1 etc.
-                // or . ...
-                match state.peek_nth(1) {
-                    Some(c) => match c {
-                        '0'..='9' => {
-                            // decimal float literal
-                            todo!()
-                        }
-                        '.' => {
-                            // either two dots or an ellipsis
-                            match state.peek_nth(2) {
-                                Some(d) => match d {
-                                    '0'..='9' => {
-                                        // dot then a decimal float literal, but for simplicity emit the dot
-                                        // and then go back to the start
-                                        state.consume(1, Token::Dot)
-                                    }
-                                    '.' => {
-                                        // ellipsis
-                                        state.consume(3, Token::Ellipsis)
-                                    }
-                                    _ => {
-                                        // two dots
-                                        state.consume(1, Token::Dot);
-                                        state.consume(1, Token::Dot);
-                                    }
-                                },
-                                None => {
-                                    // EOF but we still emit
+                    '.' => {
+                        // either two dots or an ellipsis
+                        match state.peek_nth(2) {
+                            Some(d) => match d {
+                                '0'..='9' => {
+                                    // dot then a decimal float literal, but for simplicity emit the dot
+                                    // and then go back to the start
+                                    state.consume(1, Token::Dot)
+                                }
+                                '.' => {
+                                    // ellipsis
+                                    state.consume(3, Token::Ellipsis)
+                                }
+                                _ => {
+                                    // two dots
                                     state.consume(1, Token::Dot);
                                     state.consume(1, Token::Dot);
                                 }
+                            },
+                            None => {
+                                // EOF but we still emit
+                                state.consume(1, Token::Dot);
+                                state.consume(1, Token::Dot);
                             }
                         }
-                        _ => state.consume(1, Token::Dot),
-                    },
-                    None => {
-                        // EOF but still emit
-                        state.consume(1, Token::Dot)
                     }
+                    _ => state.consume(1, Token::Dot),
+                },
+                None => {
+                    // EOF but still emit
+                    state.consume(1, Token::Dot)
                 }
             }
-            '\'' => {
-                // char literal
-                lex_char_literal(&mut state);
-            }
-            '\"' => {
-                // string literal
-                todo!()
-            }
-            '&' => {
-                // ampersand
-                // one of & && &=
-                match state.peek_nth(1) {
-                    Some('&') => state.consume(2, Token::AmpAmp),
-                    Some('=') => state.consume(2, Token::AmpEql),
-                    _ => state.consume(1, Token::Amp),
-                }
-            }
-            '!' => {
-                // bang
-                // one of ! !=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::BangEql),
-                    _ => state.consume(1, Token::Bang),
-                }
-            }
-            '^' => {
-                // caret
-                // one of ^ ^=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::CaretEql),
-                    _ => state.consume(1, Token::Caret),
-                }
-            }
-            ':' => {
-                // colon
-                // one of : :>
-                // :> is a compat for ]
-                match state.peek_nth(1) {
-                    Some('>') => state.consume(2, Token::RSquare),
-                    _ => state.consume(1, Token::Colon),
-                }
-            }
-            ',' => {
-                // comma
-                // always a ,
-                state.consume(1, Token::Comma)
-            }
-            '-' => {
-                // dash
-                // one of - -- -= ->
-                match state.peek_nth(1) {
-                    Some('-') => state.consume(2, Token::DashDash),
-                    Some('=') => state.consume(2, Token::DashEql),
-                    Some('>') => state.consume(2, Token::DashGTh),
-                    _ => state.consume(1, Token::Dash),
-                }
-            }
-            '=' => {
-                // equal
-                // one of = ==
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::EqlEql),
-                    _ => state.consume(1, Token::Eql),
-                }
-            }
-            '/' => {
-                // fslash
-                // one of / /=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::FSlEql),
-                    _ => state.consume(1, Token::FSl),
-                }
-            }
-            '>' => {
-                // gthan
-                // one of > >= >> >>=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::GThEql),
-                    Some('>') => match state.peek_nth(2) {
-                        Some('=') => state.consume(3, Token::GThGThEql),
-                        _ => state.consume(2, Token::GThGTh),
-                    },
-                    _ => state.consume(1, Token::GTh),
-                }
-            }
-            '{' => {
-                // lbrace
-                // always a {
-                state.consume(1, Token::LBrace)
-            }
-            '[' => {
-                // lsquare
-                // always a [
-                state.consume(1, Token::LSquare)
-            }
-            '(' => {
-                // lparen
-                // always a (
-                state.consume(1, Token::LParen)
+        }
+        '\'' => {
+            // char literal
+            lex_char_literal(state, Encoding::Char, 0)
+        }
+        '\"' => {
+            // string literal
+            lex_string_literal(state, Encoding::Char, 0)
+        }
+        '&' => {
+            // ampersand
+            // one of & && &=
+            match state.peek_nth(1) {
+                Some('&') => state.consume(2, Token::AmpAmp),
+                Some('=') => state.consume(2, Token::AmpEql),
+                _ => state.consume(1, Token::Amp),
             }
-            '<' => {
-                // lthan
-                // one of < <: <= << <<= <%
-                // <: is a compat for [
-                // <% is a compat for {
-                match state.peek_nth(1) {
-                    Some(':') => state.consume(2, Token::LSquare),
-                    Some('=') => state.consume(2, Token::LThEql),
-                    Some('>') => match state.peek_nth(2) {
-                        Some('=') => state.consume(3, Token::LThLThEql),
-                        _ => state.consume(2, Token::LThLTh),
-                    },
-                    Some('%') => state.consume(2, Token::LBrace),
-                    _ => state.consume(1, Token::LTh),
-                }
+        }
+        '!' => {
+            // bang
+            // one of ! !=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::BangEql),
+                _ => state.consume(1, Token::Bang),
             }
-            '%' => {
-                // pct
-                // one of % %= %>
-                // %> is a compat for }
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::PctEql),
-                    Some('>') => state.consume(2, Token::RBrace),
-                    _ => state.consume(1, Token::Pct),
-                }
+        }
+        '^' => {
+            // caret
+            // one of ^ ^=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::CaretEql),
+                _ => state.consume(1, Token::Caret),
             }
-            '|' => {
-                // pipe
-                // one of | || |=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::PipeEql),
-                    Some('|') => state.consume(2, Token::PipePipe),
-                    _ => state.consume(1, Token::Pipe),
-                }
+        }
+        ':' => {
+            // colon
+            // one of : :>
+            // :> is a compat for ]
+            match state.peek_nth(1) {
+                Some('>') => state.consume(2, Token::RSquare),
+                _ => state.consume(1, Token::Colon),
             }
-            '+' => {
-                // plus
-                // one of + += ++
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::PlusEql),
-                    Some('+') => state.consume(2, Token::PlusPlus),
-                    _ => state.consume(1, Token::Plus),
-                }
+        }
+        ',' => {
+            // comma
+            // always a ,
+            state.consume(1, Token::Comma)
+        }
+        '-' => {
+            // dash
+            // one of - -- -= ->
+            match state.peek_nth(1) {
+                Some('-') => state.consume(2, Token::DashDash),
+                Some('=') => state.consume(2, Token::DashEql),
+                Some('>') => state.consume(2, Token::DashGTh),
+                _ => state.consume(1, Token::Dash),
             }
-            '?' => {
-                // quest
-                // always a ?
-                state.consume(1, Token::Question)
+        }
+        '=' => {
+            // equal
+            // one of = ==
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::EqlEql),
+                _ => state.consume(1, Token::Eql),
             }
-            '}' => {
-                // rbrace
-                // always a ]
-                state.consume(1, Token::RBrace)
+        }
+        '/' => {
+            // fslash
+            // one of / /=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::FSlEql),
+                _ => state.consume(1, Token::FSl),
             }
-            ')' => {
-                // rparen
-                // always a )
-                state.consume(1, Token::RParen)
+        }
+        '>' => {
+            // gthan
+            // one of > >= >> >>=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::GThEql),
+                Some('>') => match state.peek_nth(2) {
+                    Some('=') => state.consume(3, Token::GThGThEql),
+                    _ => state.consume(2, Token::GThGTh),
+                },
+                _ => state.consume(1, Token::GTh),
             }
-            ']' => {
-                // rsquare
-                // always a ]
-                state.consume(1, Token::RSquare)
+        }
+        '{' => {
+            // lbrace
+            // always a {
+            state.consume(1, Token::LBrace)
+        }
+        '[' => {
+            // lsquare
+            // always a [
+            state.consume(1, Token::LSquare)
+        }
+        '(' => {
+            // lparen
+            // always a (
+            state.consume(1, Token::LParen)
+        }
+        '<' => {
+            // lthan
+            // one of < <: <= << <<= <%
+            // <: is a compat for [
+            // <% is a compat for {
+            match state.peek_nth(1) {
+                Some(':') => state.consume(2, Token::LSquare),
+                Some('=') => state.consume(2, Token::LThEql),
+                Some('<') => match state.peek_nth(2) {
+                    Some('=') => state.consume(3, Token::LThLThEql),
+                    _ => state.consume(2, Token::LThLTh),
+                },
+                Some('%') => state.consume(2, Token::LBrace),
+                _ => state.consume(1, Token::LTh),
             }
-            ';' => {
-                // semi
-                // always a ;
-                state.consume(1, Token::Semi)
+        }
+        '%' => {
+            // pct
+            // one of % %= %>
+            // %> is a compat for }
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::PctEql),
+                Some('>') => state.consume(2, Token::RBrace),
+                _ => state.consume(1, Token::Pct),
             }
-            '*' => {
-                // star
-                // one of * *=
-                match state.peek_nth(1) {
-                    Some('=') => state.consume(2, Token::StarEql),
-                    _ => state.consume(1, Token::Plus),
-                }
+        }
+        '|' => {
+            // pipe
+            // one of | || |=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::PipeEql),
+                Some('|') => state.consume(2, Token::PipePipe),
+                _ => state.consume(1, Token::Pipe),
             }
-            '~' => {
-                // tilde
-                // always a ~
-                state.consume(1, Token::Tilde)
+        }
+        '+' => {
+            // plus
+            // one of + += ++
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::PlusEql),
+                Some('+') => state.consume(2, Token::PlusPlus),
+                _ => state.consume(1, Token::Plus),
             }
-            _ => {
-                println!("unhandled: {}", c);
-                state.error(1)
+        }
+        '?' => {
+            // quest
+            // always a ?
+            state.consume(1, Token::Question)
+        }
+        '}' => {
+            // rbrace
+            // always a ]
+            state.consume(1, Token::RBrace)
+        }
+        ')' => {
+            // rparen
+            // always a )
+            state.consume(1, Token::RParen)
+        }
+        ']' => {
+            // rsquare
+            // always a ]
+            state.consume(1, Token::RSquare)
+        }
+        ';' => {
+            // semi
+            // always a ;
+            state.consume(1, Token::Semi)
+        }
+        '*' => {
+            // star
+            // one of * *=
+            match state.peek_nth(1) {
+                Some('=') => state.consume(2, Token::StarEql),
+                _ => state.consume(1, Token::Star),
             }
         }
+        '~' => {
+            // tilde
+            // always a ~
+            state.consume(1, Token::Tilde)
+        }
+        c if identifier::extended_continue(c, state.lang()) => lex_identifier(state),
+        _ => state.stray(c.len_utf8()),
     }
+}
 
-    println!("done");
-    Ok(state.tokens())
+fn lex_internal<'a>(
+    input: &'a str,
+    expansions: Option<&'a Expansions>,
+    lang: &LangOptions,
+    diagnostics: &'a Diagnostics,
+) -> (Lexed<'a>, Result<(), Error>) {
+    let already_reported = diagnostics.diagnostics().len();
+    let mut stream = TokenStream::from_state(LexStruct::new(input, expansions, lang, diagnostics));
+    let tokens = stream.by_ref().collect();
+    let source_map = stream.into_source_map();
+
+    let errors = match Error::from_reported(&diagnostics.diagnostics()[already_reported..]) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    };
+    (Lexed { tokens, source_map }, errors)
 }
@@ -3,4 +3,5 @@ mod internal;
 pub use internal::NumericLiteral;
 pub use internal::NumericState;
 pub use internal::numeric_literal_impl;
-pub use internal::numeric_state_impl;
\ No newline at end of file
+pub use internal::numeric_state_impl;
+pub use internal::parse_dec_int_no_suffix;
\ No newline at end of file
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests;
 
+mod f80;
+
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::iter::Peekable;
@@ -11,8 +13,11 @@ use std::str::FromStr;
 use hexf_parse::parse_hexf32;
 use hexf_parse::parse_hexf64;
 
+use lang::Standard;
+
 use crate::Token;
 use crate::LocationState;
+use crate::tokens::F80;
 
 pub trait NumericLiteral {
     fn consume_numeric_literal(&self) -> Token;
@@ -20,13 +25,9 @@ pub trait NumericLiteral {
 
 pub trait NumericState {
     fn peek(&self) -> Option<char>;
+    fn peek_nth(&self, n: usize) -> Option<char>;
     fn next(&self) -> Option<char>;
 
-    fn emit_unknown(&self) -> Token;
-
-    fn report_error(&self);
-    fn seen_error(&self) -> bool;
-
     fn chars_consumed(&self) -> usize;
 }
 
@@ -46,7 +47,6 @@ pub fn numeric_state_impl<'iter>(
 struct NumericLiteralImpl<'iter> {
     location: &'iter dyn LocationState,
     numeric: &'iter dyn NumericState,
-    debug: bool,
 }
 
 impl<'iter> NumericLiteralImpl<'iter> {
@@ -54,217 +54,204 @@ impl<'iter> NumericLiteralImpl<'iter> {
         location: &'iter dyn LocationState,
         numeric: &'iter dyn NumericState,
     ) -> NumericLiteralImpl<'iter> {
-        NumericLiteralImpl{location, numeric, debug: true}
+        NumericLiteralImpl{location, numeric}
     }
-}
 
-fn parse_dec_int_no_suffix(loc: &dyn LocationState, seen: &str) -> Token {
-    let parsed = u128::from_str_radix(seen, 10);
-    match parsed {
-        Ok(u) => {
-            println!("parsed ok: {u}");
-            if (i64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of i64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as i64;
-            if (i32::MIN as i64 <= u) && (u <= i32::MAX as i64) {
-                println!("IntLitI32 as {} <= {} <= {}", i32::MIN, u, i32::MAX);
-                Token::IntLitI32(u as i32)
-            } else {
-                println!("IntLitI64 as {} < {} || {} < {}", u, i32::MIN, i32::MAX, u);
-                Token::IntLitI64(u)
-            }
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            Token::Unknown(seen.to_string())
+    /// C23 6.4.4, a `'` between two digits of the sequence `dfa` is reading separates them
+    fn separator(&self, dfa: &dyn NumericDfa, previous: Option<char>) -> bool {
+        if !self.location.lang().at_least(Standard::C23) {
+            return false;
         }
+        let Some(radix) = dfa.digits() else {
+            return false;
+        };
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_digit(radix));
+        is_digit(previous) && is_digit(self.numeric.peek_nth(1))
     }
 }
 
-fn parse_dec_int_u_suffix(loc: &dyn LocationState, seen: &str, u: &str) -> Token {
-    let parsed = u128::from_str_radix(seen, 10);
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
+/// the C types an integer constant can have
+#[derive(Debug, Clone, Copy)]
+enum IntType {
+    Int,
+    UInt,
+    Long,
+    ULong,
+    LongLong,
+    ULongLong,
+}
 
-            if u <= u32::MAX as u64 {
-                Token::IntLitU32(u as u32)
-            } else{
-                Token::IntLitU64(u as u64)
-            }
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = seen.to_string() + u;
-            Token::Unknown(value)
+impl IntType {
+    fn max(self) -> u128 {
+        match self {
+            IntType::Int => i32::MAX as u128,
+            IntType::UInt => u32::MAX as u128,
+            IntType::Long | IntType::LongLong => i64::MAX as u128,
+            IntType::ULong | IntType::ULongLong => u64::MAX as u128,
         }
     }
-}
 
-fn parse_dec_int_l_suffix(loc: &dyn LocationState, seen: &str, l: &str) -> Token {
-    let parsed = u128::from_str_radix(seen, 10);
-    match parsed {
-        Ok(u) => {
-            if (i64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of i64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as i64;
-            Token::IntLitI64(u as i64)
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = seen.to_string() + l;
-            Token::Unknown(value)
+    fn token(self, value: u128) -> Token {
+        match self {
+            IntType::Int => Token::IntLitInt(value as i32),
+            IntType::UInt => Token::IntLitUInt(value as u32),
+            IntType::Long => Token::IntLitLong(value as i64),
+            IntType::ULong => Token::IntLitULong(value as u64),
+            IntType::LongLong => Token::IntLitLongLong(value as i64),
+            IntType::ULongLong => Token::IntLitULongLong(value as u64),
         }
     }
 }
 
-fn parse_dec_int_lu_suffix(loc: &dyn LocationState, seen: &str, suff_1: &str, suff_2: &str) -> Token {
-    let parsed = u128::from_str_radix(seen, 10);
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
-            Token::IntLitU64(u)
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = seen.to_string() + suff_1 + suff_1;
-            Token::Unknown(value)
-        }
+/// the types an integer constant may have (C11 6.4.4.1), it has the first of them which can
+/// represent its value. Octal and hexadecimal constants may be unsigned without a `u`
+fn int_candidates(decimal: bool, unsigned: bool, longs: usize) -> &'static [IntType] {
+    use IntType::*;
+    match (decimal, unsigned, longs) {
+        (true, false, 0) => &[Int, Long, LongLong],
+        (false, false, 0) => &[Int, UInt, Long, ULong, LongLong, ULongLong],
+        (_, true, 0) => &[UInt, ULong, ULongLong],
+        (true, false, 1) => &[Long, LongLong],
+        (false, false, 1) => &[Long, ULong, LongLong, ULongLong],
+        (_, true, 1) => &[ULong, ULongLong],
+        (true, false, _) => &[LongLong],
+        (false, false, _) => &[LongLong, ULongLong],
+        (_, true, _) => &[ULongLong],
     }
 }
 
-fn int_hex_or_oct_no_suffix(loc: &dyn LocationState, pre: &str, seen: &str, parsed: Result<u128, ParseIntError>) -> Token {
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
-            if u <= i32::MAX as u64 {
-                Token::IntLitI32(u as i32)
-            } else if u <= u32::MAX as u64 {
-                Token::IntLitU32(u as u32)
-            } else if u <= i64::MAX as u64 {
-                Token::IntLitI64(u as i64)
-            } else {
-                Token::IntLitU64(u as u64)
-            }
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = pre.to_string() + seen;
+fn int_literal(loc: &dyn LocationState, decimal: bool, pre: &str, seen: &str, suffix: &str, parsed: Result<u128, ParseIntError>) -> Token {
+    let unsigned = suffix.contains(['u', 'U']);
+    let longs = suffix.matches(['l', 'L']).count();
+    let candidates = int_candidates(decimal, unsigned, longs);
+    let typed = parsed.ok().and_then(|value| {
+        candidates.iter().find(|ty| value <= ty.max()).map(|ty| ty.token(value))
+    });
+    match typed {
+        Some(token) => token,
+        None => {
+            let value = pre.to_string() + seen + suffix;
+            loc.error("integer-too-large", &format!("integer constant {} is too large for its type", value));
             Token::Unknown(value)
         }
     }
 }
 
-fn int_hex_or_oct_l_suffix(loc: &dyn LocationState, pre: &str, seen: &str, l: &str, parsed: Result<u128, ParseIntError>) -> Token {
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
+pub fn parse_dec_int_no_suffix(loc: &dyn LocationState, seen: &str) -> Token {
+    let parsed = u128::from_str_radix(seen, 10);
+    int_literal(loc, true, "", seen, "", parsed)
+}
 
-            if u <= i64::MAX as u64 {
-                Token::IntLitI64(u as i64)
-            } else {
-                Token::IntLitU64(u)
-            }
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = pre.to_string() + seen + l;
-            Token::Unknown(value)
-        }
-    }
+fn parse_dec_int_u_suffix(loc: &dyn LocationState, seen: &str, u: &str) -> Token {
+    let parsed = u128::from_str_radix(seen, 10);
+    int_literal(loc, true, "", seen, u, parsed)
 }
 
-fn int_hex_or_oct_lu_suffix(loc: &dyn LocationState, pre: &str, seen: &str, suff_1: &str, suff_2: &str, parsed: Result<u128, ParseIntError>) -> Token {
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
-            Token::IntLitU64(u as u64)
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = pre.to_string() + seen + suff_1 + suff_2;
-            Token::Unknown(value)
-        }
-    }
+fn parse_dec_int_l_suffix(loc: &dyn LocationState, seen: &str, l: &str) -> Token {
+    let parsed = u128::from_str_radix(seen, 10);
+    int_literal(loc, true, "", seen, l, parsed)
 }
 
-fn int_hex_or_oct_u_suffix(loc: &dyn LocationState, pre: &str, seen: &str, u: &str, parsed: Result<u128, ParseIntError>) -> Token {
-    match parsed {
-        Ok(u) => {
-            if (u64::MAX as u128) < u {
-                eprintln!("{}:{}:{} - warn - value outside range of u64 will be truncated", loc.f(), loc.l(), loc.c());
-            }
-            let u = u as u64;
-            if u <= u32::MAX as u64 {
-                Token::IntLitU32(u as u32)
-            } else {
-                Token::IntLitU64(u)
-            }
-        }
-        Err(e) => {
-            eprintln!("{}:{}:{} - error - value {} cannot be parsed as a u128?: {}", loc.f(), loc.l(), loc.c(), seen, e);
-            let value = pre.to_string() + seen + u;
-            Token::Unknown(value)
-        }
-    }
+fn parse_dec_int_lu_suffix(loc: &dyn LocationState, seen: &str, suff_1: &str, suff_2: &str) -> Token {
+    let parsed = u128::from_str_radix(seen, 10);
+    int_literal(loc, true, "", seen, &(suff_1.to_string() + suff_2), parsed)
 }
 
 fn parse_hex_int_no_suffix(loc: &dyn LocationState, pre: &str, seen: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 16);
-    int_hex_or_oct_no_suffix(loc, pre, seen, parsed)
+    int_literal(loc, false, pre, seen, "", parsed)
 }
 
 fn parse_hex_int_l_suffix(loc: &dyn LocationState, pre: &str, seen: &str, l: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 16);
-    int_hex_or_oct_l_suffix(loc, pre, seen, l, parsed)
+    int_literal(loc, false, pre, seen, l, parsed)
 }
 
 fn parse_hex_int_u_suffix(loc: &dyn LocationState, pre: &str, seen: &str, u: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 16);
-    int_hex_or_oct_u_suffix(loc, pre, seen, u, parsed)
-
+    int_literal(loc, false, pre, seen, u, parsed)
 }
 
 fn parse_hex_int_lu_suffix(loc: &dyn LocationState, pre: &str, seen: &str, suff_1: &str, suff_2: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 16);
-    int_hex_or_oct_lu_suffix(loc, pre, seen, suff_1, suff_2, parsed)
+    int_literal(loc, false, pre, seen, &(suff_1.to_string() + suff_2), parsed)
 }
 
 fn parse_oct_int_no_suffix(loc: &dyn LocationState, seen: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 8);
-    int_hex_or_oct_no_suffix(loc, "", seen, parsed)
+    int_literal(loc, false, "", seen, "", parsed)
 }
 
 fn parse_oct_int_l_suffix(loc: &dyn LocationState, seen: &str, l: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 8);
-    int_hex_or_oct_l_suffix(loc, "", seen, l, parsed)
+    int_literal(loc, false, "", seen, l, parsed)
 }
 
 fn parse_oct_int_u_suffix(loc: &dyn LocationState, seen: &str, u: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 8);
-    int_hex_or_oct_u_suffix(loc, "", seen, u, parsed)
+    int_literal(loc, false, "", seen, u, parsed)
 }
 
 fn parse_oct_int_lu_suffix(loc: &dyn LocationState, seen: &str, suff_1: &str, suff_2: &str) -> Token {
     let parsed = u128::from_str_radix(seen, 8);
-    int_hex_or_oct_lu_suffix(loc, "", seen, suff_1, suff_2, parsed)
+    int_literal(loc, false, "", seen, &(suff_1.to_string() + suff_2), parsed)
+}
+
+/// binary constants are new in C23, before that they are a GNU extension
+fn binary_constant(loc: &dyn LocationState) {
+    if !loc.lang().at_least(Standard::C23) {
+        loc.extension("pedantic", "binary constants are a C23 feature or GCC extension");
+    }
+}
+
+fn parse_bin_int_no_suffix(loc: &dyn LocationState, pre: &str, seen: &str) -> Token {
+    binary_constant(loc);
+    let parsed = u128::from_str_radix(seen, 2);
+    int_literal(loc, false, pre, seen, "", parsed)
+}
+
+fn parse_bin_int_l_suffix(loc: &dyn LocationState, pre: &str, seen: &str, l: &str) -> Token {
+    binary_constant(loc);
+    let parsed = u128::from_str_radix(seen, 2);
+    int_literal(loc, false, pre, seen, l, parsed)
+}
+
+fn parse_bin_int_u_suffix(loc: &dyn LocationState, pre: &str, seen: &str, u: &str) -> Token {
+    binary_constant(loc);
+    let parsed = u128::from_str_radix(seen, 2);
+    int_literal(loc, false, pre, seen, u, parsed)
+}
+
+fn parse_bin_int_lu_suffix(loc: &dyn LocationState, pre: &str, seen: &str, suff_1: &str, suff_2: &str) -> Token {
+    binary_constant(loc);
+    let parsed = u128::from_str_radix(seen, 2);
+    int_literal(loc, false, pre, seen, &(suff_1.to_string() + suff_2), parsed)
+}
+
+/// C23 6.4.4.1, a `wb` constant is a `_BitInt(N)` with the smallest N which can hold its
+/// value and a sign bit (at least 2), a `uwb` one an `unsigned _BitInt(N)` with the
+/// smallest N which can hold its value (at least 1)
+fn parse_bit_int(loc: &dyn LocationState, pre: &str, seen: &str, radix: u32, suffix: &str) -> Token {
+    if !loc.lang().at_least(Standard::C23) {
+        loc.extension("pedantic", "ISO C does not support literal 'wb' suffixes before C23");
+    }
+    if pre.starts_with("0b") || pre.starts_with("0B") {
+        binary_constant(loc);
+    }
+    let value = match u128::from_str_radix(seen, radix) {
+        Ok(value) => value,
+        Err(_) => {
+            let value = pre.to_string() + seen + suffix;
+            loc.error("integer-too-large", &format!("integer constant {} is too large for its type", value));
+            return Token::Unknown(value);
+        }
+    };
+    let bits = u128::BITS - value.leading_zeros();
+    if suffix.contains(['u', 'U']) {
+        Token::IntLitUBitInt(value, bits.max(1))
+    } else {
+        Token::IntLitBitInt(value, (bits + 1).max(2))
+    }
 }
 
 fn parse_dec_float_no_suffix(loc: &dyn LocationState, seen: &str, e: &str, exp: &str) -> Token {
@@ -273,7 +260,7 @@ fn parse_dec_float_no_suffix(loc: &dyn LocationState, seen: &str, e: &str, exp:
     match parsed {
         Ok(val) => Token::FloatLit64(val),
         Err(e) => {
-            eprintln!("{}:{}:{} - warn - unable to convert dec_float to f64: seen:[{}] e:[{}] exp:[{}]", loc.f(), loc.l(), loc.c(), seen, e, exp);
+            loc.warning("invalid-float", &format!("unable to convert dec_float to f64: seen:[{}] e:[{}] exp:[{}]", seen, e, exp));
             Token::Unknown(value)
         }
     }
@@ -285,16 +272,21 @@ fn parse_dec_float_f_suffix(loc: &dyn LocationState, seen: &str, e: &str, exp: &
     match parsed {
         Ok(val) => Token::FloatLit32(val),
         Err(e) => {
-            eprintln!("{}:{}:{} - warn - unable to convert dec_float to f32: seen:[{}] e:[{}] exp:[{}]", loc.f(), loc.l(), loc.c(), seen, e, exp);
+            loc.warning("invalid-float", &format!("unable to convert dec_float to f32: seen:[{}] e:[{}] exp:[{}]", seen, e, exp));
             Token::Unknown(value)
         }
     }
 }
 
-fn parse_dec_float_l_suffix(loc: &dyn LocationState, seen: &str, e: &str, exp: &str, l: &str) -> Token {
-    eprintln!("{}:{}:{} - warn - unimplemented dec_float_l: seen:[{}] e:[{}] exp:[{}] l[{}]", loc.f(), loc.l(), loc.c(), seen, e, exp, l);
-    let value = seen.to_string() + e + exp + l;
-    Token::Unknown(value)
+fn long_double(loc: &dyn LocationState, value: F80) -> Token {
+    if value == F80::INFINITY {
+        loc.warning("overflow", "floating constant exceeds range of 'long double'");
+    }
+    Token::FloatLit80(value)
+}
+
+fn parse_dec_float_l_suffix(loc: &dyn LocationState, seen: &str, _e: &str, exp: &str, _l: &str) -> Token {
+    long_double(loc, f80::from_dec(seen, exp))
 }
 
 fn parse_hex_float_no_suffix(loc: &dyn LocationState, pre: &str, seen: &str, p: &str, exp: &str) -> Token {
@@ -302,7 +294,7 @@ fn parse_hex_float_no_suffix(loc: &dyn LocationState, pre: &str, seen: &str, p:
     match parse_hexf64(&value, false) {
         Ok(f) => Token::FloatLit64(f),
         Err(e) => {
-            eprintln!("{}:{}:{} - error - unable to parse hexf64: [{}] - {}", loc.f(), loc.l(), loc.c(), value, e);
+            loc.error("invalid-float", &format!("unable to parse hexf64: [{}] - {}", value, e));
             Token::Unknown(value)
         }
     }
@@ -313,7 +305,7 @@ fn parse_hex_float_f_suffix(loc: &dyn LocationState, pre: &str, seen: &str, p: &
     match parse_hexf32(&value, false) {
         Ok(f) => Token::FloatLit32(f),
         Err(e) => {
-            eprintln!("{}:{}:{} - error - unable to parse hexf32: [{}] - {}", loc.f(), loc.l(), loc.c(), value, e);
+            loc.error("invalid-float", &format!("unable to parse hexf32: [{}] - {}", value, e));
             let mut value = value;
             value += &exp;
             Token::Unknown(value)
@@ -321,14 +313,17 @@ fn parse_hex_float_f_suffix(loc: &dyn LocationState, pre: &str, seen: &str, p: &
     }
 }
 
-fn parse_hex_float_l_suffix(loc: &dyn LocationState, pre: &str, seen: &str, p: &str, exp: &str, l: &str) -> Token {
-    eprintln!("{}:{}:{} - warn - unimplemented hex_float_l: pre:[{}] seen:[{}] p:[{}] exp:[{}] l[{}]", loc.f(), loc.l(), loc.c(), pre, seen, p, exp, l);
-    let value = pre.to_string() + seen + p + exp + l;
-    Token::Unknown(value)
+fn parse_hex_float_l_suffix(loc: &dyn LocationState, _pre: &str, seen: &str, _p: &str, exp: &str, _l: &str) -> Token {
+    long_double(loc, f80::from_hex(seen, exp))
 }
 
 trait NumericDfa : Debug {
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token>;
+
+    /// the radix of the digits being read, if a C23 digit separator may come next
+    fn digits(&self) -> Option<u32> {
+        None
+    }
 }
 
 fn init_dfa<'iter>(loc: &'iter dyn LocationState, peeked: Option<char>) -> Box<dyn NumericDfa + 'iter> {
@@ -364,6 +359,72 @@ struct InitZeroX {
     pref: String,
 }
 
+#[derive(Debug)]
+struct InitZeroB {
+    pref: String,
+}
+
+#[derive(Debug)]
+struct BinInt {
+    pref: String,
+    seen: String,
+}
+
+#[derive(Debug)]
+struct BinIntL {
+    pref: String,
+    seen: String,
+    suff: String,
+}
+
+#[derive(Debug)]
+struct BinIntLL {
+    pref: String,
+    seen: String,
+    suff: String,
+}
+
+#[derive(Debug)]
+struct BinIntLU {
+    pref: String,
+    seen: String,
+    suf1: String,
+    suf2: String,
+}
+
+#[derive(Debug)]
+struct BinIntLLU {
+    pref: String,
+    seen: String,
+    suf1: String,
+    suf2: String,
+}
+
+#[derive(Debug)]
+struct BinIntU {
+    pref: String,
+    seen: String,
+    suff: String,
+}
+
+/// a `w` (after a `u` if there is one) which should be followed by the `b` of a
+/// bit-precise suffix, for any radix
+#[derive(Debug)]
+struct BitIntW {
+    pref: String,
+    seen: String,
+    radix: u32,
+    suff: String,
+}
+
+#[derive(Debug)]
+struct BitIntWB {
+    pref: String,
+    seen: String,
+    radix: u32,
+    suff: String,
+}
+
 #[derive(Debug)]
 struct DecInt {
     seen: String,
@@ -593,6 +654,10 @@ struct Unkn {
 }
 
 impl NumericDfa for InitZero {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '7')) => {
@@ -610,6 +675,11 @@ impl NumericDfa for InitZero {
                 next_seen.push(c);
                 Ok(Box::new(DecFloat{seen: next_seen}))
             }
+            Some(c @ ('e' | 'E')) => {
+                let seen = self.seen.clone();
+                let e = String::from(c);
+                Ok(Box::new(DecFloatExp_{seen, e}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let seen = self.seen.clone();
                 let suff = String::from(c);
@@ -624,6 +694,17 @@ impl NumericDfa for InitZero {
                 let mut next_seen = self.seen.clone();
                 next_seen.push(c);
                 Ok(Box::new(InitZeroX{pref: next_seen}))
+            }
+            Some(c @ ('b' | 'B')) => {
+                let mut next_seen = self.seen.clone();
+                next_seen.push(c);
+                Ok(Box::new(InitZeroB{pref: next_seen}))
+            }
+            Some(c @ ('w' | 'W')) => {
+                let pref = String::new();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 8, suff}))
             }
              Some(c @ ('a' ..= 'z' | 'A' ..= 'Z'| '_' )) => {
                 let suff = String::from(c);
@@ -677,7 +758,7 @@ impl NumericDfa for InitZeroX {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - found hex prefix with no digits following", loc.f(), loc.l(), loc.c());
+                loc.error("hex-no-digits", "found hex prefix with no digits following");
                 let value = self.pref.clone();
                 Err(Token::Unknown(value))
             }
@@ -686,6 +767,10 @@ impl NumericDfa for InitZeroX {
 }
 
 impl NumericDfa for DecInt {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9')) => {
@@ -698,6 +783,12 @@ impl NumericDfa for DecInt {
                 let e = String::from(c);
                 Ok(Box::new(DecFloatExp_{seen, e}))
             }
+            Some(c @ ('w' | 'W')) => {
+                let pref = String::new();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 10, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let seen = self.seen.clone();
                 let suff = String::from(c);
@@ -836,6 +927,13 @@ impl NumericDfa for DecIntLU {
 impl NumericDfa for DecIntU {
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
+            Some(c @ ('w' | 'W')) => {
+                let pref = String::new();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 10, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let seen = self.seen.clone();
                 let suf1 = self.suff.clone();
@@ -856,6 +954,10 @@ impl NumericDfa for DecIntU {
 }
 
 impl NumericDfa for OctDecInt {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ '.') => {
@@ -863,13 +965,23 @@ impl NumericDfa for OctDecInt {
                 seen.push(c);
                 Ok(Box::new(DecFloat{seen}))
             }
-            Some(c @ ('0' ..= '9' |'a' ..= 'z' | 'A' ..= 'Z' | '_')) => {
+            Some(c @ ('0' ..= '9')) => {
+                let mut seen = self.seen.clone();
+                seen.push(c);
+                Ok(Box::new(OctDecInt{seen}))
+            }
+            Some(c @ ('e' | 'E')) => {
+                let seen = self.seen.clone();
+                let e = String::from(c);
+                Ok(Box::new(DecFloatExp_{seen, e}))
+            }
+            Some(c @ ('a' ..= 'z' | 'A' ..= 'Z' | '_')) => {
                 let seen = self.seen.clone();
                 let suff = String::from(c);
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - found non-octal values in octal integer", loc.f(), loc.l(), loc.c());
+                loc.error("invalid-octal-digit", "found non-octal values in octal integer");
                 let seen = self.seen.clone();
                 Err(Token::Unknown(seen))
             }
@@ -878,6 +990,10 @@ impl NumericDfa for OctDecInt {
 }
 
 impl NumericDfa for OctInt {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '7')) => {
@@ -895,6 +1011,12 @@ impl NumericDfa for OctInt {
                 let e = String::from(c);
                 Ok(Box::new(DecFloatExp_{seen, e}))
             }
+            Some(c @ ('w' | 'W')) => {
+                let pref = String::new();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 8, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let seen = self.seen.clone();
                 let suff = String::from(c);
@@ -1029,6 +1151,13 @@ impl NumericDfa for OctIntLU {
 impl NumericDfa for OctIntU {
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
+            Some(c @ ('w' | 'W')) => {
+                let pref = String::new();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 8, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let seen = self.seen.clone();
                 let suf1 = self.suff.clone();
@@ -1049,6 +1178,10 @@ impl NumericDfa for OctIntU {
 }
 
 impl NumericDfa for HexInt {
+    fn digits(&self) -> Option<u32> {
+        Some(16)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9' | 'a' ..= 'f' | 'A' ..= 'F')) => {
@@ -1057,6 +1190,12 @@ impl NumericDfa for HexInt {
                 seen.push(c);
                 Ok(Box::new(HexInt{pref, seen}))
             }
+            Some(c @ ('w' | 'W')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 16, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let pref = self.pref.clone();
                 let seen = self.seen.clone();
@@ -1215,6 +1354,13 @@ impl NumericDfa for HexIntLU {
 impl NumericDfa for HexIntU {
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
+            Some(c @ ('w' | 'W')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 16, suff}))
+            }
             Some(c @ ('l' | 'L')) => {
                 let pref = self.pref.clone();
                 let seen = self.seen.clone();
@@ -1236,6 +1382,10 @@ impl NumericDfa for HexIntU {
 }
 
 impl NumericDfa for DecFloat {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9')) => {
@@ -1271,6 +1421,10 @@ impl NumericDfa for DecFloat {
 }
 
 impl NumericDfa for DecFloatExp {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9')) => {
@@ -1362,7 +1516,7 @@ impl NumericDfa for DecFloatExpSign {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - exponent has no digits", loc.f(), loc.l(), loc.c());
+                loc.error("exponent-no-digits", "exponent has no digits");
                 let mut value = self.seen.clone();
                 value += &self.e;
                 value += &self.exp;
@@ -1394,7 +1548,7 @@ impl NumericDfa for DecFloatExp_ {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - exponent has no digits", loc.f(), loc.l(), loc.c());
+                loc.error("exponent-no-digits", "exponent has no digits");
                 let mut value = self.seen.clone();
                 value += &self.e;
                 Err(Token::Unknown(value))
@@ -1436,6 +1590,10 @@ impl NumericDfa for DecFloatL {
 }
 
 impl NumericDfa for HexFloat {
+    fn digits(&self) -> Option<u32> {
+        Some(16)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9' | 'a' ..= 'f' | 'A' ..= 'F')) => {
@@ -1457,7 +1615,7 @@ impl NumericDfa for HexFloat {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - hex float literals must have an exponent", loc.f(), loc.l(), loc.c());
+                loc.error("hex-float-no-exponent", "hex float literals must have an exponent");
                 let mut value = self.pref.clone();
                 value += &self.seen;
                 Err(Token::Unknown(value))
@@ -1467,6 +1625,10 @@ impl NumericDfa for HexFloat {
 }
 
 impl NumericDfa for HexFloatExp {
+    fn digits(&self) -> Option<u32> {
+        Some(10)
+    }
+
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
             Some(c @ ('0' ..= '9')) => {
@@ -1565,7 +1727,7 @@ impl NumericDfa for HexFloatExpSign {
             Ok(Box::new(Unkn{seen, suff}))
         }
         _ => {
-            eprintln!("{}:{}:{} - error - exponent has no digits", loc.f(), loc.l(), loc.c());
+            loc.error("exponent-no-digits", "exponent has no digits");
             let mut value = self.pref.clone();
             value += &self.seen;
             value += &self.p;
@@ -1601,7 +1763,7 @@ impl NumericDfa for HexFloatExp_ {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - exponent has no digits", loc.f(), loc.l(), loc.c());
+                loc.error("exponent-no-digits", "exponent has no digits");
                 let mut value = self.pref.clone();
                 value += &self.seen;
                 value += &self.p;
@@ -1611,6 +1773,265 @@ impl NumericDfa for HexFloatExp_ {
     }
 }
 
+impl NumericDfa for InitZeroB {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('0' | '1')) => {
+                let pref = self.pref.clone();
+                let seen = String::from(c);
+                Ok(Box::new(BinInt{pref, seen}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let seen = self.pref[..1].to_string();
+                let mut suff = self.pref[1..].to_string();
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                loc.error("binary-no-digits", "found binary prefix with no digits following");
+                let value = self.pref.clone();
+                Err(Token::Unknown(value))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinInt {
+    fn digits(&self) -> Option<u32> {
+        Some(2)
+    }
+
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('0' | '1')) => {
+                let pref = self.pref.clone();
+                let mut seen = self.seen.clone();
+                seen.push(c);
+                Ok(Box::new(BinInt{pref, seen}))
+            }
+            Some(c @ ('l' | 'L')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BinIntL{pref, seen, suff}))
+            }
+            Some(c @ ('u' | 'U')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BinIntU{pref, seen, suff}))
+            }
+            Some(c @ ('w' | 'W')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suff = String::from(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 2, suff}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let suff = String::from(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_no_suffix(loc, &self.pref, &self.seen))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinIntL {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('l' | 'L')) => {
+                let previous = self.suff.chars().next().expect("There should always be a single char in suffix");
+                if previous == c {
+                    let pref = self.pref.clone();
+                    let seen = self.seen.clone();
+                    let mut suff = self.suff.clone();
+                    suff.push(c);
+                    Ok(Box::new(BinIntLL{pref, seen, suff}))
+                } else {
+                    let mut seen = self.pref.clone();
+                    seen += &self.seen;
+                    let mut suff = self.suff.clone();
+                    suff.push(c);
+                    Ok(Box::new(Unkn{seen, suff}))
+                }
+            }
+            Some(c @ ('u' | 'U')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suf1 = self.suff.clone();
+                let suf2 = String::from(c);
+                Ok(Box::new(BinIntLU{pref, seen, suf1, suf2}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_l_suffix(loc, &self.pref, &self.seen, &self.suff))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinIntLL {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('u' | 'U')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suf1 = self.suff.clone();
+                let suf2 = String::from(c);
+                Ok(Box::new(BinIntLLU{pref, seen, suf1, suf2}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_l_suffix(loc, &self.pref, &self.seen, &self.suff))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinIntLLU {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let mut suff = self.suf1.clone();
+                suff += &self.suf2;
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_lu_suffix(loc, &self.pref, &self.seen, &self.suf1, &self.suf2))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinIntLU {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('l' | 'L')) => {
+                let previous = self.suf2.chars().next().expect("There should always be a single char in suffix");
+                if previous == c {
+                    let pref = self.pref.clone();
+                    let seen = self.seen.clone();
+                    let suf1 = self.suf1.clone();
+                    let mut suf2 = self.suf2.clone();
+                    suf2.push(c);
+                    Ok(Box::new(BinIntLLU{pref, seen, suf1, suf2}))
+                } else {
+                    let mut seen = self.pref.clone();
+                    seen += &self.seen;
+                    let mut suff = self.suf1.clone();
+                    suff += &self.suf2;
+                    suff.push(c);
+                    Ok(Box::new(Unkn{seen, suff}))
+                }
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let mut suff = self.suf1.clone();
+                suff += &self.suf2;
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_lu_suffix(loc, &self.pref, &self.seen, &self.suf1, &self.suf2))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BinIntU {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('l' | 'L')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let suf1 = self.suff.clone();
+                let suf2 = String::from(c);
+                Ok(Box::new(BinIntLU{pref, seen, suf1, suf2}))
+            }
+            Some(c @ ('w' | 'W')) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntW{pref, seen, radix: 2, suff}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let mut seen = self.pref.clone();
+                seen += &self.seen;
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bin_int_u_suffix(loc, &self.pref, &self.seen, &self.suff))
+            }
+        }
+    }
+}
+
+impl NumericDfa for BitIntW {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        let w = self.suff.chars().last().expect("The suffix always ends in the w");
+        match peeked {
+            Some(c @ ('b' | 'B')) if c.is_lowercase() == w.is_lowercase() => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntWB{pref, seen, radix: self.radix, suff}))
+            }
+            _ => {
+                let seen = self.pref.clone() + &self.seen;
+                let suff = self.suff.clone();
+                Unkn{seen, suff}.next(loc, peeked)
+            }
+        }
+    }
+}
+
+impl NumericDfa for BitIntWB {
+    fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
+        match peeked {
+            Some(c @ ('u' | 'U')) if !self.suff.contains(['u', 'U']) => {
+                let pref = self.pref.clone();
+                let seen = self.seen.clone();
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(BitIntWB{pref, seen, radix: self.radix, suff}))
+            }
+            Some(c @ ('0' ..= '9' | 'a' ..= 'z' | 'A' ..= 'Z' | '_' | '.')) => {
+                let seen = self.pref.clone() + &self.seen;
+                let mut suff = self.suff.clone();
+                suff.push(c);
+                Ok(Box::new(Unkn{seen, suff}))
+            }
+            _ => {
+                Err(parse_bit_int(loc, &self.pref, &self.seen, self.radix, &self.suff))
+            }
+        }
+    }
+}
+
 impl NumericDfa for Unkn {
     fn next(&self, loc: &dyn LocationState, peeked: Option<char>) -> Result<Box<dyn NumericDfa>, Token> {
         match peeked {
@@ -1621,7 +2042,7 @@ impl NumericDfa for Unkn {
                 Ok(Box::new(Unkn{seen, suff}))
             }
             _ => {
-                eprintln!("{}:{}:{} - error - unexpected suffix [{}] for numeric value {}{}", loc.f(), loc.l(), loc.c(), self.suff, self.seen, self.suff);
+                loc.error("invalid-suffix", &format!("unexpected suffix [{}] for numeric value {}{}", self.suff, self.seen, self.suff));
                 let mut value = self.seen.clone();
                 value += &self.suff;
                 Err(Token::Unknown(value))
@@ -1635,17 +2056,19 @@ impl<'iter> NumericLiteral for NumericLiteralImpl<'iter>{
     fn consume_numeric_literal(&self) -> Token {
         let peeked = self.numeric.peek();
         let mut dfa = init_dfa(self.location, peeked);
-        self.numeric.next();
+        let mut previous = self.numeric.next();
 
         loop {
             let peeked = self.numeric.peek();
-            if self.debug {
-                println!("{}:{}:{} - debug - inputs: ({:?}, {:?})", self.location.f(), self.location.l(), self.location.c(), dfa, peeked);
+            if peeked == Some('\'') && self.separator(dfa.as_ref(), previous) {
+                // a digit separator is consumed but doesn't change the value
+                previous = self.numeric.next();
+                continue;
             }
             let dfa_or_token = dfa.next(self.location, peeked);
             match dfa_or_token {
                 Ok(next_dfa) => {
-                    self.numeric.next();
+                    previous = self.numeric.next();
                     dfa = next_dfa
                 }
                 Err(token) => break token,
@@ -1657,7 +2080,6 @@ impl<'iter> NumericLiteral for NumericLiteralImpl<'iter>{
 struct NumericStateImpl<'iter> {
     iter: RefCell<Peekable<Chars<'iter>>>,
     consumed: RefCell<String>,
-    seen_error: RefCell<bool>,
 }
 
 impl<'iter> NumericStateImpl<'iter> {
@@ -1667,17 +2089,17 @@ impl<'iter> NumericStateImpl<'iter> {
         NumericStateImpl{
             iter: RefCell::new(iter),
             consumed: RefCell::new(String::new()),
-            seen_error: RefCell::new(false),
         }
     }
 }
 
 impl<'iter> NumericState for NumericStateImpl<'iter> {
     fn peek(&self) -> Option<char> {
-        match self.iter.borrow_mut().peek() {
-            None => None,
-            Some(c) => Some(*c),
-        }
+        self.iter.borrow_mut().peek().copied()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.iter.borrow().clone().nth(n)
     }
 
     fn next(&self) -> Option<char> {
@@ -1689,18 +2111,6 @@ impl<'iter> NumericState for NumericStateImpl<'iter> {
         r
     }
 
-    fn emit_unknown(&self) -> Token {
-        Token::Unknown(self.consumed.borrow().clone())
-    }
-
-    fn report_error(&self) {
-        *self.seen_error.borrow_mut() = true;
-    }
-
-    fn seen_error(&self) -> bool {
-        *self.seen_error.borrow()
-    }
-
     fn chars_consumed(&self) -> usize {
         self.consumed.borrow().chars().count()
     }
@@ -0,0 +1,281 @@
+//! Conversion of `L` suffixed floating literals to the x87 80 bit extended format: a sign
+//! bit, a 15 bit exponent biased by 16383 and a 64 bit significand with an explicit integer
+//! bit. The literal is held exactly as a ratio of big integers and rounded once, to nearest
+//! with ties to even, as gcc does.
+
+use std::cmp::Ordering;
+
+use crate::tokens::F80;
+
+const BIAS: i64 = 16383;
+const MAX_EXPONENT: i64 = 0x7fff;
+/// the exponent of the least significant bit of a subnormal
+const MIN_LSB: i64 = 1 - BIAS - 63;
+const INTEGER_BIT: u128 = 1 << 63;
+
+/// exponents past this are saturated, every literal which has one is zero or infinite
+const EXPONENT_LIMIT: i64 = 1 << 40;
+
+/// the nearest `F80` to the decimal `seen` (digits with an optional point) times ten to the
+/// power `exp` (digits with an optional sign, empty for none)
+pub fn from_dec(seen: &str, exp: &str) -> F80 {
+    let (digits, fraction) = parse_digits(seen, 10);
+    if digits.is_zero() {
+        return F80::new(0);
+    }
+    let exp10 = parse_exponent(exp).saturating_sub(fraction);
+
+    // the value is in [10^(n-1+exp10), 10^(n+exp10)) for n significant digits
+    let significant = seen
+        .trim_start_matches(['0', '.'])
+        .chars()
+        .filter(|c| *c != '.')
+        .count() as i64;
+    if significant + exp10 > 4933 {
+        return F80::INFINITY;
+    }
+    if significant + exp10 <= -4951 {
+        return F80::new(0);
+    }
+
+    // 10^exp10 is 5^exp10 * 2^exp10, keeping the twos out of the big integers
+    let powers = Big::pow5(exp10.unsigned_abs());
+    if exp10 >= 0 {
+        round(digits.mul(&powers), Big::from(1), exp10)
+    } else {
+        round(digits, powers, exp10)
+    }
+}
+
+/// the nearest `F80` to the hexadecimal `seen` (digits with an optional point, without the
+/// `0x`) times two to the power `exp`
+pub fn from_hex(seen: &str, exp: &str) -> F80 {
+    let (digits, fraction) = parse_digits(seen, 16);
+    if digits.is_zero() {
+        return F80::new(0);
+    }
+    let exp2 = parse_exponent(exp).saturating_sub(4 * fraction);
+
+    let top = digits.bits() as i64 + exp2;
+    if top > BIAS + 1 {
+        return F80::INFINITY;
+    }
+    if top < MIN_LSB - 1 {
+        return F80::new(0);
+    }
+    round(digits, Big::from(1), exp2)
+}
+
+/// the digits of `seen` as an integer, along with how many of them came after the point
+fn parse_digits(seen: &str, radix: u32) -> (Big, i64) {
+    let mut digits = Big::from(0);
+    let mut fraction = 0;
+    let mut after_point = false;
+    for c in seen.chars() {
+        match c.to_digit(radix) {
+            Some(d) => {
+                digits = digits.mul_small(radix).add_small(d);
+                if after_point {
+                    fraction += 1;
+                }
+            }
+            None => after_point = c == '.',
+        }
+    }
+    (digits, fraction)
+}
+
+fn parse_exponent(exp: &str) -> i64 {
+    let (negative, digits) = match exp.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, exp.trim_start_matches('+')),
+    };
+    let magnitude = digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0, |acc: i64, d| (acc * 10 + d as i64).min(EXPONENT_LIMIT));
+    if negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// round `num / den * 2^exp2` to the nearest `F80`, `num` mustn't be zero
+fn round(mut num: Big, mut den: Big, exp2: i64) -> F80 {
+    // scale so the quotient has 66 or 67 bits, two more than the significand at least,
+    // anything left over only matters as to whether it is zero
+    let shift = 66 - (num.bits() as i64 - den.bits() as i64);
+    if shift >= 0 {
+        num = num.shl(shift as usize);
+    } else {
+        den = den.shl(-shift as usize);
+    }
+    let (quotient, rest) = num.div_rem(&den);
+    let sticky = !rest.is_zero();
+
+    let lsb = exp2 - shift;
+    let len = 128 - quotient.leading_zeros() as i64;
+    let top = lsb + len - 1;
+    let target = (top - 63).max(MIN_LSB);
+    let drop = target - lsb;
+    if drop > len {
+        // below half the smallest subnormal
+        return F80::new(0);
+    }
+
+    let mut significand = quotient >> drop;
+    let remainder = quotient & ((1 << drop) - 1);
+    let half = 1 << (drop - 1);
+    if remainder > half || (remainder == half && (sticky || significand & 1 == 1)) {
+        significand += 1;
+    }
+
+    let mut target = target;
+    if significand == INTEGER_BIT << 1 {
+        significand >>= 1;
+        target += 1;
+    }
+    if significand < INTEGER_BIT {
+        // subnormal, or zero if it rounded down to it
+        return F80::new(significand);
+    }
+    let exponent = target + 63 + BIAS;
+    if exponent >= MAX_EXPONENT {
+        return F80::INFINITY;
+    }
+    F80::new((exponent as u128) << 64 | significand)
+}
+
+/// just enough of an unsigned big integer for the conversions, little endian
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Big {
+    limbs: Vec<u32>,
+}
+
+impl From<u32> for Big {
+    fn from(value: u32) -> Self {
+        Big { limbs: vec![value] }.trimmed()
+    }
+}
+
+impl Big {
+    fn trimmed(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn mul_small(mut self, factor: u32) -> Self {
+        let mut carry = 0;
+        for limb in &mut self.limbs {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        self.limbs.push(carry as u32);
+        self.trimmed()
+    }
+
+    fn add_small(mut self, addend: u32) -> Self {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            if carry == 0 {
+                break;
+            }
+            let sum = *limb as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+        self.limbs.push(carry as u32);
+        self.trimmed()
+    }
+
+    fn mul(&self, other: &Big) -> Big {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let product = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        Big { limbs }.trimmed()
+    }
+
+    fn pow5(exp: u64) -> Big {
+        // the largest power of five which fits in a limb
+        const POW5_13: u32 = 1_220_703_125;
+        let mut power = Big::from(1);
+        for _ in 0..exp / 13 {
+            power = power.mul_small(POW5_13);
+        }
+        power.mul_small(5u32.pow((exp % 13) as u32))
+    }
+
+    fn shl(&self, shift: usize) -> Big {
+        let (words, bits) = (shift / 32, shift % 32);
+        let mut limbs = vec![0u32; words];
+        let mut carry = 0;
+        for limb in &self.limbs {
+            limbs.push(limb << bits | carry);
+            carry = if bits == 0 { 0 } else { limb >> (32 - bits) };
+        }
+        limbs.push(carry);
+        Big { limbs }.trimmed()
+    }
+
+    fn sub_assign(&mut self, other: &Big) {
+        let mut borrow = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let subtrahend = other.limbs.get(i).copied().unwrap_or(0) as i64 + borrow;
+            let difference = *limb as i64 - subtrahend;
+            borrow = (difference < 0) as i64;
+            *limb = difference.rem_euclid(1 << 32) as u32;
+        }
+        *self = std::mem::take(self).trimmed();
+    }
+
+    /// `self / den` and `self % den`, where the quotient fits in a `u128`
+    fn div_rem(mut self, den: &Big) -> (u128, Big) {
+        let mut quotient = 0;
+        let top = (self.bits() + 1).saturating_sub(den.bits()).min(127);
+        for i in (0..=top).rev() {
+            let shifted = den.shl(i);
+            if self >= shifted {
+                self.sub_assign(&shifted);
+                quotient |= 1 << i;
+            }
+        }
+        (quotient, self)
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,4 +1,7 @@
+// the expected values are grouped as sign, exponent, (x87 integer bit) and fraction
+#[allow(clippy::unusual_byte_groupings)]
 mod dec_float;
+#[allow(clippy::unusual_byte_groupings)]
 mod hex_float;
 
 use crate::numeric_literal::numeric_literal_impl;
@@ -12,7 +15,7 @@ use crate::Token::FloatLit80;
 use crate::Token::Unknown;
 
 fn actual(input: &str) -> (Token, usize) {
-    let location = Box::new(TestLocation);
+    let location = Box::new(TestLocation::default());
     let numeric = numeric_state_impl(input.chars().peekable());
 
     let numeric_literal = numeric_literal_impl(location.as_ref(), numeric.as_ref());
@@ -63,6 +63,15 @@ fn test_float_literal_dec_no_suffix_no_fract_neg() {
     assert_eq!(exp_sz, act_sz);
 }
 
+#[test]
+fn test_float_literal_dec_no_suffix_zero_no_fract() {
+    let (exp_t, act_t, act_sz) = exp_f64_and_actual(0.0, "0e0");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
 #[test]
 fn test_float_literal_dec_no_suffix_from_oct() {
     let (exp_t, act_t, act_sz) = exp_f64_and_actual(1.0, "01.");
@@ -72,6 +81,24 @@ fn test_float_literal_dec_no_suffix_from_oct() {
     assert_eq!(exp_sz, act_sz);
 }
 
+#[test]
+fn test_float_literal_dec_no_suffix_from_oct_with_exponent() {
+    let (exp_t, act_t, act_sz) = exp_f64_and_actual(80.0, "08e1");
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_no_suffix_from_oct_with_neg_exponent() {
+    let (exp_t, act_t, act_sz) = exp_f64_and_actual(0.9, "09e-1");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
 #[test]
 fn test_float_literal_dec_f_suffix_no_exponent() {
     let (exp_t, act_t, act_sz) = exp_f32_and_actual(1.0, "1.0f");
@@ -145,88 +172,210 @@ fn test_float_literal_dec_f_suffix_from_oct() {
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_exponent() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1.0l");
-    let exp_sz = 2;
+    let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_exponent_no_integer() {
-    let value = 0b0__011_1111_1101_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, ".25l");
-    let exp_sz = 2;
+    let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_exponent_no_fract() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1.l");
-    let exp_sz = 2;
+    let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_integer() {
-    let value = 0b0__011_1111_1101_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__100_0000_0000_0000__1__010_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, ".25e1l");
-    let exp_sz = 2;
+    let exp_sz = 6;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_fract() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e0l");
-    let exp_sz = 2;
+    let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_fract_pos() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e+0l");
-    let exp_sz = 2;
+    let exp_sz = 5;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_no_fract_neg() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e-0l");
-    let exp_sz = 2;
+    let exp_sz = 5;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_dec_l_suffix_from_oct() {
-    let value = 0b0__011_1111_1111_1101__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let value = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "01.l");
-    let exp_sz = 2;
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_inexact() {
+    let value: u128 = 0b0__011_1111_1111_1011__1__100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1100_1101;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0.1L");
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_many_digits() {
+    let value: u128 = 0b0__100_0000_0101_1111__1__100_0111_0111_0100_1000_0111_1111_1011_0110_0001_1011_1001_1111_0000_0111_0111;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "123456789012345678901234567890.L");
+    let exp_sz = 32;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_double_min() {
+    let value: u128 = 0b0__011_1100_0000_0001__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0100_0110;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "2.2250738585072014e-308L");
+    let exp_sz = 24;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_large() {
+    let value: u128 = 0b0__111_1111_1111_1110__1__101_0111_0010_1100_1011_0010_1010_1001_0101_1100_0111_1110_1111_0110_1100_1101;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e4932L");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_max() {
+    let value: u128 = 0b0__111_1111_1111_1110__1__111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1.18973149535723176502e+4932L");
+    let exp_sz = 29;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_overflow() {
+    let value: u128 = 0b0__111_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e4933L");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_huge_exponent() {
+    let value: u128 = 0b0__111_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e99999999999999999999L");
+    let exp_sz = 23;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_min_normal() {
+    let value: u128 = 0b0__000_0000_0000_0001__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "3.3621031431120935063e-4932L");
+    let exp_sz = 28;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_min_subnormal() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "3.6e-4951L");
+    let exp_sz = 10;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_rounds_up_to_min_subnormal() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1.9e-4951L");
+    let exp_sz = 10;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_rounds_down_to_zero() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1.8e-4951L");
+    let exp_sz = 10;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_underflow() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "1e-5000L");
+    let exp_sz = 8;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_dec_l_suffix_zero() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0.0L");
+    let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
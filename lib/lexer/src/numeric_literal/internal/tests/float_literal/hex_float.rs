@@ -290,73 +290,66 @@ fn test_float_literal_hex_f_suffix_p_neg() {
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_0_p0() {
     let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x0.p0l");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_1_p0() {
     let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.p0l");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_upcase_l_suffix_1_p4() {
     let value: u128 = 0b0__100_0000_0000_0011__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.p4L");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_min() {
     let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1p-16445l");
-    let exp_sz = 22;
+    let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_1_max() {
     let value: u128 = 0b0__111_1111_1111_1110__1__111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.fffffffffffffffep+16383l");
-    let exp_sz = 6;
+    let exp_sz = 28;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_no_integer() {
     let value: u128 = 0b0__011_1111_1111_1111__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x.Fp1l");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_no_point() {
     let value: u128 = 0b0__100_0000_0000_0011__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0xFp1l");
@@ -367,44 +360,150 @@ fn test_float_literal_hex_l_suffix_no_point() {
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_int_point() {
     let value: u128 = 0b0__100_0000_0000_0011__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0xF.p1l");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_upcase_p() {
     let value: u128 = 0b0__100_0000_0000_0011__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0xF.P1l");
-    let exp_sz = 6;
+    let exp_sz = 7;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_p_pos() {
     let value: u128 = 0b0__100_0000_0000_0011__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0xF.p+1l");
-    let exp_sz = 6;
+    let exp_sz = 8;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
 #[test]
-#[ignore = "long doubles unimplemented"]
 fn test_float_literal_hex_l_suffix_p_neg() {
     let value: u128 = 0b0__100_0000_0000_0001__1__111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
     let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0xF.p-1l");
-    let exp_sz = 6;
+    let exp_sz = 8;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_round_down() {
+    let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.00000000000000008p0L");
+    let exp_sz = 24;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_round_up() {
+    let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.00000000000000018p0L");
+    let exp_sz = 24;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_tie_to_even_down() {
+    let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.0000000000000001p0L");
+    let exp_sz = 23;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_tie_to_even_up() {
+    let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0010;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.0000000000000003p0L");
+    let exp_sz = 23;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_sticky_past_tie() {
+    let value: u128 = 0b0__011_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.000000000000000080001p0L");
+    let exp_sz = 28;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_round_up_carries() {
+    let value: u128 = 0b0__100_0000_0000_0000__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.fffffffffffffffffp0L");
+    let exp_sz = 24;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_overflow() {
+    let value: u128 = 0b0__111_1111_1111_1111__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1p16384L");
+    let exp_sz = 10;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_subnormal() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1100;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.fffffffffffffffp-16383L");
+    let exp_sz = 27;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_subnormal_rounds_to_normal() {
+    let value: u128 = 0b0__000_0000_0000_0001__1__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.ffffffffffffffffp-16383L");
+    let exp_sz = 28;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_half_min_to_zero() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1p-16446L");
+    let exp_sz = 11;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_float_literal_hex_l_suffix_past_half_min() {
+    let value: u128 = 0b0__000_0000_0000_0000__0__000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001;
+    let (exp_t, act_t, act_sz) = exp_f80_and_actual(value, "0x1.8p-16446L");
+    let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
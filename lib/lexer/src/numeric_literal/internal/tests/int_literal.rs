@@ -1,19 +1,32 @@
+mod bin_int;
+mod bit_int;
 mod dec_int;
 mod hex_int;
 mod oct_int;
+mod separators;
+
+use lang::{LangOptions, Standard};
 
 use crate::numeric_literal::numeric_literal_impl;
 use crate::numeric_literal::numeric_state_impl;
 use crate::tests::TestLocation;
 use crate::Token;
-use crate::Token::IntLitI32;
-use crate::Token::IntLitI64;
-use crate::Token::IntLitU32;
-use crate::Token::IntLitU64;
+use crate::Token::IntLitBitInt;
+use crate::Token::IntLitInt;
+use crate::Token::IntLitLong;
+use crate::Token::IntLitLongLong;
+use crate::Token::IntLitUBitInt;
+use crate::Token::IntLitUInt;
+use crate::Token::IntLitULong;
+use crate::Token::IntLitULongLong;
 use crate::Token::Unknown;
 
 fn actual(input: &str) -> (Token, usize) {
-    let location = Box::new(TestLocation);
+    actual_as(input, LangOptions::default())
+}
+
+fn actual_as(input: &str, lang: LangOptions) -> (Token, usize) {
+    let location = Box::new(TestLocation::with_lang(lang));
     let numeric = numeric_state_impl(input.chars().peekable());
 
     let numeric_literal = numeric_literal_impl(location.as_ref(), numeric.as_ref());
@@ -24,6 +37,10 @@ fn actual(input: &str) -> (Token, usize) {
     (token, sz)
 }
 
+fn c23() -> LangOptions {
+    LangOptions::new(Standard::C23)
+}
+
 fn unknown_and_actual(input: &str) -> (Token, Token, usize) {
     let exp_token = Unknown(input.to_string());
     let (act_token, act_sz) = actual(input);
@@ -31,30 +48,54 @@ fn unknown_and_actual(input: &str) -> (Token, Token, usize) {
     (exp_token, act_token, act_sz)
 }
 
-fn exp_i32_and_actual(v: i32, input: &str) -> (Token, Token, usize) {
-        let exp_t: Token = IntLitI32(v);
+fn exp_int_and_actual(v: i32, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitInt(v);
         let (act_t, act_sz) = actual(input);
         (exp_t, act_t, act_sz)
 }
 
-fn exp_u32_and_actual(v: u32, input: &str) -> (Token,  Token, usize) {
-        let exp_t: Token = IntLitU32(v);
+fn exp_uint_and_actual(v: u32, input: &str) -> (Token,  Token, usize) {
+        let exp_t: Token = IntLitUInt(v);
         let (act_t, act_sz) = actual(input);
         (exp_t, act_t, act_sz)
 }
 
-fn exp_i64_and_actual(v: i64, input: &str) -> (Token, Token, usize) {
-        let exp_t: Token = IntLitI64(v);
+fn exp_long_and_actual(v: i64, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitLong(v);
         let (act_t, act_sz) = actual(input);
         (exp_t, act_t, act_sz)
 }
 
-fn exp_u64_and_actual(v: u64, input: &str) -> (Token, Token, usize) {
-        let exp_t: Token = IntLitU64(v);
+fn exp_ulong_and_actual(v: u64, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitULong(v);
         let (act_t, act_sz) = actual(input);
         (exp_t, act_t, act_sz)
 }
 
+fn exp_long_long_and_actual(v: i64, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitLongLong(v);
+        let (act_t, act_sz) = actual(input);
+        (exp_t, act_t, act_sz)
+}
+
+fn exp_ulong_long_and_actual(v: u64, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitULongLong(v);
+        let (act_t, act_sz) = actual(input);
+        (exp_t, act_t, act_sz)
+}
+
+fn exp_bit_int_and_actual(v: u128, width: u32, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitBitInt(v, width);
+        let (act_t, act_sz) = actual_as(input, c23());
+        (exp_t, act_t, act_sz)
+}
+
+fn exp_ubit_int_and_actual(v: u128, width: u32, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitUBitInt(v, width);
+        let (act_t, act_sz) = actual_as(input, c23());
+        (exp_t, act_t, act_sz)
+}
+
 #[test]
 #[should_panic(expected = "TEST:1:1 - FATAL - this isn't a numeric literal")]
 fn test_int_literal_empty() {
@@ -0,0 +1,136 @@
+use super::*;
+
+#[test]
+fn test_int_literal_bin_no_suffix_zero() {
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(0, "0b0");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_no_suffix_max_i32() {
+    let value = i32::MAX;
+    let input = format!("{:#b}", value);
+
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
+    let exp_sz = 33;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_no_suffix_min_u32() {
+    let value = i32::MAX as u32 + 1;
+    let input = format!("{:#b}", value);
+
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
+    let exp_sz = 34;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_no_suffix_min_i64() {
+    let value = u32::MAX as i64 + 1;
+    let input = format!("{:#b}", value);
+
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
+    let exp_sz = 35;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_upper_prefix() {
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(5, "0B101");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_l_suffix() {
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(5, "0b101l");
+    let exp_sz = 6;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_ll_suffix() {
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(5, "0b101LL");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_u_suffix() {
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(5, "0b101u");
+    let exp_sz = 6;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_lu_suffix() {
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(5, "0b101lU");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_ull_suffix() {
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(5, "0b101ull");
+    let exp_sz = 8;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_lll_suffix() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("0b101lll");
+    let exp_sz = 8;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_bad_digit() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("0b102");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_no_digits() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("0b");
+    let exp_sz = 2;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bin_prefix_then_letter() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("0bz");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
@@ -0,0 +1,130 @@
+use super::*;
+
+#[test]
+fn test_int_literal_bit_int_zero() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(0, 2, "0wb");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_dec() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(255, 9, "255wb");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_upper() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(1, 2, "1WB");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_mixed_case() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("1wB");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_hex() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(0xffff, 17, "0xffffwb");
+    let exp_sz = 8;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_oct() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(8, 5, "010wb");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_bin() {
+    let (exp_t, act_t, act_sz) = exp_bit_int_and_actual(5, 4, "0b101wb");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_ubit_int_zero() {
+    let (exp_t, act_t, act_sz) = exp_ubit_int_and_actual(0, 1, "0uwb");
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_ubit_int_u_first() {
+    let (exp_t, act_t, act_sz) = exp_ubit_int_and_actual(255, 8, "255Uwb");
+    let exp_sz = 6;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_ubit_int_u_last() {
+    let (exp_t, act_t, act_sz) = exp_ubit_int_and_actual(255, 8, "255wbu");
+    let exp_sz = 6;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_ubit_int_max() {
+    let value = u128::MAX;
+    let input = format!("{:#x}uwb", value);
+
+    let (exp_t, act_t, act_sz) = exp_ubit_int_and_actual(value, 128, &input);
+    let exp_sz = 37;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_two_u() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("1uwbu");
+    let exp_sz = 5;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_with_l() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("1lwb");
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_bit_int_w_only() {
+    let (exp_t, act_t, act_sz) = unknown_and_actual("1w");
+    let exp_sz = 2;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
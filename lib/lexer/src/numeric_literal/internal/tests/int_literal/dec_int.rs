@@ -5,7 +5,7 @@ fn test_int_literal_dec_no_suffix_min_i32() {
     let value = 1;
     let input = format!("{}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
     let exp_sz = 1;
 
     assert_eq!(exp_t, act_t);
@@ -17,7 +17,7 @@ fn test_int_literal_dec_no_suffix_max_i32() {
     let value = i32::MAX;
     let input = format!("{}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
     let exp_sz = 10;
 
     assert_eq!(exp_t, act_t);
@@ -29,7 +29,7 @@ fn test_int_literal_dec_no_suffix_min_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 10;
 
     assert_eq!(exp_t, act_t);
@@ -41,7 +41,7 @@ fn test_int_literal_dec_no_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -53,7 +53,7 @@ fn test_int_literal_dec_no_suffix_overflow_i64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("{}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(i64::MIN, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -65,7 +65,7 @@ fn test_int_literal_dec_u_suffix_min_u32() {
     let value = 1;
     let input = format!("{}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 2;
 
     assert_eq!(exp_t, act_t);
@@ -77,7 +77,7 @@ fn test_int_literal_dec_u_suffix_max_u32() {
     let value = u32::MAX;
     let input = format!("{}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -89,7 +89,7 @@ fn test_int_literal_dec_u_suffix_min_u64() {
     let value = u32::MAX as u64 + 1;
     let input = format!("{}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -101,7 +101,7 @@ fn test_int_literal_dec_u_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -113,7 +113,7 @@ fn test_int_literal_dec_u_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -126,7 +126,7 @@ fn test_int_literal_dec_U_suffix_min_u32() {
     let value = 1;
     let input = format!("{}U", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 2;
 
     assert_eq!(exp_t, act_t);
@@ -139,7 +139,7 @@ fn test_int_literal_dec_U_suffix_max_u32() {
     let value = u32::MAX;
     let input = format!("{}U", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -152,7 +152,7 @@ fn test_int_literal_dec_U_suffix_min_u64() {
     let value = u32::MAX as u64 + 1;
     let input = format!("{}U", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -165,7 +165,7 @@ fn test_int_literal_dec_U_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{}U", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -178,7 +178,7 @@ fn test_int_literal_dec_U_suffix_overflow_u32() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}U", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -190,7 +190,7 @@ fn test_int_literal_dec_l_suffix_min_i32_is_i64() {
     let value = 1;
     let input = format!("{}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 2;
 
     assert_eq!(exp_t, act_t);
@@ -202,7 +202,7 @@ fn test_int_literal_dec_l_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("{}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -214,7 +214,7 @@ fn test_int_literal_dec_l_suffix_min_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -226,7 +226,7 @@ fn test_int_literal_dec_l_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -238,7 +238,7 @@ fn test_int_literal_dec_l_suffix_overflow_i64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -251,7 +251,7 @@ fn test_int_literal_dec_L_suffix_min_i32_is_i64() {
     let value = 1;
     let input = format!("{}L", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 2;
 
     assert_eq!(exp_t, act_t);
@@ -264,7 +264,7 @@ fn test_int_literal_dec_L_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("{}L", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -277,7 +277,7 @@ fn test_int_literal_dec_L_suffix_min_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{}L", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -290,7 +290,7 @@ fn test_int_literal_dec_L_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{}L", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -303,7 +303,7 @@ fn test_int_literal_dec_L_suffix_overflow_i64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}L", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -315,7 +315,7 @@ fn test_int_literal_dec_ll_suffix_min_i32_is_i64() {
     let value = 1;
     let input = format!("{}ll", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -327,7 +327,7 @@ fn test_int_literal_dec_ll_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("{}ll", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -339,7 +339,7 @@ fn test_int_literal_dec_ll_suffix_min_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{}ll", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -351,7 +351,7 @@ fn test_int_literal_dec_ll_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{}ll", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -363,7 +363,7 @@ fn test_int_literal_dec_ll_suffix_overflow_i64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}ll", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -376,7 +376,7 @@ fn test_int_literal_dec_LL_suffix_min_i32_is_i64() {
     let value = 1;
     let input = format!("{}LL", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -389,7 +389,7 @@ fn test_int_literal_dec_LL_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("{}LL", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -402,7 +402,7 @@ fn test_int_literal_dec_LL_suffix_min_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{}LL", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -415,7 +415,7 @@ fn test_int_literal_dec_LL_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{}LL", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -428,7 +428,7 @@ fn test_int_literal_dec_LL_suffix_overflow_i64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}LL", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -440,7 +440,7 @@ fn test_int_literal_dec_ul_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -452,7 +452,7 @@ fn test_int_literal_dec_ul_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("{}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -464,7 +464,7 @@ fn test_int_literal_dec_ul_suffix_min_i64_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("{}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -476,7 +476,7 @@ fn test_int_literal_dec_ul_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -488,7 +488,7 @@ fn test_int_literal_dec_ul_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -500,7 +500,7 @@ fn test_int_literal_dec_ull_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -512,7 +512,7 @@ fn test_int_literal_dec_ull_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("{}ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -524,7 +524,7 @@ fn test_int_literal_dec_ull_suffix_min_i64_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("{}ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -536,7 +536,7 @@ fn test_int_literal_dec_ull_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{}ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -549,7 +549,7 @@ fn test_int_literal_dec_ull_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -561,7 +561,7 @@ fn test_int_literal_dec_lu_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -573,7 +573,7 @@ fn test_int_literal_dec_lu_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("{}lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -585,7 +585,7 @@ fn test_int_literal_dec_lu_suffix_min_i64_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("{}lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -597,7 +597,7 @@ fn test_int_literal_dec_lu_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{}lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -609,7 +609,7 @@ fn test_int_literal_dec_lu_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -621,7 +621,7 @@ fn test_int_literal_dec_llu_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}llu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -633,7 +633,7 @@ fn test_int_literal_dec_llu_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("{}llu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -645,7 +645,7 @@ fn test_int_literal_dec_llu_suffix_min_i64_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("{}llu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -658,7 +658,7 @@ fn test_int_literal_dec_llu_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{}llu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -671,7 +671,7 @@ fn test_int_literal_dec_llu_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{}llu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -684,7 +684,7 @@ fn test_int_literal_dec_Ul_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}Ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -697,7 +697,7 @@ fn test_int_literal_dec_lU_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}lU", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -710,7 +710,7 @@ fn test_int_literal_dec_Ull_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}Ull", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -723,7 +723,7 @@ fn test_int_literal_dec_llU_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}llU", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -736,7 +736,7 @@ fn test_int_literal_dec_uL_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}uL", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -749,7 +749,7 @@ fn test_int_literal_dec_Lu_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}Lu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -762,7 +762,7 @@ fn test_int_literal_dec_uLL_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}uLL", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -775,7 +775,7 @@ fn test_int_literal_dec_LLu_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}LLu", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -788,7 +788,7 @@ fn test_int_literal_dec_UL_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}UL", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -801,7 +801,7 @@ fn test_int_literal_dec_LU_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}LU", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -814,7 +814,7 @@ fn test_int_literal_dec_ULL_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}ULL", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -827,13 +827,37 @@ fn test_int_literal_dec_LLU_suffix_min_i32_is_u64() {
     let value = 1;
     let input = format!("{}LLU", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
     assert_eq!(exp_sz, act_sz);
 }
 
+#[test]
+fn test_int_literal_dec_l_suffix_min_u64_too_large() {
+    let value = i64::MAX as u64 + 1;
+    let input = format!("{}l", value);
+
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
+    let exp_sz = 20;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_dec_ll_suffix_min_u64_too_large() {
+    let value = i64::MAX as u64 + 1;
+    let input = format!("{}ll", value);
+
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
+    let exp_sz = 21;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
 #[test]
 #[allow(non_snake_case)]
 fn test_int_literal_dec_Ll_suffix_unknown() {
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn test_int_literal_hex_no_suffix_min_i32() {
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(0, "0x0");
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(0, "0x0");
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -14,7 +14,7 @@ fn test_int_literal_hex_no_suffix_max_i32() {
     let value = i32::MAX;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
     let exp_sz = 10;
 
     assert_eq!(exp_t, act_t);
@@ -26,7 +26,7 @@ fn test_int_literal_hex_no_suffix_min_u32() {
     let value = i32::MAX as u32 + 1;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 10;
 
     assert_eq!(exp_t, act_t);
@@ -38,7 +38,7 @@ fn test_int_literal_hex_no_suffix_max_u32() {
     let value = u32::MAX;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 10;
 
     assert_eq!(exp_t, act_t);
@@ -50,7 +50,7 @@ fn test_int_literal_hex_no_suffix_min_i64() {
     let value = u32::MAX as i64 + 1;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -62,7 +62,7 @@ fn test_int_literal_hex_no_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 18;
 
     assert_eq!(exp_t, act_t);
@@ -74,7 +74,7 @@ fn test_int_literal_hex_no_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 18;
 
     assert_eq!(exp_t, act_t);
@@ -86,7 +86,7 @@ fn test_int_literal_hex_no_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 18;
 
     assert_eq!(exp_t, act_t);
@@ -98,7 +98,7 @@ fn test_int_literal_hex_no_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{:#x}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -109,7 +109,7 @@ fn test_int_literal_hex_no_suffix_overflow_u64() {
 fn test_int_literal_hex_no_suffix_leading_zeros_i32() {
     let input = "0x0000000000000000000000000000000000000000000000000000000000000000000000000000001";
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(1, input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(1, input);
     let exp_sz = 81;
 
     assert_eq!(exp_t, act_t);
@@ -121,7 +121,7 @@ fn test_int_literal_hex_u_suffix_min_i32_is_u32() {
     let value = 0;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -133,7 +133,7 @@ fn test_int_literal_hex_u_suffix_max_i32_is_u32() {
     let value = i32::MAX as u32;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -145,7 +145,7 @@ fn test_int_literal_hex_u_suffix_min_u32() {
     let value = i32::MAX as u32 + 1;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -157,7 +157,7 @@ fn test_int_literal_hex_u_suffix_max_u32() {
     let value = u32::MAX;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -169,7 +169,7 @@ fn test_int_literal_hex_u_suffix_min_i64_is_u64() {
     let value = u32::MAX as u64 + 1;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -181,7 +181,7 @@ fn test_int_literal_hex_u_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -193,7 +193,7 @@ fn test_int_literal_hex_u_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -205,7 +205,7 @@ fn test_int_literal_hex_u_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -213,11 +213,11 @@ fn test_int_literal_hex_u_suffix_max_u64() {
 }
 
 #[test]
-fn test_int_literal_hex_u_suffix_overflow_u64_is_u32() {
+fn test_int_literal_hex_u_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -229,7 +229,7 @@ fn test_int_literal_hex_l_suffix_min_i32_is_i64() {
     let value = 0;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 4;
 
     assert_eq!(exp_t, act_t);
@@ -241,7 +241,7 @@ fn test_int_literal_hex_l_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -253,7 +253,7 @@ fn test_int_literal_hex_l_suffix_min_u32_is_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -265,7 +265,7 @@ fn test_int_literal_hex_l_suffix_max_u32_is_i64() {
     let value = u32::MAX as i64;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 11;
 
     assert_eq!(exp_t, act_t);
@@ -277,7 +277,7 @@ fn test_int_literal_hex_l_suffix_min_i64() {
     let value = u32::MAX as i64 + 1;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -289,7 +289,7 @@ fn test_int_literal_hex_l_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{:#x}u", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -301,7 +301,7 @@ fn test_int_literal_hex_l_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -313,7 +313,7 @@ fn test_int_literal_hex_l_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 19;
 
     assert_eq!(exp_t, act_t);
@@ -321,11 +321,11 @@ fn test_int_literal_hex_l_suffix_max_u64() {
 }
 
 #[test]
-fn test_int_literal_hex_l_suffix_overflow_u64_is_u64() {
+fn test_int_literal_hex_l_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{:#x}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -337,7 +337,7 @@ fn test_int_literal_hex_ul_suffix_min_i32_is_u64() {
     let value = 0;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 5;
 
     assert_eq!(exp_t, act_t);
@@ -349,7 +349,7 @@ fn test_int_literal_hex_ul_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -361,7 +361,7 @@ fn test_int_literal_hex_ul_suffix_min_u32_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -373,7 +373,7 @@ fn test_int_literal_hex_ul_suffix_max_u32_is_u64() {
     let value = u32::MAX as u64;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -385,7 +385,7 @@ fn test_int_literal_hex_ul_suffix_min_i64_is_u64() {
     let value = u32::MAX as u64 + 1;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -397,7 +397,7 @@ fn test_int_literal_hex_ul_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -409,7 +409,7 @@ fn test_int_literal_hex_ul_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -421,7 +421,7 @@ fn test_int_literal_hex_ul_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 20;
 
     assert_eq!(exp_t, act_t);
@@ -429,11 +429,59 @@ fn test_int_literal_hex_ul_suffix_max_u64() {
 }
 
 #[test]
-fn test_int_literal_hex_ul_suffix_overflow_u64_is_u64() {
+fn test_int_literal_hex_ul_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("{:#x}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
+    let exp_sz = 21;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_hex_ll_suffix_max_i64() {
+    let value = i64::MAX;
+    let input = format!("{:#x}ll", value);
+
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
+    let exp_sz = 20;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_hex_ll_suffix_min_u64() {
+    let value = i64::MAX as u64 + 1;
+    let input = format!("{:#x}LL", value);
+
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
+    let exp_sz = 20;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_hex_ull_suffix_min_i32_is_u64() {
+    let value = 1;
+    let input = format!("{:#x}ull", value);
+
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
+    let exp_sz = 6;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_hex_ll_suffix_overflow() {
+    let value = u64::MAX as i128 + 1;
+    let input = format!("{:#x}ll", value);
+
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 21;
 
     assert_eq!(exp_t, act_t);
@@ -7,7 +7,7 @@ fn test_int_literal_oct_no_suffix_min_i32() {
     let value = 0;
     let input = format!("{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
     let exp_sz = 1;
 
     assert_eq!(exp_t, act_t);
@@ -21,7 +21,7 @@ fn test_int_literal_oct_no_suffix_max_i32() {
     let value = i32::MAX;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -35,7 +35,7 @@ fn test_int_literal_oct_no_suffix_min_u32() {
     let value = i32::MAX as u32 + 1;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -49,7 +49,7 @@ fn test_int_literal_oct_no_suffix_max_u32() {
     let value = u32::MAX;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -63,7 +63,7 @@ fn test_int_literal_oct_no_suffix_min_i64() {
     let value = u32::MAX as i64 + 1;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 12;
 
     assert_eq!(exp_t, act_t);
@@ -77,7 +77,7 @@ fn test_int_literal_oct_no_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 22;
 
     assert_eq!(exp_t, act_t);
@@ -91,7 +91,7 @@ fn test_int_literal_oct_no_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -105,7 +105,7 @@ fn test_int_literal_oct_no_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -119,7 +119,7 @@ fn test_int_literal_oct_no_suffix_overflow_u64() {
     let value = u64::MAX as i128 + 1;
     let input = format!("0{:o}", value);
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -130,7 +130,7 @@ fn test_int_literal_oct_no_suffix_overflow_u64() {
 fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
     let input = "00000000000000000000000000000000000000000000000000000000000000000000000000000001";
 
-    let (exp_t, act_t, act_sz) = exp_i32_and_actual(1, input);
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(1, input);
     let exp_sz = 80;
 
     assert_eq!(exp_t, act_t);
@@ -150,7 +150,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = 0;
      let input = format!("{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
      let exp_sz = 2;
 
      assert_eq!(exp_t, act_t);
@@ -164,7 +164,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = i32::MAX as u32;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
      let exp_sz = 13;
 
      assert_eq!(exp_t, act_t);
@@ -178,7 +178,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = i32::MAX as u32 + 1;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
      let exp_sz = 13;
 
      assert_eq!(exp_t, act_t);
@@ -192,7 +192,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = u32::MAX;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u32_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_uint_and_actual(value, &input);
      let exp_sz = 13;
 
      assert_eq!(exp_t, act_t);
@@ -206,7 +206,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = u32::MAX as u64 + 1;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
      let exp_sz = 13;
 
      assert_eq!(exp_t, act_t);
@@ -220,7 +220,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = i64::MAX as u64;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
      let exp_sz = 23;
 
      assert_eq!(exp_t, act_t);
@@ -234,7 +234,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = i64::MAX as u64 + 1;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
      let exp_sz = 24;
 
      assert_eq!(exp_t, act_t);
@@ -248,7 +248,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = u64::MAX;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+     let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
      let exp_sz = 24;
 
      assert_eq!(exp_t, act_t);
@@ -262,7 +262,7 @@ fn test_int_literal_oct_no_suffix_leading_zeros_i32() {
      let value = u64::MAX as i128 + 1;
      let input = format!("0{:o}u", value);
 
-     let (exp_t, act_t, act_sz) = exp_u32_and_actual(0, &input);
+     let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
      let exp_sz = 24;
 
      assert_eq!(exp_t, act_t);
@@ -282,7 +282,7 @@ fn test_int_literal_oct_l_suffix_min_i32_is_i64() {
     let value = 0;
     let input = format!("{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 2;
 
     assert_eq!(exp_t, act_t);
@@ -296,7 +296,7 @@ fn test_int_literal_oct_l_suffix_max_i32_is_i64() {
     let value = i32::MAX as i64;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -310,7 +310,7 @@ fn test_int_literal_oct_l_suffix_min_u32_is_i64() {
     let value = i32::MAX as i64 + 1;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -324,7 +324,7 @@ fn test_int_literal_oct_l_suffix_max_u32_is_i64() {
     let value = u32::MAX as i64;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -338,7 +338,7 @@ fn test_int_literal_oct_l_suffix_min_i64() {
     let value = u32::MAX as i64 + 1;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 13;
 
     assert_eq!(exp_t, act_t);
@@ -352,7 +352,7 @@ fn test_int_literal_oct_l_suffix_max_i64() {
     let value = i64::MAX;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_long_and_actual(value, &input);
     let exp_sz = 23;
 
     assert_eq!(exp_t, act_t);
@@ -366,7 +366,7 @@ fn test_int_literal_oct_l_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 24;
 
     assert_eq!(exp_t, act_t);
@@ -380,7 +380,7 @@ fn test_int_literal_oct_l_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 24;
 
     assert_eq!(exp_t, act_t);
@@ -388,13 +388,13 @@ fn test_int_literal_oct_l_suffix_max_u64() {
 }
 
 #[test]
-fn test_int_literal_oct_l_suffix_overflow_u64_is_i64() {
+fn test_int_literal_oct_l_suffix_overflow_u64() {
     // {:o} format does not include leading zero
     // {:#o} format emits 0o10.... which isn't what we need
     let value = u64::MAX as i128 + 1;
     let input = format!("0{:o}l", value);
 
-    let (exp_t, act_t, act_sz) = exp_i64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
     let exp_sz = 24;
 
     assert_eq!(exp_t, act_t);
@@ -414,7 +414,7 @@ fn test_int_literal_oct_ul_suffix_min_i32_is_u64() {
     let value = 0;
     let input = format!("{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 3;
 
     assert_eq!(exp_t, act_t);
@@ -428,7 +428,7 @@ fn test_int_literal_oct_ul_suffix_max_i32_is_u64() {
     let value = i32::MAX as u64;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 14;
 
     assert_eq!(exp_t, act_t);
@@ -442,7 +442,7 @@ fn test_int_literal_oct_ul_suffix_min_u32_is_u64() {
     let value = i32::MAX as u64 + 1;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 14;
 
     assert_eq!(exp_t, act_t);
@@ -456,7 +456,7 @@ fn test_int_literal_oct_ul_suffix_max_u32_is_u64() {
     let value = u32::MAX as u64;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 14;
 
     assert_eq!(exp_t, act_t);
@@ -470,7 +470,7 @@ fn test_int_literal_oct_ul_suffix_min_i64_is_u64() {
     let value = u32::MAX as u64 + 1;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 14;
 
     assert_eq!(exp_t, act_t);
@@ -484,7 +484,7 @@ fn test_int_literal_oct_ul_suffix_max_i64_is_u64() {
     let value = i64::MAX as u64;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 24;
 
     assert_eq!(exp_t, act_t);
@@ -498,7 +498,7 @@ fn test_int_literal_oct_ul_suffix_min_u64() {
     let value = i64::MAX as u64 + 1;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 25;
 
     assert_eq!(exp_t, act_t);
@@ -512,7 +512,7 @@ fn test_int_literal_oct_ul_suffix_max_u64() {
     let value = u64::MAX;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(value, &input);
+    let (exp_t, act_t, act_sz) = exp_ulong_and_actual(value, &input);
     let exp_sz = 25;
 
     assert_eq!(exp_t, act_t);
@@ -520,13 +520,37 @@ fn test_int_literal_oct_ul_suffix_max_u64() {
 }
 
 #[test]
-fn test_int_literal_oct_ul_suffix_overflow_u64_is_u64() {
+fn test_int_literal_oct_ul_suffix_overflow_u64() {
     // {:o} format does not include leading zero
     // {:#o} format emits 0o10.... which isn't what we need
     let value = u64::MAX as i128 + 1;
     let input = format!("0{:o}ul", value);
 
-    let (exp_t, act_t, act_sz) = exp_u64_and_actual(0, &input);
+    let (exp_t, act_t, act_sz) = unknown_and_actual(&input);
+    let exp_sz = 25;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_oct_ll_suffix_max_i64() {
+    let value = i64::MAX;
+    let input = format!("0{:o}ll", value);
+
+    let (exp_t, act_t, act_sz) = exp_long_long_and_actual(value, &input);
+    let exp_sz = 24;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_oct_ll_suffix_min_u64() {
+    let value = i64::MAX as u64 + 1;
+    let input = format!("0{:o}LL", value);
+
+    let (exp_t, act_t, act_sz) = exp_ulong_long_and_actual(value, &input);
     let exp_sz = 25;
 
     assert_eq!(exp_t, act_t);
@@ -0,0 +1,108 @@
+use super::*;
+
+fn exp_c23_int_and_actual(v: i32, input: &str) -> (Token, Token, usize) {
+        let exp_t: Token = IntLitInt(v);
+        let (act_t, act_sz) = actual_as(input, c23());
+        (exp_t, act_t, act_sz)
+}
+
+#[test]
+fn test_int_literal_separator_dec() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(1000000, "1'000'000");
+    let exp_sz = 9;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_hex() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(0xdead, "0xde'ad");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_oct() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(0o17, "01'7");
+    let exp_sz = 4;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_bin() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(0b1010, "0b10'10");
+    let exp_sz = 7;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_with_suffix() {
+    let (act_t, act_sz) = actual_as("1'0ul", c23());
+    let exp_sz = 5;
+
+    assert_eq!(IntLitULong(10), act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_trailing() {
+    // not between two digits so it ends the number, leaving a character constant
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(1, "1'");
+    let exp_sz = 1;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_doubled() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(1, "1''0");
+    let exp_sz = 1;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_after_prefix() {
+    // the x isn't a digit, so this is a 0 followed by junk
+    let (act_t, act_sz) = actual_as("0x'1", c23());
+    let exp_sz = 2;
+
+    assert_eq!(Unknown("0x".to_string()), act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_not_digit_of_radix() {
+    let (exp_t, act_t, act_sz) = exp_c23_int_and_actual(1, "0b1'2");
+    let exp_sz = 3;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_float() {
+    let (act_t, act_sz) = actual_as("1'0.2'5", c23());
+    let exp_sz = 7;
+
+    assert_eq!(Token::FloatLit64(10.25), act_t);
+    assert_eq!(exp_sz, act_sz);
+}
+
+#[test]
+fn test_int_literal_separator_before_c23() {
+    let (exp_t, act_t, act_sz) = exp_int_and_actual(1, "1'000");
+    let exp_sz = 1;
+
+    assert_eq!(exp_t, act_t);
+    assert_eq!(exp_sz, act_sz);
+}
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+/// a file named by a linemarker in the preprocessed unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FileId(u32);
+
+/// one level of an include stack, see `Include`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct IncludeId(u32);
+
+/// the bytes of the preprocessed unit a token was lexed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span {
+            start: start as u32,
+            end: end as u32,
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start as usize
+    }
+
+    pub fn end(&self) -> usize {
+        self.end as usize
+    }
+
+    pub fn len(&self) -> usize {
+        self.end() - self.start()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// from the start of this span to the end of `other`
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+        }
+    }
+}
+
+/// what the flags 3 and 4 of a linemarker say about the file it is in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SystemHeader {
+    #[default]
+    No,
+    /// a system header, where warnings are suppressed
+    Yes,
+    /// a system header whose contents are implicitly wrapped in `extern "C"` (which only
+    /// matters to C++)
+    ExternC,
+}
+
+impl SystemHeader {
+    /// from whether a linemarker has flag 3 and flag 4
+    pub fn from_flags(three: bool, four: bool) -> Self {
+        match (three, four) {
+            (_, true) => SystemHeader::ExternC,
+            (true, false) => SystemHeader::Yes,
+            (false, false) => SystemHeader::No,
+        }
+    }
+
+    pub fn is_system(&self) -> bool {
+        *self != SystemHeader::No
+    }
+}
+
+/// where in the original source a position in the preprocessed unit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+    file: &'a str,
+    line: u32,
+    column: usize,
+    system_header: SystemHeader,
+}
+
+impl<'a> Location<'a> {
+    pub fn file(&self) -> &'a str {
+        self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn system_header(&self) -> SystemHeader {
+        self.system_header
+    }
+}
+
+/// being in `file`, having been included from line `line` of `parent`'s file. Every token
+/// in the same file shares one of these rather than carrying its own copy of the stack
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Include {
+    file: FileId,
+    parent: Option<IncludeId>,
+    line: u32,
+    system: SystemHeader,
+}
+
+/// the lines from `offset` on (until the next segment) are in `include`, the first of
+/// them being `line`, there is one of these for each linemarker
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment {
+    offset: u32,
+    include: IncludeId,
+    line: u32,
+}
+
+/// maps byte offsets in a preprocessed unit back to the file, line and column they came
+/// from. It is built up as the unit is lexed, recording the linemarkers and the offset of
+/// every newline, and answers queries by binary search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceMap<'a> {
+    text: &'a str,
+    files: Vec<&'a str>,
+    file_ids: HashMap<&'a str, FileId>,
+    includes: Vec<Include>,
+    segments: Vec<Segment>,
+    newlines: Vec<u32>,
+    current: IncludeId,
+}
+
+impl<'a> SourceMap<'a> {
+    /// the map of `text`, whose lines from `offset` on are in `file` starting at `line`
+    pub(crate) fn new(
+        text: &'a str,
+        file: &'a str,
+        line: u32,
+        offset: usize,
+        system: SystemHeader,
+    ) -> Self {
+        let mut map = SourceMap {
+            text,
+            files: vec![],
+            file_ids: HashMap::new(),
+            includes: vec![],
+            segments: vec![],
+            newlines: vec![],
+            current: IncludeId(0),
+        };
+        let file = map.file_id(file);
+        map.current = map.include(file, None, 0, system);
+        map.segment(offset, line);
+        map
+    }
+
+    fn file_id(&mut self, name: &'a str) -> FileId {
+        let next = FileId(self.files.len() as u32);
+        let id = *self.file_ids.entry(name).or_insert(next);
+        if id == next {
+            self.files.push(name);
+        }
+        id
+    }
+
+    fn include(
+        &mut self,
+        file: FileId,
+        parent: Option<IncludeId>,
+        line: u32,
+        system: SystemHeader,
+    ) -> IncludeId {
+        self.includes.push(Include {
+            file,
+            parent,
+            line,
+            system,
+        });
+        IncludeId(self.includes.len() as u32 - 1)
+    }
+
+    fn segment(&mut self, offset: usize, line: u32) {
+        self.segments.push(Segment {
+            offset: offset as u32,
+            include: self.current,
+            line,
+        });
+    }
+
+    fn get(&self, include: IncludeId) -> &Include {
+        &self.includes[include.0 as usize]
+    }
+
+    /// a newline at `offset`
+    pub(crate) fn newline(&mut self, offset: usize) {
+        self.newlines.push(offset as u32);
+    }
+
+    /// a linemarker entering `file`, included from `from_line` of the current file, whose
+    /// lines from `offset` on start at `line`
+    pub(crate) fn enter(
+        &mut self,
+        offset: usize,
+        file: &'a str,
+        line: u32,
+        from_line: u32,
+        system: SystemHeader,
+    ) {
+        let file = self.file_id(file);
+        self.current = self.include(file, Some(self.current), from_line, system);
+        self.segment(offset, line);
+    }
+
+    /// a linemarker returning to `file` from the current one
+    pub(crate) fn exit(&mut self, offset: usize, file: &'a str, line: u32, system: SystemHeader) {
+        let file = self.file_id(file);
+        let parent = self.get(self.current).parent;
+        self.current = match parent {
+            Some(parent) if self.get(parent).file == file && self.get(parent).system == system => {
+                parent
+            }
+            // the linemarkers don't match up, trust the marker over the stack
+            Some(parent) => {
+                let Include { parent, line, .. } = *self.get(parent);
+                self.include(file, parent, line, system)
+            }
+            None => self.include(file, None, 0, system),
+        };
+        self.segment(offset, line);
+    }
+
+    /// a linemarker staying at the same depth, possibly renaming the file (e.g. from
+    /// `<built-in>` to the main file) or marking it a system header (`#pragma GCC
+    /// system_header`)
+    pub(crate) fn move_to(
+        &mut self,
+        offset: usize,
+        file: &'a str,
+        line: u32,
+        system: SystemHeader,
+    ) {
+        let file = self.file_id(file);
+        let current = *self.get(self.current);
+        if current.file != file || current.system != system {
+            self.current = self.include(file, current.parent, current.line, system);
+        }
+        self.segment(offset, line);
+    }
+
+    /// all of the preprocessed unit
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// the text `span` covers
+    pub fn spelling(&self, span: Span) -> &'a str {
+        &self.text[span.start()..span.end()]
+    }
+
+    fn file_name(&self, file: FileId) -> &'a str {
+        self.files[file.0 as usize]
+    }
+
+    fn segment_of(&self, offset: usize) -> &Segment {
+        let after = self
+            .segments
+            .partition_point(|segment| segment.offset as usize <= offset);
+        &self.segments[after.saturating_sub(1)]
+    }
+
+    /// the file, line and column `span` starts at
+    pub fn location(&self, span: Span) -> Location<'a> {
+        let offset = span.start();
+        let segment = self.segment_of(offset);
+        let first = self.newlines.partition_point(|n| *n < segment.offset);
+        let before = self.newlines.partition_point(|n| (*n as usize) < offset);
+        let line_start = if before > first {
+            self.newlines[before - 1] as usize + 1
+        } else {
+            segment.offset as usize
+        };
+        let include = self.get(segment.include);
+        Location {
+            file: self.file_name(include.file),
+            line: segment.line + (before - first) as u32,
+            column: self.text[line_start..offset].chars().count() + 1,
+            system_header: include.system,
+        }
+    }
+
+    /// the file and line of each `#include` which led to `span`, innermost first
+    pub fn included_from(&self, span: Span) -> impl Iterator<Item = (&'a str, u32)> + '_ {
+        let include = self.get(self.segment_of(span.start()).include);
+        std::iter::successors(Some(include), |include| {
+            include.parent.map(|parent| self.get(parent))
+        })
+        .filter_map(|include| {
+            let parent = self.get(include.parent?);
+            Some((self.file_name(parent.file), include.line))
+        })
+    }
+
+    /// `span` as diagnostics report it
+    pub fn diagnostic_span(&self, span: Span) -> diagnostics::Span {
+        let location = self.location(span);
+        let length = self.text[span.start()..span.end()].chars().count();
+        diagnostics::Span::new(location.file, location.line, location.column, length)
+    }
+}
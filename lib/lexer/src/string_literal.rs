@@ -1,4 +1,58 @@
+mod concatenate;
 mod internal;
 
+pub use concatenate::{concatenate, is_string_literal};
 pub use internal::string_literal_impl;
 pub use internal::StringLiteral;
+
+use crate::text::char_escape::char_esc_impl;
+use crate::text::hex_escape::hex_esc_impl;
+use crate::text::oct_escape::oct_esc_impl;
+use crate::text::univ_esc::univ_esc_impl;
+use crate::text::{text_state_impl_i16, text_state_impl_i32, text_state_impl_i8};
+use crate::text::{Encoding, TextState};
+use crate::LocationState;
+use crate::Token;
+
+/// consume a string literal from the start of `input` (which must be the opening `"`,
+/// any encoding prefix has already been stripped) returning the token and the number
+/// of chars consumed
+pub fn consume_string_literal(
+    location: &dyn LocationState,
+    input: &str,
+    encoding: Encoding,
+) -> (Token, usize) {
+    let (token, n) = match encoding {
+        Encoding::Char | Encoding::Utf8 => {
+            let text = text_state_impl_i8(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+        Encoding::Char16 => {
+            let text = text_state_impl_i16(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+        Encoding::Char32 | Encoding::WChar => {
+            let text = text_state_impl_i32(input.chars().peekable());
+            (consume_with(location, text.as_ref()), text.chars_consumed())
+        }
+    };
+
+    (encoding.with_prefix(token), n)
+}
+
+fn consume_with<C>(location: &dyn LocationState, text: &dyn TextState<Ch = C>) -> Token {
+    let hex_escape = hex_esc_impl(location, text);
+    let oct_escape = oct_esc_impl(location, text);
+    let univ_escape = univ_esc_impl(location, text);
+
+    let char_escape = char_esc_impl(
+        location,
+        text,
+        hex_escape.as_ref(),
+        oct_escape.as_ref(),
+        univ_escape.as_ref(),
+    );
+
+    let string_literal = string_literal_impl(location, text, char_escape.as_ref());
+    string_literal.consume_string_literal()
+}
@@ -0,0 +1,178 @@
+use diagnostics::{Diagnostic, Diagnostics, Expansion};
+use lang::LangOptions;
+
+use crate::text::Encoding;
+use crate::LocatedToken;
+use crate::Location;
+use crate::LocationState;
+use crate::SourceMap;
+use crate::Token;
+
+use super::consume_string_literal;
+
+fn encoding_of(token: &Token) -> Option<Encoding> {
+    match token {
+        Token::StringLit(_) => Some(Encoding::Char),
+        Token::StringLit_u8(_) => Some(Encoding::Utf8),
+        Token::StringLit_u(_) => Some(Encoding::Char16),
+        Token::StringLit_U(_) => Some(Encoding::Char32),
+        Token::StringLit_L(_) => Some(Encoding::WChar),
+        _ => None,
+    }
+}
+
+/// a token's location along with where diagnostics about it are reported
+struct PieceLocation<'l, 'a> {
+    location: Location<'a>,
+    expansion: Option<&'l Expansion>,
+    lang: &'l LangOptions,
+    diagnostics: &'l Diagnostics,
+}
+
+impl<'l, 'a> LocationState for PieceLocation<'l, 'a> {
+    fn f(&self) -> &str {
+        self.location.file()
+    }
+
+    fn l(&self) -> u32 {
+        self.location.line()
+    }
+
+    fn c(&self) -> usize {
+        self.location.column()
+    }
+
+    fn lang(&self) -> &LangOptions {
+        self.lang
+    }
+
+    fn in_system_header(&self) -> bool {
+        self.location.system_header().is_system()
+    }
+
+    fn report(&self, diagnostic: Diagnostic) {
+        let diagnostic = match self.expansion {
+            Some(expansion) => diagnostic.with_expansion(expansion),
+            None => diagnostic,
+        };
+        let diagnostic = match self.in_system_header() {
+            true => diagnostic.in_system_header(),
+            false => diagnostic,
+        };
+        self.diagnostics.report(diagnostic)
+    }
+}
+
+/// whether `token` is a string literal, which are concatenated with any adjacent to them
+pub fn is_string_literal(token: &Token) -> bool {
+    encoding_of(token).is_some()
+}
+
+/// translation phase 6 (C11 5.1.1.2): a run of adjacent string literal tokens is
+/// concatenated into a single token, `source_map` is of the text the tokens were lexed from
+pub fn concatenate(
+    source_map: &SourceMap,
+    mut run: Vec<LocatedToken>,
+    lang: &LangOptions,
+    diagnostics: &Diagnostics,
+) -> LocatedToken {
+    match run.len() {
+        1 => run.pop().expect("run has one piece"),
+        _ => merge(source_map, run, lang, diagnostics),
+    }
+}
+
+fn merge(
+    source_map: &SourceMap,
+    run: Vec<LocatedToken>,
+    lang: &LangOptions,
+    diagnostics: &Diagnostics,
+) -> LocatedToken {
+    // C11 6.4.5p5 - if any of the literals has an encoding prefix the result has it too,
+    // whether differently prefixed literals can be combined is implementation defined
+    // and like gcc we reject it, carrying on with the first prefix we saw
+    let mut encoding = Encoding::Char;
+    for piece in &run {
+        let piece_encoding = encoding_of(&piece.token).expect("only string literals are merged");
+        match (encoding, piece_encoding) {
+            (_, Encoding::Char) => (),
+            (Encoding::Char, e) => encoding = e,
+            (e, p) if e == p => (),
+            (e, p) => {
+                let loc = PieceLocation {
+                    location: source_map.location(piece.span),
+                    expansion: piece.expansion(),
+                    lang,
+                    diagnostics,
+                };
+                loc.error(
+                    "string-concatenation-prefix",
+                    &format!(
+                        "unsupported non-standard concatenation of string literals with prefixes [{}] and [{}]",
+                        e.prefix(),
+                        p.prefix()
+                    ),
+                );
+            }
+        }
+    }
+
+    let pieces = run.iter().map(|piece| {
+        let piece_encoding = encoding_of(&piece.token).expect("only string literals are merged");
+        match (piece_encoding, &piece.token) {
+            (e, t) if e == encoding => t.clone(),
+            (Encoding::Char, Token::StringLit(v)) if encoding == Encoding::Utf8 => {
+                Token::StringLit_u8(v.clone())
+            }
+            (e, _) => {
+                // the values depend on the encoding so lex the piece again without its prefix
+                let loc = PieceLocation {
+                    location: source_map.location(piece.span),
+                    expansion: piece.expansion(),
+                    lang,
+                    diagnostics,
+                };
+                let body = &source_map.spelling(piece.span)[e.prefix().len()..];
+                let (token, _) = consume_string_literal(&loc, body, encoding);
+                token
+            }
+        }
+    });
+
+    let first = run.first().expect("run has multiple pieces");
+    let last = run.last().expect("run has multiple pieces");
+    let span = first.span.to(last.span);
+    let spelling = source_map.spelling(span);
+
+    let token = pieces
+        .reduce(|acc, next| match (acc, next) {
+            (Token::StringLit(mut a), Token::StringLit(b)) => {
+                a.extend(b);
+                Token::StringLit(a)
+            }
+            (Token::StringLit_u8(mut a), Token::StringLit_u8(b)) => {
+                a.extend(b);
+                Token::StringLit_u8(a)
+            }
+            (Token::StringLit_u(mut a), Token::StringLit_u(b)) => {
+                a.extend(b);
+                Token::StringLit_u(a)
+            }
+            (Token::StringLit_U(mut a), Token::StringLit_U(b)) => {
+                a.extend(b);
+                Token::StringLit_U(a)
+            }
+            (Token::StringLit_L(mut a), Token::StringLit_L(b)) => {
+                a.extend(b);
+                Token::StringLit_L(a)
+            }
+            _ => Token::Unknown(spelling.to_string()),
+        })
+        .expect("run has multiple pieces");
+
+    LocatedToken {
+        token,
+        span,
+        expansion: first.expansion.clone(),
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests;
 
+use diagnostics::Diagnostic;
+
 use crate::char_literal::CharLiteral;
 use crate::text::char_escape;
 use crate::text::char_escape::CharEsc;
@@ -56,13 +58,9 @@ impl<'iter, C> StringLiteral<C> for StringLiteralImpl<'iter, C> {
         loop {
             match self.text.peek() {
                 Some('\n') | None => {
-                    eprintln!(
-                        "{}:{}:{} - error - unterminated string literal",
-                        self.location.f(),
-                        self.location.l(),
-                        self.location.c()
-                    );
-                    break self.text.emit_unknown()
+                    self.location
+                        .error("unterminated-string-literal", "unterminated string literal");
+                    break self.text.emit_unknown();
                 }
                 _ => (),
             }
@@ -75,7 +73,13 @@ impl<'iter, C> StringLiteral<C> for StringLiteralImpl<'iter, C> {
                 ),
                 Some('\"') => {
                     if self.text.seen_error() {
-                        eprintln!("{}:{}:{} - warn - seen error in processing, returning Unknown token not StringLit", self.location.f(), self.location.l(), self.location.c());
+                        self.location.report(
+                            Diagnostic::note(
+                                "invalid-token",
+                                "seen error in processing, returning Unknown token not StringLit",
+                            )
+                            .with_span(self.location.span()),
+                        );
                         break self.text.emit_unknown();
                     }
                     break self.text.emit_string_lit();
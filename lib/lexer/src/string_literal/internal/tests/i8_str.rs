@@ -11,7 +11,7 @@ use crate::Token::StringLit;
 use crate::Token::Unknown;
 
 fn actual(input: &str) -> (Token, usize) {
-    let location = Box::new(TestLocation);
+    let location = Box::new(TestLocation::default());
     let text = text_state_impl_i8(input.chars().peekable());
 
     let hex_escape = hex_esc_impl::<i8>(location.as_ref(), text.as_ref());
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// an interned identifier, equal symbols have equal spellings so comparing and copying
+/// them is as cheap as for an integer. There is one table for the whole process, so a
+/// symbol means the same on any thread it is sent to
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// the spellings of the symbols seen so far, each one is leaked so it lives as long as
+/// the compilation, there are only as many as there are distinct identifiers
+#[derive(Default)]
+struct SymbolTable {
+    by_spelling: HashMap<&'static str, Symbol>,
+    spellings: Vec<&'static str>,
+}
+
+impl SymbolTable {
+    fn intern(&mut self, spelling: &str) -> Symbol {
+        if let Some(symbol) = self.by_spelling.get(spelling) {
+            return *symbol;
+        }
+        let spelling: &'static str = Box::leak(spelling.to_string().into_boxed_str());
+        let symbol = Symbol(self.spellings.len() as u32);
+        self.spellings.push(spelling);
+        self.by_spelling.insert(spelling, symbol);
+        symbol
+    }
+}
+
+static SYMBOLS: OnceLock<Mutex<SymbolTable>> = OnceLock::new();
+
+fn symbols() -> MutexGuard<'static, SymbolTable> {
+    SYMBOLS
+        .get_or_init(Mutex::default)
+        .lock()
+        .expect("a thread panicked while interning")
+}
+
+impl Symbol {
+    /// the symbol spelt `spelling`, only the first time a spelling is seen allocates
+    pub fn intern(spelling: &str) -> Self {
+        symbols().intern(spelling)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        symbols().spellings[self.0 as usize]
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,6 +1,27 @@
+use diagnostics::{Diagnostic, Diagnostics};
+use lang::LangOptions;
+
 use crate::LocationState;
 
-pub struct TestLocation;
+#[derive(Debug, Default)]
+pub struct TestLocation {
+    diagnostics: Diagnostics,
+    lang: LangOptions,
+}
+
+impl TestLocation {
+    pub fn with_lang(lang: LangOptions) -> Self {
+        TestLocation {
+            lang,
+            ..Default::default()
+        }
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.diagnostics()
+    }
+}
+
 impl LocationState for TestLocation {
     fn f(&self) -> &str {
         "TEST"
@@ -13,4 +34,15 @@ impl LocationState for TestLocation {
     fn c(&self) -> usize {
         1
     }
+
+    fn lang(&self) -> &LangOptions {
+        &self.lang
+    }
+
+    fn report(&self, diagnostic: Diagnostic) {
+        self.diagnostics.report(diagnostic)
+    }
 }
+
+mod lex;
+mod properties;
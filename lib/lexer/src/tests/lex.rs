@@ -0,0 +1,1075 @@
+use std::rc::Rc;
+
+use diagnostics::{
+    render, Diagnostics, ErrorKind, Expansion, Expansions, Format, Severity, SourceLines, Span,
+    StageError,
+};
+use lang::{LangOptions, Pedantic, Standard};
+
+use crate::lex;
+use crate::lex_with_expansions;
+use crate::Error;
+use crate::Symbol;
+use crate::Token;
+use crate::TokenStream;
+
+fn lex_tokens(input: &str) -> Vec<Token> {
+    lex_tokens_as(input, &LangOptions::default())
+}
+
+fn lex_tokens_as(input: &str, lang: &LangOptions) -> Vec<Token> {
+    let diagnostics = Diagnostics::new();
+    let located = lex(input, lang, &diagnostics).expect("lex should succeed");
+    located.tokens().iter().map(|t| t.token().clone()).collect()
+}
+
+/// the (severity, code, line, column) of everything reported while lexing `input`
+fn lex_diagnostics(input: &str) -> Vec<(Severity, &'static str, u32, usize)> {
+    lex_diagnostics_as(input, &LangOptions::default())
+}
+
+fn lex_diagnostics_as(input: &str, lang: &LangOptions) -> Vec<(Severity, &'static str, u32, usize)> {
+    let diagnostics = Diagnostics::new();
+    let _ = lex(input, lang, &diagnostics);
+    diagnostics
+        .diagnostics()
+        .iter()
+        .map(|d| {
+            let span = d.primary().expect("lexer diagnostics have a location");
+            (d.severity(), d.code(), span.line(), span.column())
+        })
+        .collect()
+}
+
+fn in_file(body: &str) -> String {
+    format!("# 1 \"test.c\"\n{body}")
+}
+
+#[test]
+fn test_lex_dec_int() {
+    let input = in_file("42;");
+    let exp = vec![Token::IntLitInt(42), Token::Semi];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_oct_and_hex_int() {
+    let input = in_file("017 0x1fU 0");
+    let exp = vec![
+        Token::IntLitInt(0o17),
+        Token::IntLitUInt(0x1f),
+        Token::IntLitInt(0),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_int_types() {
+    let input = in_file("2147483648 0xFFFFFFFF 0x8000000000000000 1ll 1ull");
+    let exp = vec![
+        Token::IntLitLong(2147483648),
+        Token::IntLitUInt(0xFFFFFFFF),
+        Token::IntLitULong(0x8000000000000000),
+        Token::IntLitLongLong(1),
+        Token::IntLitULongLong(1),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_int_too_large() {
+    let input = in_file("x = 9223372036854775808;");
+    let exp = vec![(Severity::Error, "integer-too-large", 1, 5)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_dec_float() {
+    let input = in_file("1.5 .25f 1e3");
+    let exp = vec![
+        Token::FloatLit64(1.5),
+        Token::FloatLit32(0.25),
+        Token::FloatLit64(1000.0),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_dec_float_with_leading_zero() {
+    let input = in_file("08e1 09e-1 01e1 09.5 089.5");
+    let exp = vec![
+        Token::FloatLit64(80.0),
+        Token::FloatLit64(0.9),
+        Token::FloatLit64(10.0),
+        Token::FloatLit64(9.5),
+        Token::FloatLit64(89.5),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+    assert!(lex_diagnostics(&input).is_empty());
+}
+
+#[test]
+fn test_lex_dot_dot_float() {
+    let input = in_file("..5");
+    let exp = vec![Token::Dot, Token::FloatLit64(0.5)];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_numeric_locations() {
+    let input = in_file("( 0x10;\n  7");
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(&input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let source_map = lexed.source_map();
+
+    let hex = lexed
+        .tokens()
+        .iter()
+        .find(|t| t.token() == &Token::IntLitInt(0x10))
+        .expect("should have lexed the hex literal");
+    let loc = source_map.location(hex.span());
+    assert_eq!(
+        ("test.c", 1, 3, "0x10"),
+        (loc.file(), loc.line(), loc.column(), source_map.spelling(hex.span()))
+    );
+
+    let seven = lexed.tokens().last().expect("should have lexed tokens");
+    let loc = source_map.location(seven.span());
+    assert_eq!(&Token::IntLitInt(7), seven.token());
+    assert_eq!(
+        ("test.c", 2, 3, "7"),
+        (loc.file(), loc.line(), loc.column(), source_map.spelling(seven.span()))
+    );
+}
+
+#[test]
+fn test_lex_string_literal() {
+    let input = in_file("\"ab\\n\";");
+    let exp = vec![Token::StringLit(vec![0x61, 0x62, 0x0a]), Token::Semi];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_prefixed_string_literals() {
+    let input = in_file("L\"a\" ; u\"b\" ; U\"c\" ; u8\"d\"");
+    let exp = vec![
+        Token::StringLit_L(vec![0x61]),
+        Token::Semi,
+        Token::StringLit_u(vec![0x62]),
+        Token::Semi,
+        Token::StringLit_U(vec![0x63]),
+        Token::Semi,
+        Token::StringLit_u8(vec![0x64]),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_string_concatenation() {
+    let input = in_file("\"ab\" \"c\"\n  \"d\";");
+    let exp = vec![Token::StringLit(vec![0x61, 0x62, 0x63, 0x64]), Token::Semi];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_string_concatenation_location() {
+    let input = in_file("( \"ab\" \"c\"");
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(&input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let span = lexed.tokens().last().expect("should have lexed tokens").span();
+    let loc = lexed.source_map().location(span);
+
+    assert_eq!(
+        ("test.c", 1, 3, "\"ab\" \"c\""),
+        (loc.file(), loc.line(), loc.column(), lexed.source_map().spelling(span))
+    );
+}
+
+#[test]
+fn test_lex_string_concatenation_widens() {
+    let input = in_file("\"a\\xff\" L\"b\" \"\\u00e9\"");
+    let exp = vec![Token::StringLit_L(vec![0x61, 0xff, 0x62, 0xe9])];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_string_concatenation_utf8() {
+    let input = in_file("\"a\" u8\"\\u00e9\"");
+    let exp = vec![Token::StringLit_u8(vec![0x61, 0xc3u8 as i8, 0xa9u8 as i8])];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_string_concatenation_mixed_prefixes() {
+    let input = in_file("u\"a\" U\"b\"");
+    let diagnostics = Diagnostics::new();
+
+    let lexed = lex(&input, &LangOptions::default(), &diagnostics);
+    assert!(matches!(lexed, Err(Error::Diagnosed(_))));
+}
+
+#[test]
+fn test_lex_string_concatenation_stops_at_other_tokens() {
+    let input = in_file("\"a\" , \"b\"");
+    let exp = vec![
+        Token::StringLit(vec![0x61]),
+        Token::Comma,
+        Token::StringLit(vec![0x62]),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_char_literals() {
+    let input = in_file("'a' L'b' u'c' U'd' '\\n'");
+    let exp = vec![
+        Token::CharLit(0x61),
+        Token::CharLit_L(0x62),
+        Token::CharLit_u(0x63),
+        Token::CharLit_U(0x64),
+        Token::CharLit(0x0a),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_identifiers_and_keywords() {
+    let input = in_file("static int _Bool x_1 Lx u8 \\u00e9t\\U000000e9");
+    let exp = vec![
+        Token::KwStatic,
+        Token::KwInt,
+        Token::Kw_Bool,
+        Token::Identifier(Symbol::intern("x_1")),
+        Token::Identifier(Symbol::intern("Lx")),
+        Token::Identifier(Symbol::intern("u8")),
+        Token::Identifier(Symbol::intern("\u{e9}t\u{e9}")),
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_punctuators() {
+    let input = in_file("a *= *b << 2 <<= c;");
+    let exp = vec![
+        Token::Identifier(Symbol::intern("a")),
+        Token::StarEql,
+        Token::Star,
+        Token::Identifier(Symbol::intern("b")),
+        Token::LThLTh,
+        Token::IntLitInt(2),
+        Token::LThLThEql,
+        Token::Identifier(Symbol::intern("c")),
+        Token::Semi,
+    ];
+
+    assert_eq!(exp, lex_tokens(&input));
+}
+
+#[test]
+fn test_lex_return_0() {
+    let input = include_str!("../asset/return_0.i");
+    let exp = vec![
+        Token::KwInt,
+        Token::Identifier(Symbol::intern("main")),
+        Token::LParen,
+        Token::KwVoid,
+        Token::RParen,
+        Token::LBrace,
+        Token::KwReturn,
+        Token::IntLitInt(0),
+        Token::Semi,
+        Token::RBrace,
+    ];
+
+    assert_eq!(exp, lex_tokens(input));
+}
+
+#[test]
+fn test_lex_include_a() {
+    let input = include_str!("../asset/include_a.i");
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let source_map = lexed.source_map();
+
+    let b = lexed
+        .tokens()
+        .iter()
+        .find(|t| t.token() == &Token::Identifier(Symbol::intern("b")))
+        .expect("should have lexed b");
+    let loc = source_map.location(b.span());
+    assert_eq!(
+        ("src/asset/include_b.h", 3, 11),
+        (loc.file(), loc.line(), loc.column())
+    );
+    let included_from: Vec<(&str, u32)> = source_map.included_from(b.span()).collect();
+    let exp = vec![("src/asset/include_a.h", 3), ("src/asset/include_a.c", 1)];
+    assert_eq!(exp, included_from);
+
+    let ret = lexed
+        .tokens()
+        .iter()
+        .find(|t| t.token() == &Token::KwReturn)
+        .expect("should have lexed return");
+    let loc = source_map.location(ret.span());
+    assert_eq!(0, source_map.included_from(ret.span()).count());
+    assert_eq!(
+        ("src/asset/include_a.c", 4, 5),
+        (loc.file(), loc.line(), loc.column())
+    );
+}
+
+#[test]
+fn test_lex_clean_input_no_diagnostics() {
+    let input = include_str!("../asset/return_0.i");
+
+    assert_eq!(
+        vec![] as Vec<(Severity, &str, u32, usize)>,
+        lex_diagnostics(input)
+    );
+}
+
+#[test]
+fn test_lex_stray_char() {
+    let input = in_file("int a;\n  @ b;");
+    let exp = vec![(Severity::Error, "stray-char", 2, 3)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_unterminated_string() {
+    let input = in_file("x = \"abc\n;");
+    let exp = vec![(Severity::Error, "unterminated-string-literal", 1, 5)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_unterminated_char() {
+    let input = in_file("x = 'a;\ny");
+    let exp = vec![(Severity::Error, "unterminated-char-literal", 1, 5)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_unknown_escape_is_warning() {
+    let input = in_file("'\\q'");
+    let exp = vec![(Severity::Warning, "unknown-escape", 1, 1)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_long_double_overflow_is_warning() {
+    let input = in_file("1e5000L 0x1p16384L");
+    let exp = vec![
+        (Severity::Warning, "overflow", 1, 1),
+        (Severity::Warning, "overflow", 1, 9),
+    ];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_mixed_prefix_concatenation() {
+    let input = in_file("u\"a\" U\"b\"");
+    let exp = vec![(Severity::Error, "string-concatenation-prefix", 1, 6)];
+
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+#[test]
+fn test_lex_errors_fail() {
+    let input = in_file("a @ b;");
+    let diagnostics = Diagnostics::new();
+
+    let Err(Error::Diagnosed(errors)) = lex(&input, &LangOptions::default(), &diagnostics) else {
+        panic!("lex should fail on a stray char");
+    };
+    assert_eq!(
+        vec!["stray-char"],
+        errors.iter().map(|d| d.code()).collect::<Vec<_>>()
+    );
+    assert_eq!(ErrorKind::User, Error::Diagnosed(errors).kind());
+}
+
+#[test]
+fn test_lex_warnings_succeed() {
+    let input = in_file("'\\q'");
+    let diagnostics = Diagnostics::new();
+
+    assert!(lex(&input, &LangOptions::default(), &diagnostics).is_ok());
+    assert_eq!(1, diagnostics.warning_count());
+}
+
+/// `F` defined at test.c:1 and used at test.c:2:5, as the preprocessor would describe it
+fn expansion_of_f() -> Rc<Expansion> {
+    Rc::new(Expansion::new(
+        "F",
+        Span::new("test.c", 2, 5, 1),
+        Span::point("test.c", 1, 9),
+        None,
+    ))
+}
+
+#[test]
+fn test_lex_expansions() {
+    // `int F;` with `#define F x;` before it
+    let input = "# 1 \"test.c\"\n\nint x;;";
+    let mut expansions = Expansions::new();
+    expansions.insert(input.find('x').unwrap(), expansion_of_f());
+    expansions.insert(input.find(';').unwrap(), expansion_of_f());
+    let diagnostics = Diagnostics::new();
+
+    let lang = LangOptions::default();
+    let lexed =
+        lex_with_expansions(input, &expansions, &lang, &diagnostics).expect("lex should succeed");
+    let names: Vec<Option<&str>> = lexed
+        .tokens()
+        .iter()
+        .map(|t| t.expansion().map(|e| e.name()))
+        .collect();
+    assert_eq!(vec![None, Some("F"), Some("F"), None], names);
+}
+
+#[test]
+fn test_lex_expansion_diagnostics() {
+    // `int F;` with `#define F @` before it
+    let input = "# 1 \"test.c\"\n\nint @;";
+    let mut expansions = Expansions::new();
+    expansions.insert(input.find('@').unwrap(), expansion_of_f());
+    let diagnostics = Diagnostics::new();
+
+    let lang = LangOptions::default();
+    let Err(Error::Diagnosed(errors)) = lex_with_expansions(input, &expansions, &lang, &diagnostics)
+    else {
+        panic!("lex should fail on a stray char");
+    };
+    let [stray] = &errors[..] else {
+        panic!("expected one error, got {errors:?}");
+    };
+    let labels: Vec<&str> = stray.secondary().iter().map(|l| l.message()).collect();
+    assert_eq!(
+        vec!["in expansion of macro \"F\" defined at test.c:1"],
+        labels
+    );
+}
+
+#[test]
+fn test_lex_keywords_by_standard() {
+    let input = in_file("inline restrict _Bool _Generic");
+    let c89 = LangOptions::new(Standard::C89);
+    let exp = vec![
+        Token::Identifier(Symbol::intern("inline")),
+        Token::Identifier(Symbol::intern("restrict")),
+        Token::Kw_Bool,
+        Token::Kw_Generic,
+    ];
+    assert_eq!(exp, lex_tokens_as(&input, &c89));
+    assert!(lex_diagnostics_as(&input, &c89).is_empty());
+
+    let c99 = LangOptions::new(Standard::C99);
+    let exp = vec![
+        Token::KwInline,
+        Token::KwRestrict,
+        Token::Kw_Bool,
+        Token::Kw_Generic,
+    ];
+    assert_eq!(exp, lex_tokens_as(&input, &c99));
+}
+
+#[test]
+fn test_lex_pedantic_keywords() {
+    let input = in_file("_Bool _Static_assert");
+    let mut lang = LangOptions::new(Standard::C99);
+    lang.pedantic = Pedantic::Warn;
+    let exp = vec![(Severity::Warning, "pedantic", 1, 7)];
+    assert_eq!(exp, lex_diagnostics_as(&input, &lang));
+
+    lang.pedantic = Pedantic::Error;
+    let exp = vec![(Severity::Error, "pedantic", 1, 7)];
+    assert_eq!(exp, lex_diagnostics_as(&input, &lang));
+
+    lang.standard = Standard::C11;
+    assert!(lex_diagnostics_as(&input, &lang).is_empty());
+}
+
+#[test]
+fn test_lex_ucns_in_c89() {
+    let c89 = LangOptions::new(Standard::C89);
+
+    // still decoded in a string literal, but not portable
+    let input = in_file("\"\\u00e9\"");
+    let exp = vec![Token::StringLit(vec![0xc3u8 as i8, 0xa9u8 as i8])];
+    assert_eq!(exp, lex_tokens_as(&input, &c89));
+    let exp = vec![(Severity::Warning, "c89-ucn", 1, 1)];
+    assert_eq!(exp, lex_diagnostics_as(&input, &c89));
+
+    // and not allowed at all in an identifier
+    let input = in_file("a\\u00e9");
+    let codes: Vec<&str> = lex_diagnostics_as(&input, &c89)
+        .iter()
+        .map(|(_, code, _, _)| *code)
+        .collect();
+    assert!(codes.contains(&"stray-backslash"));
+}
+
+#[test]
+fn test_lex_stray_char_message() {
+    // as gcc shows them, non-ASCII and control chars by their first byte in octal
+    for (input, exp) in [("@", "@"), ("\u{1f600}", "\\360"), ("\x01", "\\1")] {
+        let diagnostics = Diagnostics::new();
+        let _ = lex(&in_file(input), &LangOptions::new(Standard::C89), &diagnostics);
+        let messages: Vec<String> = diagnostics
+            .diagnostics()
+            .iter()
+            .map(|d| d.message().to_string())
+            .collect();
+        assert_eq!(vec![format!("stray '{exp}' in program")], messages, "{input}");
+    }
+}
+
+#[test]
+fn test_lex_stray_backslash_message() {
+    // quoted as gcc has it, whether the backslash starts the identifier or is in it
+    let c89 = LangOptions::new(Standard::C89);
+    for input in ["\\u00e9", "a\\u00e9"] {
+        let diagnostics = Diagnostics::new();
+        let _ = lex(&in_file(input), &c89, &diagnostics);
+        let messages: Vec<String> = diagnostics
+            .diagnostics()
+            .iter()
+            .filter(|d| d.code() == "stray-backslash")
+            .map(|d| d.message().to_string())
+            .collect();
+        assert_eq!(vec!["stray '\\' in program"], messages, "{input}");
+    }
+}
+
+#[test]
+fn test_lex_c23_keywords() {
+    let input = in_file("bool true false nullptr _BitInt");
+    let c23 = LangOptions::new(Standard::C23);
+    let exp = vec![
+        Token::KwBool,
+        Token::KwTrue,
+        Token::KwFalse,
+        Token::KwNullptr,
+        Token::Kw_BitInt,
+    ];
+    assert_eq!(exp, lex_tokens_as(&input, &c23));
+
+    let exp = vec![
+        Token::Identifier(Symbol::intern("bool")),
+        Token::Identifier(Symbol::intern("true")),
+        Token::Identifier(Symbol::intern("false")),
+        Token::Identifier(Symbol::intern("nullptr")),
+        Token::Kw_BitInt,
+    ];
+    assert_eq!(exp, lex_tokens_as(&input, &LangOptions::default()));
+}
+
+#[test]
+fn test_lex_u8_char_literal() {
+    let input = in_file("u8'a'");
+    let c23 = LangOptions::new(Standard::C23);
+    assert_eq!(vec![Token::CharLit_u8('a' as i32)], lex_tokens_as(&input, &c23));
+
+    // before C23 it's an identifier followed by a character constant
+    let exp = vec![Token::Identifier(Symbol::intern("u8")), Token::CharLit('a' as i32)];
+    assert_eq!(exp, lex_tokens_as(&input, &LangOptions::default()));
+
+    // the i8 backend also sees two bytes and warns about a multi-char constant
+    let input = in_file("u8'\\u00e9'");
+    let diagnostics = lex_diagnostics_as(&input, &c23);
+    assert!(diagnostics.contains(&(Severity::Error, "u8-char-too-large", 1, 1)));
+}
+
+#[test]
+fn test_lex_digit_separators() {
+    let input = in_file("1'000 0x1'0");
+    let c23 = LangOptions::new(Standard::C23);
+    let exp = vec![Token::IntLitInt(1000), Token::IntLitInt(16)];
+    assert_eq!(exp, lex_tokens_as(&input, &c23));
+}
+
+#[test]
+fn test_lex_binary_constants() {
+    let input = in_file("0b101");
+    assert_eq!(vec![Token::IntLitInt(5)], lex_tokens(&input));
+    assert!(lex_diagnostics(&input).is_empty());
+
+    let mut lang = LangOptions {
+        pedantic: Pedantic::Warn,
+        ..Default::default()
+    };
+    let exp = vec![(Severity::Warning, "pedantic", 1, 1)];
+    assert_eq!(exp, lex_diagnostics_as(&input, &lang));
+
+    lang.standard = Standard::C23;
+    assert!(lex_diagnostics_as(&input, &lang).is_empty());
+}
+
+#[test]
+fn test_lex_bit_int_constants() {
+    let input = in_file("1wb 1uwb 0xffWB");
+    let c23 = LangOptions::new(Standard::C23);
+    let exp = vec![
+        Token::IntLitBitInt(1, 2),
+        Token::IntLitUBitInt(1, 1),
+        Token::IntLitBitInt(0xff, 9),
+    ];
+    assert_eq!(exp, lex_tokens_as(&input, &c23));
+    assert!(lex_diagnostics_as(&input, &c23).is_empty());
+}
+
+#[test]
+fn test_lex_gnu_keywords() {
+    let input = in_file("extern __inline int f(char *__restrict s) __attribute__((pure));");
+    let exp = vec![
+        Token::KwExtern,
+        Token::Kw__inline,
+        Token::KwInt,
+        Token::Identifier(Symbol::intern("f")),
+        Token::LParen,
+        Token::KwChar,
+        Token::Star,
+        Token::Kw__restrict,
+        Token::Identifier(Symbol::intern("s")),
+        Token::RParen,
+        Token::Kw__attribute__,
+        Token::LParen,
+        Token::LParen,
+        Token::Identifier(Symbol::intern("pure")),
+        Token::RParen,
+        Token::RParen,
+        Token::Semi,
+    ];
+    assert_eq!(exp, lex_tokens(&input));
+
+    // reserved identifiers, so never reported even with -pedantic
+    let lang = LangOptions {
+        standard: Standard::C89,
+        pedantic: Pedantic::Error,
+    };
+    let input = in_file("__extension__ __typeof__(__int128) __builtin_va_list __label__ __asm__");
+    assert!(lex_diagnostics_as(&input, &lang).is_empty());
+}
+
+#[test]
+fn test_lex_utf8_source() {
+    let input = in_file("char *caf\u{e9} = \"\u{e9}\"; int w = L'\u{e9}'; char16_t *u = u\"\u{1f600}\";");
+    let exp = vec![
+        Token::KwChar,
+        Token::Star,
+        Token::Identifier(Symbol::intern("caf\u{e9}")),
+        Token::Eql,
+        Token::StringLit(vec![0xc3u8 as i8, 0xa9u8 as i8]),
+        Token::Semi,
+        Token::KwInt,
+        Token::Identifier(Symbol::intern("w")),
+        Token::Eql,
+        Token::CharLit_L(0xe9),
+        Token::Semi,
+        Token::Identifier(Symbol::intern("char16_t")),
+        Token::Star,
+        Token::Identifier(Symbol::intern("u")),
+        Token::Eql,
+        Token::StringLit_u(vec![0xd83du16 as i16, 0xde00u16 as i16]),
+        Token::Semi,
+    ];
+    assert_eq!(exp, lex_tokens(&input));
+    assert!(lex_diagnostics(&input).is_empty());
+}
+
+#[test]
+fn test_lex_utf8_not_identifier() {
+    // U+0301 COMBINING ACUTE ACCENT can't start an identifier, U+00A0 NO-BREAK SPACE
+    // can't be in one at all
+    let input = in_file("\u{301}a \u{a0}");
+    let exp = vec![
+        (Severity::Error, "invalid-identifier-start", 1, 1),
+        (Severity::Note, "invalid-token", 1, 1),
+        (Severity::Error, "stray-char", 1, 4),
+    ];
+    assert_eq!(exp, lex_diagnostics(&input));
+}
+
+/// the source line of `test.c` which `test_lex_caret_after_utf8_identifier` lexes
+struct Utf8Line;
+impl SourceLines for Utf8Line {
+    fn line(&self, file: &str, line: u32) -> Option<String> {
+        match (file, line) {
+            ("test.c", 1) => Some("int café = 'a;".to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_lex_caret_after_utf8_identifier() {
+    // the caret goes under the quote, columns count chars not the bytes of the é
+    let input = in_file("int café = 'a;");
+    let diagnostics = Diagnostics::new();
+    let _ = lex(&input, &LangOptions::default(), &diagnostics);
+    let exp = concat!(
+        "test.c:1:12: error: unterminated char literal [unterminated-char-literal]\n",
+        "    1 | int café = 'a;\n",
+        "      |            ^\n",
+    );
+    assert_eq!(exp, render(&diagnostics.diagnostics(), Format::Caret, &Utf8Line));
+}
+
+#[test]
+fn test_symbol_intern() {
+    let a = Symbol::intern("counter");
+    assert_eq!(a, Symbol::intern("counter"));
+    assert_ne!(a, Symbol::intern("Counter"));
+    assert_eq!("counter", a.as_str());
+    assert_eq!("counter", a.to_string());
+}
+
+#[test]
+fn test_symbol_across_threads() {
+    // interned on one thread, spelt and interned again on another
+    let a = std::thread::spawn(|| Symbol::intern("from_another_thread"))
+        .join()
+        .expect("interning thread panicked");
+    assert_eq!("from_another_thread", a.as_str());
+    let b = std::thread::spawn(move || (a.as_str(), Symbol::intern("from_another_thread")))
+        .join()
+        .expect("spelling thread panicked");
+    assert_eq!(("from_another_thread", a), b);
+}
+
+#[test]
+fn test_lex_identifiers_share_symbols() {
+    let tokens = lex_tokens("# 1 \"test.c\"\nint abc; abc = abc2;");
+    assert_eq!(tokens[1], tokens[3]);
+    assert_ne!(tokens[3], tokens[5]);
+}
+
+#[test]
+fn test_lex_fast_paths_fall_back() {
+    // each of these starts out looking like a plain identifier or number
+    assert_eq!(
+        vec![Token::Identifier(Symbol::intern("a\u{e9}"))],
+        lex_tokens("# 1 \"test.c\"\na\\u00e9")
+    );
+    assert_eq!(
+        vec![Token::Identifier(Symbol::intern("ab\u{e9}"))],
+        lex_tokens("# 1 \"test.c\"\nab\u{e9}")
+    );
+    assert_eq!(vec![Token::IntLitLong(12)], lex_tokens("# 1 \"test.c\"\n12l"));
+    assert_eq!(vec![Token::IntLitInt(0o17)], lex_tokens("# 1 \"test.c\"\n017"));
+    assert_eq!(vec![Token::IntLitInt(0)], lex_tokens("# 1 \"test.c\"\n0"));
+    assert_eq!(vec![Token::FloatLit64(1.5)], lex_tokens("# 1 \"test.c\"\n1.5"));
+}
+
+#[test]
+fn test_source_map_lines_and_includes() {
+    let input = "# 1 \"main.c\"\nint a;\n# 1 \"inc.h\" 1\n\nint b;\n# 3 \"main.c\" 2\n  int c;";
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let source_map = lexed.source_map();
+
+    let found: Vec<(&str, &str, u32, usize, usize)> = lexed
+        .tokens()
+        .iter()
+        .filter(|t| matches!(t.token(), Token::Identifier(_)))
+        .map(|t| {
+            let loc = source_map.location(t.span());
+            let depth = source_map.included_from(t.span()).count();
+            (source_map.spelling(t.span()), loc.file(), loc.line(), loc.column(), depth)
+        })
+        .collect();
+    let exp = vec![
+        ("a", "main.c", 1, 5, 0),
+        ("b", "inc.h", 2, 5, 1),
+        ("c", "main.c", 3, 7, 0),
+    ];
+    assert_eq!(exp, found);
+}
+
+#[test]
+fn test_token_stream_matches_lex() {
+    let input = in_file("int main(void) {\n  return \"a\" \"b\"[0] + 0x1f;\n}\n");
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(&input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let streamed: Vec<_> = TokenStream::new(&input, &LangOptions::default(), &diagnostics).collect();
+
+    assert_eq!(lexed.tokens(), &streamed[..]);
+}
+
+#[test]
+fn test_token_stream_lookahead() {
+    let input = in_file("a \"b\" \"c\" ; d");
+    let diagnostics = Diagnostics::new();
+    let mut stream = TokenStream::new(&input, &LangOptions::default(), &diagnostics);
+
+    let peeked = |t: Option<&crate::LocatedToken>| t.map(|t| t.token().clone());
+    assert_eq!(Some(Token::Semi), peeked(stream.peek_nth(2)));
+    assert_eq!(Some(Token::StringLit(vec![0x62, 0x63])), peeked(stream.peek_nth(1)));
+    assert_eq!(None, peeked(stream.peek_nth(4)));
+    assert_eq!(Some(Token::Identifier(Symbol::intern("a"))), peeked(stream.peek()));
+    assert_eq!(4, stream.count());
+}
+
+#[test]
+fn test_token_stream_is_lazy() {
+    let input = in_file("a @\nb");
+    let diagnostics = Diagnostics::new();
+    let mut stream = TokenStream::new(&input, &LangOptions::default(), &diagnostics);
+
+    let a = stream.next().expect("should lex a");
+    assert_eq!(&Token::Identifier(Symbol::intern("a")), a.token());
+    assert!(!diagnostics.has_errors());
+    let at = stream.next().expect("should lex @");
+    assert_eq!(&Token::Unknown("@".to_string()), at.token());
+    assert!(diagnostics.has_errors());
+
+    let b = stream.next().expect("should lex b");
+    let loc = stream.source_map().location(b.span());
+    assert_eq!(("test.c", 2, 1), (loc.file(), loc.line(), loc.column()));
+    assert!(stream.next().is_none());
+}
+
+/// lexing the tokens of `input` as `Display` spells them gives back the same tokens
+fn assert_display_round_trips(input: &str, lang: &LangOptions) {
+    let tokens = lex_tokens_as(&in_file(input), lang);
+    let spelt: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+    let again = lex_tokens_as(&in_file(&spelt.join(" ")), lang);
+    assert_eq!(tokens, again, "spelt as {spelt:?}");
+}
+
+#[test]
+fn test_display_punctuators_and_keywords() {
+    let lang = LangOptions {
+        standard: Standard::C23,
+        ..Default::default()
+    };
+    assert_display_round_trips(
+        "& && &= ! != ^ ^= : , - -- -= -> . ... = == / /= > >= >> >>= { [ ( < <= << <<= % %= \
+         | |= || + += ++ ? } ) ] ; * *= ~ <: :> <% %>",
+        &lang,
+    );
+    assert_display_round_trips(
+        "alignas alignof auto bool break case char const constexpr continue default do double \
+         else enum extern false float for goto if inline int long nullptr register restrict \
+         return short signed sizeof static static_assert struct switch thread_local true \
+         typedef typeof typeof_unqual union unsigned void while _Alignas _Alignof _Atomic \
+         _BitInt _Bool _Complex _Generic _Imaginary _Noreturn _Static_assert _Thread_local \
+         __asm__ __asm __attribute__ __builtin_va_list __extension__ __inline __inline__ \
+         __int128 __label__ __restrict __restrict__ __typeof__ __typeof x _y \u{e9}t\u{e9}",
+        &lang,
+    );
+    assert_eq!("[", Token::LSquare.to_string());
+    assert_eq!("__asm__", lex_tokens("# 1 \"test.c\"\n__asm")[0].to_string());
+}
+
+#[test]
+fn test_display_numbers() {
+    let lang = LangOptions {
+        standard: Standard::C23,
+        ..Default::default()
+    };
+    assert_display_round_trips(
+        "0 7 2147483647 2147483648 0xffffffff 0x7fffffffffffffff 0xffffffffffffffff 1l 1ll \
+         1u 1ul 1ull 1wb 255uwb 0b101 1'000",
+        &lang,
+    );
+    assert_display_round_trips(
+        "0.1 1.5 1e300 1e-300 5e-324 0x1p-1074 1e999 123456789.125 .5f 0.1f 3.4e38f 1e39f",
+        &lang,
+    );
+    assert_display_round_trips(
+        "0.1L 1.5L 1e4000L 1e-4940L 0x1.fffffffffffffffep16383L 1e5000L 0.0L",
+        &lang,
+    );
+    assert_eq!("1.5", Token::FloatLit64(1.5).to_string());
+    assert_eq!("0x1.8p+0L", lex_tokens("# 1 \"test.c\"\n1.5L")[0].to_string());
+}
+
+#[test]
+fn test_display_chars_and_strings() {
+    let lang = LangOptions {
+        standard: Standard::C23,
+        ..Default::default()
+    };
+    assert_display_round_trips(
+        "'a' ' ' '\\'' '\"' '\\\\' '\\n' '\\0' '\\xff' '\\x7f' L'\\u00e9' u'\\uffff' \
+         U'\\U0001f600' u8'a' L'\\xffffffff'",
+        &lang,
+    );
+    assert_display_round_trips(
+        "\"\" \"a\\\"b'c\\\\\" ; \"\\t\\r\\n\\a\\b\\f\\v\" ; \"\\xffa\" ; \"\\u00e9\" ; \
+         u8\"\\u00e9\" ; u\"\\U0001f600x\" ; U\"\\U0001f600\" ; L\"\\x0\\x1g\"",
+        &lang,
+    );
+    assert_eq!("\"\\xff\\x61g\"", lex_tokens("# 1 \"test.c\"\n\"\\xff\" \"ag\"")[0].to_string());
+}
+
+#[test]
+fn test_lexed_dump() {
+    let input = "# 1 \"main.c\"\nint a;\n# 1 \"inc.h\" 1\n  \"x\\n\"\n# 2 \"main.c\" 2\n";
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let exp = "KwInt 'int'\tLoc=<main.c:1:1>\n\
+               Identifier 'a'\tLoc=<main.c:1:5>\n\
+               Semi ';'\tLoc=<main.c:1:6>\n\
+               StringLit '\"x\\n\"'\tLoc=<inc.h:1:3> included from <main.c:2>\n";
+
+    assert_eq!(exp, lexed.dump());
+}
+
+#[test]
+fn test_source_map_system_headers() {
+    let input = "# 1 \"main.c\"\n# 1 \"/usr/include/a.h\" 1 3 4\na\n# 1 \"inc.h\" 1\nb\n\
+                 # 2 \"inc.h\"\n# 2 \"inc.h\" 3\nc\n# 2 \"main.c\" 2\nd\n";
+    let diagnostics = Diagnostics::new();
+    let lexed = lex(input, &LangOptions::default(), &diagnostics).expect("lex should succeed");
+    let source_map = lexed.source_map();
+
+    let found: Vec<(&str, crate::SystemHeader)> = lexed
+        .tokens()
+        .iter()
+        .map(|t| (source_map.spelling(t.span()), source_map.location(t.span()).system_header()))
+        .collect();
+    let exp = vec![
+        ("a", crate::SystemHeader::ExternC),
+        ("b", crate::SystemHeader::No),
+        ("c", crate::SystemHeader::Yes),
+        ("d", crate::SystemHeader::No),
+    ];
+    assert_eq!(exp, found);
+}
+
+#[test]
+fn test_lex_system_header_warnings() {
+    let input = "# 1 \"main.c\"\n# 1 \"/usr/include/a.h\" 1 3\nint c = 'ab';\n# 2 \"main.c\" 2\n";
+    let lang = LangOptions::default();
+
+    let diagnostics = Diagnostics::new();
+    lex(input, &lang, &diagnostics).expect("lex should succeed");
+    assert!(diagnostics.is_empty());
+
+    let diagnostics = Diagnostics::new().with_system_headers(true);
+    lex(input, &lang, &diagnostics).expect("lex should succeed");
+    let reported = diagnostics.diagnostics();
+    assert_eq!(vec!["multichar"], reported.iter().map(|d| d.code()).collect::<Vec<_>>());
+    assert!(reported[0].is_in_system_header());
+}
+
+#[test]
+fn test_lex_system_header_errors() {
+    let input = "# 1 \"main.c\"\n# 1 \"/usr/include/a.h\" 1 3\nint @;\n";
+    let diagnostics = Diagnostics::new();
+
+    let lexed = lex(input, &LangOptions::default(), &diagnostics);
+    assert!(matches!(lexed, Err(Error::Diagnosed(_))));
+}
+
+#[test]
+fn test_lex_system_header_pedantic_errors() {
+    let input = "# 1 \"main.c\"\n# 1 \"/usr/include/a.h\" 1 3\nint b = 0b1;\n# 2 \"main.c\" 2\n0b1";
+    let lang = LangOptions {
+        pedantic: Pedantic::Error,
+        ..Default::default()
+    };
+
+    let diagnostics = Diagnostics::new().with_system_headers(true);
+    let _ = lex(input, &lang, &diagnostics);
+    let severities: Vec<(Severity, u32)> = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|d| (d.severity(), d.primary().expect("should have a span").line()))
+        .collect();
+    assert_eq!(vec![(Severity::Warning, 1), (Severity::Error, 2)], severities);
+}
+
+/// the tokens of `input` whether or not it has errors, and the codes of everything reported
+fn lex_recovering_from(input: &str, diagnostics: &Diagnostics) -> (Vec<Token>, Vec<&'static str>) {
+    let expansions = Expansions::new();
+    let input = in_file(input);
+    let (lexed, _) = crate::lex_recovering(&input, &expansions, &LangOptions::default(), diagnostics);
+    let tokens = lexed.tokens().iter().map(|t| t.token().clone()).collect();
+    let codes = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+    (tokens, codes)
+}
+
+#[test]
+fn test_lex_recovers_from_stray_hash() {
+    let (tokens, codes) = lex_recovering_from("int # x;\n#x\n", &Diagnostics::new());
+    let exp = vec![
+        Token::KwInt,
+        Token::Unknown("#".to_string()),
+        Token::Identifier(Symbol::intern("x")),
+        Token::Semi,
+    ];
+    assert_eq!(exp, tokens);
+    assert_eq!(vec!["stray-char", "invalid-linemarker"], codes);
+}
+
+#[test]
+fn test_lex_recovers_from_malformed_literals() {
+    let input = "a = \"b\n;\nc = 'd\n;\ne = '';\nf = \"\\xg\";\ng = 0x;\nh = 1e+;\ni = \\ j;\nk @ l;\n";
+    let (tokens, codes) = lex_recovering_from(input, &Diagnostics::new());
+
+    // every malformed piece is a single token and the statements around them are intact
+    let unknowns: Vec<&Token> = tokens.iter().filter(|t| matches!(t, Token::Unknown(_))).collect();
+    let exp = ["\"b", "'d", "''", "\"\\xg\"", "0x", "1e+", "\\", "@"];
+    assert_eq!(exp.map(|u| Token::Unknown(u.to_string())).iter().collect::<Vec<_>>(), unknowns);
+    assert_eq!(8, tokens.iter().filter(|t| **t == Token::Semi).count());
+    for name in ["a", "c", "e", "f", "g", "h", "i", "j", "k", "l"] {
+        assert!(tokens.contains(&Token::Identifier(Symbol::intern(name))), "lost {name}");
+    }
+
+    let errors = [
+        "unterminated-string-literal",
+        "unterminated-char-literal",
+        "empty-char-literal",
+        "hex-escape-no-digits",
+        "hex-no-digits",
+        "exponent-no-digits",
+        "stray-backslash",
+        "stray-char",
+    ];
+    assert_eq!(errors.to_vec(), codes.into_iter().filter(|c| *c != "invalid-token").collect::<Vec<_>>());
+}
+
+#[test]
+fn test_lex_recovering_reports_errors() {
+    let expansions = Expansions::new();
+    let input = in_file("int @;");
+    let diagnostics = Diagnostics::new();
+    let (lexed, errors) =
+        crate::lex_recovering(&input, &expansions, &LangOptions::default(), &diagnostics);
+
+    assert_eq!(3, lexed.tokens().len());
+    assert!(matches!(errors, Err(Error::Diagnosed(ref e)) if e.len() == 1));
+}
+
+#[test]
+fn test_lex_max_errors() {
+    let diagnostics = Diagnostics::new().with_max_errors(2);
+    let (tokens, codes) = lex_recovering_from("a @ b @ c @ d\n", &diagnostics);
+
+    assert_eq!(vec!["stray-char", "stray-char", "max-errors"], codes);
+    assert!(!tokens.contains(&Token::Identifier(Symbol::intern("d"))));
+}
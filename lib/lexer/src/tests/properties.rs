@@ -0,0 +1,163 @@
+use hexf_parse::parse_hexf64;
+use lang::{LangOptions, Standard};
+use proptest::prelude::*;
+
+use crate::char_literal::char_literal_impl;
+use crate::numeric_literal::{numeric_literal_impl, numeric_state_impl};
+use crate::string_literal::string_literal_impl;
+use crate::tests::TestLocation;
+use crate::text::char_escape::char_esc_impl;
+use crate::text::hex_escape::hex_esc_impl;
+use crate::text::oct_escape::oct_esc_impl;
+use crate::text::text_state_impl_i8;
+use crate::text::univ_esc::univ_esc_impl;
+use crate::Token;
+
+/// text made mostly of the characters C source is made of, so that the interesting parts
+/// of the lexer are reached far more often than by arbitrary strings
+fn c_like() -> impl Strategy<Value = String> {
+    let pieces = prop_oneof![
+        "[ \t\n]",
+        "[a-zA-Z_][a-zA-Z0-9_]{0,6}",
+        "[0-9.][0-9a-fA-FxXpPeEuUlLwWbB.'+-]{0,10}",
+        "(u8|u|U|L)?[\"'][a-z\\\\'\"0-7xuU{}]{0,8}[\"']?",
+        "\\\\[uU]?[0-9a-fA-F]{0,8}",
+        "[-+*/%<>=!&|^~?:;,.()\\[\\]{}@$`#\\\\]{1,3}",
+        "[\u{80}-\u{10ffff}]",
+        "\n# ?[0-9]{0,3}( \"[a-z./<>-]{0,6}\"?)?( [0-4]){0,4}\n?",
+    ];
+    prop::collection::vec(pieces, 0..24).prop_map(|pieces| pieces.concat())
+}
+
+fn any_lang() -> impl Strategy<Value = LangOptions> {
+    prop_oneof![
+        Just(Standard::C89),
+        Just(Standard::C99),
+        Just(Standard::C11),
+        Just(Standard::C17),
+        Just(Standard::C23),
+    ]
+    .prop_map(|standard| LangOptions {
+        standard,
+        ..Default::default()
+    })
+}
+
+fn check_spans(input: &str, lang: &LangOptions) -> Result<(), TestCaseError> {
+    crate::check_spans(input, lang).map_err(TestCaseError::fail)
+}
+
+fn numeric(input: &str) -> (Token, usize) {
+    let location = TestLocation::default();
+    let numeric = numeric_state_impl(input.chars().peekable());
+    let numeric_literal = numeric_literal_impl(&location, numeric.as_ref());
+    let token = numeric_literal.consume_numeric_literal();
+    (token, numeric.chars_consumed())
+}
+
+fn string(input: &str) -> (Token, usize) {
+    let location = TestLocation::default();
+    let text = text_state_impl_i8(input.chars().peekable());
+    let hex_escape = hex_esc_impl::<i8>(&location, text.as_ref());
+    let oct_escape = oct_esc_impl::<i8>(&location, text.as_ref());
+    let univ_escape = univ_esc_impl::<i8>(&location, text.as_ref());
+    let char_escape = char_esc_impl(
+        &location,
+        text.as_ref(),
+        hex_escape.as_ref(),
+        oct_escape.as_ref(),
+        univ_escape.as_ref(),
+    );
+    let string_literal = string_literal_impl(&location, text.as_ref(), char_escape.as_ref());
+    let token = string_literal.consume_string_literal();
+    (token, text.chars_consumed())
+}
+
+fn char(input: &str) -> (Token, usize) {
+    let location = TestLocation::default();
+    let text = text_state_impl_i8(input.chars().peekable());
+    let hex_escape = hex_esc_impl::<i8>(&location, text.as_ref());
+    let oct_escape = oct_esc_impl::<i8>(&location, text.as_ref());
+    let univ_escape = univ_esc_impl::<i8>(&location, text.as_ref());
+    let char_escape = char_esc_impl(
+        &location,
+        text.as_ref(),
+        hex_escape.as_ref(),
+        oct_escape.as_ref(),
+        univ_escape.as_ref(),
+    );
+    let char_literal = char_literal_impl(&location, text.as_ref(), char_escape.as_ref());
+    let token = char_literal.consume_char_literal();
+    (token, text.chars_consumed())
+}
+
+/// `value` as a C hexadecimal floating constant, `0x1.8p+1` for 3.0
+fn hex_float(value: f64) -> String {
+    let bits = value.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let mantissa = bits & ((1 << 52) - 1);
+    match exponent {
+        0 => format!("0x0.{:013x}p-1022", mantissa),
+        _ => format!("0x1.{:013x}p{:+}", mantissa, exponent - 1023),
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_lex_any_input(input in any::<String>(), lang in any_lang()) {
+        check_spans(&input, &lang)?;
+    }
+
+    #[test]
+    fn test_lex_c_like_input(input in c_like(), lang in any_lang()) {
+        check_spans(&input, &lang)?;
+    }
+
+    #[test]
+    fn test_numeric_consumes_within_input(input in "\\.?[0-9][0-9a-fA-FxXpPeEuUlLwWbB.'+-]{0,16}[^0-9a-zA-Z]?") {
+        let (_, n) = numeric(&input);
+        prop_assert!(n <= input.chars().count());
+    }
+
+    #[test]
+    fn test_string_consumes_within_input(input in "\"[^\n]{0,16}\n?.{0,4}") {
+        let (_, n) = string(&input);
+        prop_assert!(n <= input.chars().count());
+    }
+
+    #[test]
+    fn test_char_consumes_within_input(input in "'[^\n]{0,16}\n?.{0,4}") {
+        let (_, n) = char(&input);
+        prop_assert!(n <= input.chars().count());
+    }
+
+    #[test]
+    fn test_dec_float_round_trips(value in any::<f64>().prop_filter("finite", |v| v.is_finite() && v.is_sign_positive())) {
+        for spelling in [format!("{value:?}"), format!("{value:e}")] {
+            let spelling = match spelling.contains(['.', 'e']) {
+                true => spelling,
+                false => spelling + ".",
+            };
+            let (token, n) = numeric(&spelling);
+            prop_assert_eq!(Token::FloatLit64(spelling.parse().unwrap()), token, "{}", spelling);
+            prop_assert_eq!(spelling.chars().count(), n);
+        }
+    }
+
+    #[test]
+    fn test_dec_float_f_round_trips(value in any::<f32>().prop_filter("finite", |v| v.is_finite() && v.is_sign_positive())) {
+        let spelling = format!("{value:e}f");
+        let (token, n) = numeric(&spelling);
+        prop_assert_eq!(Token::FloatLit32(spelling[..spelling.len() - 1].parse().unwrap()), token, "{}", spelling);
+        prop_assert_eq!(spelling.chars().count(), n);
+    }
+
+    #[test]
+    fn test_hex_float_round_trips(value in any::<f64>().prop_filter("finite", |v| v.is_finite() && v.is_sign_positive())) {
+        let spelling = hex_float(value);
+        let (token, n) = numeric(&spelling);
+        prop_assert_eq!(Token::FloatLit64(value), token.clone(), "{}", spelling);
+        prop_assert_eq!(Token::FloatLit64(parse_hexf64(&spelling, false).unwrap()), token, "{}", spelling);
+        prop_assert_eq!(spelling.chars().count(), n);
+    }
+}
@@ -1,3 +1,4 @@
+mod encoding;
 mod internal;
 
 pub mod char_escape;
@@ -5,6 +6,7 @@ pub mod hex_escape;
 pub mod oct_escape;
 pub mod univ_esc;
 
+pub use encoding::Encoding;
 pub use internal::text_state_impl_i16;
 pub use internal::text_state_impl_i32;
 pub use internal::text_state_impl_i8;
@@ -69,12 +69,9 @@ impl<'iter, C> CharEsc for CharEscImpl<'iter, C> {
             Some('t') => emit_escape!(b'\t'),
             Some('v') => emit_escape!(0x0b),
             None => (),
-            _ => eprintln!(
-                "{}:{}:{} - warn - unknown escape in char literal",
-                self.location.f(),
-                self.location.l(),
-                self.location.c()
-            ),
+            _ => self
+                .location
+                .warning("unknown-escape", "unknown escape in char literal"),
         }
     }
 }
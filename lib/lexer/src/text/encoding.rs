@@ -0,0 +1,41 @@
+use crate::Token;
+
+/// The encoding prefix of a string or character literal, see C11 6.4.4.4 and 6.4.5
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Encoding {
+    /// no prefix: "..." or '...'
+    Char,
+    /// u8"..."
+    Utf8,
+    /// u"..." or u'...', char16_t
+    Char16,
+    /// U"..." or U'...', char32_t
+    Char32,
+    /// L"..." or L'...', wchar_t
+    WChar,
+}
+
+impl Encoding {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Encoding::Char => "",
+            Encoding::Utf8 => "u8",
+            Encoding::Char16 => "u",
+            Encoding::Char32 => "U",
+            Encoding::WChar => "L",
+        }
+    }
+
+    /// `token` as made by the text backend for a literal with this prefix, the backends
+    /// only know their element width so a `u8` string comes out as a plain one, an `L`
+    /// literal as a `U` one and an unknown token without its prefix
+    pub fn with_prefix(&self, token: Token) -> Token {
+        match (self, token) {
+            (Encoding::Utf8, Token::StringLit(v)) => Token::StringLit_u8(v),
+            (Encoding::WChar, Token::StringLit_U(v)) => Token::StringLit_L(v),
+            (Encoding::WChar, Token::CharLit_U(v)) => Token::CharLit_L(v),
+            (_, Token::Unknown(s)) => Token::Unknown(self.prefix().to_string() + &s),
+            (_, t) => t,
+        }
+    }
+}
@@ -54,12 +54,7 @@ impl<'iter> TextState for I16Text<'iter> {
     fn emit_char_lit(&self, location: &dyn LocationState) -> Token {
         match self.output.borrow().len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - empty char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.error("empty-char-literal", "empty char literal");
                 self.emit_unknown()
             }
             1 => {
@@ -67,12 +62,7 @@ impl<'iter> TextState for I16Text<'iter> {
                 Token::CharLit_u((v as i32) & 0x0000ffff)
             }
             _ => {
-                eprintln!(
-                    "{}:{}:{} - warn - multi-char char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("multichar", "multi-char char literal");
                 let v: i16 = *self
                     .output
                     .borrow()
@@ -113,11 +103,9 @@ impl<'iter> TextState for I16Text<'iter> {
     fn push_hex_value(&self, location: &dyn LocationState, hexs: String) {
         match hexs.len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - hex escape with no following hex digits",
-                    location.f(),
-                    location.l(),
-                    location.c()
+                location.error(
+                    "hex-escape-no-digits",
+                    "hex escape with no following hex digits",
                 );
                 self.report_error()
             }
@@ -127,12 +115,7 @@ impl<'iter> TextState for I16Text<'iter> {
                 self.push_c(u16val as i16)
             }
             n => {
-                eprintln!(
-                    "{}:{}:{} - warn - hex escape sequence out of range",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("escape-out-of-range", "hex escape sequence out of range");
                 let u16val = u16::from_str_radix(&hexs[n - 4..], 16)
                     .expect("We're only looking at 4 hex chars, this should parse to a u16");
                 self.push_c(u16val as i16)
@@ -54,22 +54,12 @@ impl<'iter> TextState for I32Text<'iter> {
     fn emit_char_lit(&self, location: &dyn LocationState) -> Token {
         match self.output.borrow().len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - empty char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.error("empty-char-literal", "empty char literal");
                 self.emit_unknown()
             }
             1 => Token::CharLit_U(self.output.borrow()[0]),
             _ => {
-                eprintln!(
-                    "{}:{}:{} - warn - multi-char char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("multichar", "multi-char char literal");
                 let v = *self
                     .output
                     .borrow()
@@ -105,11 +95,9 @@ impl<'iter> TextState for I32Text<'iter> {
     fn push_hex_value(&self, location: &dyn LocationState, hexs: String) {
         match hexs.len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - hex escape with no following hex digits",
-                    location.f(),
-                    location.l(),
-                    location.c()
+                location.error(
+                    "hex-escape-no-digits",
+                    "hex escape with no following hex digits",
                 );
                 self.report_error()
             }
@@ -119,12 +107,7 @@ impl<'iter> TextState for I32Text<'iter> {
                 self.push_c(u32val as i32)
             }
             n => {
-                eprintln!(
-                    "{}:{}:{} - warn - hex escape sequence out of range",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("escape-out-of-range", "hex escape sequence out of range");
                 let u32val = u32::from_str_radix(&hexs[n - 8..], 16)
                     .expect("We're only looking at 4 hex chars, this should parse to a u16");
                 self.push_c(u32val as i32)
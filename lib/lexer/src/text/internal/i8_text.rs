@@ -54,22 +54,12 @@ impl<'iter> TextState for I8Text<'iter> {
     fn emit_char_lit(&self, location: &dyn LocationState) -> Token {
         match self.output.borrow().len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - empty char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.error("empty-char-literal", "empty char literal");
                 self.emit_unknown()
             }
             1 => Token::CharLit(self.output.borrow()[0] as i32),
             _ => {
-                eprintln!(
-                    "{}:{}:{} - warn - multi-char char literal",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("multichar", "multi-char char literal");
                 let mut u: u32 = 0;
                 for o in self.output.borrow().iter() {
                     (u, _) = u.overflowing_shl(8);
@@ -106,11 +96,9 @@ impl<'iter> TextState for I8Text<'iter> {
         match u8::from_str_radix(&octs, 8) {
             Ok(uval) => self.push_u8(uval),
             Err(e) => {
-                eprintln!(
-                    "{}:{}:{} - warn - octal escape sequence out of range: {e}",
-                    location.f(),
-                    location.l(),
-                    location.c()
+                location.warning(
+                    "escape-out-of-range",
+                    &format!("octal escape sequence out of range: {e}"),
                 );
                 unsafe {
                     // we reduce the leading octal char by 4 which should bring the value into the i8 range
@@ -138,11 +126,9 @@ impl<'iter> TextState for I8Text<'iter> {
         }
         match hexs.len() {
             0 => {
-                eprintln!(
-                    "{}:{}:{} - error - hex escape with no following hex digits",
-                    location.f(),
-                    location.l(),
-                    location.c()
+                location.error(
+                    "hex-escape-no-digits",
+                    "hex escape with no following hex digits",
                 );
                 self.report_error()
             }
@@ -152,12 +138,7 @@ impl<'iter> TextState for I8Text<'iter> {
                 self.push_u8(uval)
             }
             n => {
-                eprintln!(
-                    "{}:{}:{} - warn - hex escape sequence out of range",
-                    location.f(),
-                    location.l(),
-                    location.c()
-                );
+                location.warning("escape-out-of-range", "hex escape sequence out of range");
                 let uval = u8::from_str_radix(&hexs[n - 2..], 16)
                     .expect("We're only looking at 2 hex chars, this should parse to a u8");
                 self.push_u8(uval)
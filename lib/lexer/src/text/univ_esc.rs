@@ -1,11 +1,12 @@
+use crate::identifier::{extended_continue, extended_start};
 use crate::text::TextState;
 use crate::LocationState;
 
 pub trait UnivEsc {
     fn consume_universal_short(&self);
     fn consume_universal_long(&self);
-    fn consume_universal_short_identifier(&self);
-    fn consume_universal_long_identifier(&self);
+    fn consume_universal_short_identifier(&self, initial: bool);
+    fn consume_universal_long_identifier(&self, initial: bool);
 }
 
 pub fn univ_esc_impl<'iter, C>(
@@ -28,17 +29,31 @@ impl<'iter, C: 'iter> UnivEscImpl<'iter, C> {
         UnivEscImpl { location, text }
     }
 
+    /// C89 didn't have universal character names, as gcc does we still decode them in
+    /// character constants and string literals but warn they aren't portable
+    fn c89_warning(&self) {
+        if !self.location.lang().universal_character_names() {
+            self.location.warning(
+                "c89-ucn",
+                "universal character names are only valid in C++ and C99",
+            );
+        }
+    }
+
     fn no_restrict(_: u32) -> bool {
         true
     }
 
-    fn identifier_restrict(u: u32) -> bool {
-        match u {
-            0x0300..=0x036f => false,
-            0x1dc0..=0x1dff => false,
-            0x20d0..=0x20ff => false,
-            0xfe20..=0xfe2f => false,
-            _ => true,
+    /// C11 6.4.2.1, a universal character name in an identifier must name a character
+    /// allowed there by the standard, `initial` if it's the first in the identifier, `$`
+    /// is let through as gcc allows it in identifiers
+    fn identifier_restrict(&self, u: u32, initial: bool) -> bool {
+        let lang = self.location.lang();
+        match char::from_u32(u) {
+            Some('$') => true,
+            Some(c) if initial => extended_start(c, lang),
+            Some(c) => extended_continue(c, lang),
+            None => false,
         }
     }
 
@@ -71,13 +86,9 @@ impl<'iter, C: 'iter> UnivEscImpl<'iter, C> {
                 _ => true,
             };
             if !meets_universal_constraints {
-                eprintln!(
-                    "{}:{}:{} - error - invalid universal character name \\{}{}",
-                    self.location.f(),
-                    self.location.l(),
-                    self.location.c(),
-                    esc_char,
-                    hexs
+                self.location.error(
+                    "invalid-ucn",
+                    &format!("invalid universal character name \\{}{}", esc_char, hexs),
                 );
                 self.text.report_error();
                 return;
@@ -85,7 +96,13 @@ impl<'iter, C: 'iter> UnivEscImpl<'iter, C> {
 
             let meets_restrict = restrict(uval);
             if !meets_restrict {
-                eprintln!("{}:{}:{} - error - universal character name \\{}{} does not meet the restrictions applied", self.location.f(), self.location.l(), self.location.c(), esc_char, hexs);
+                self.location.error(
+                    "invalid-ucn-identifier",
+                    &format!(
+                        "universal character name \\{}{} does not meet the restrictions applied",
+                        esc_char, hexs
+                    ),
+                );
                 self.text.report_error();
                 return;
             }
@@ -93,25 +110,20 @@ impl<'iter, C: 'iter> UnivEscImpl<'iter, C> {
             match char::from_u32(uval) {
                 Some(c) => self.text.push_char(c),
                 None => {
-                    eprintln!(
-                        "{}:{}:{} - error - universal character name \\{}{} does not map to a char",
-                        self.location.f(),
-                        self.location.l(),
-                        self.location.c(),
-                        esc_char,
-                        hexs
+                    self.location.error(
+                        "invalid-ucn",
+                        &format!(
+                            "universal character name \\{}{} does not map to a char",
+                            esc_char, hexs
+                        ),
                     );
                     self.text.report_error()
                 }
             }
         } else {
-            eprintln!(
-                "{}:{}:{} - error - incomplete universal character name \\{}{}",
-                self.location.f(),
-                self.location.l(),
-                self.location.c(),
-                esc_char,
-                hexs
+            self.location.error(
+                "incomplete-ucn",
+                &format!("incomplete universal character name \\{}{}", esc_char, hexs),
             );
             self.text.report_error()
         }
@@ -129,6 +141,7 @@ impl<'input, C> UnivEsc for UnivEscImpl<'input, C> {
                 self.location.c()
             ),
         };
+        self.c89_warning();
         self.consume_universal(Self::no_restrict, "u", 4)
     }
 
@@ -142,10 +155,11 @@ impl<'input, C> UnivEsc for UnivEscImpl<'input, C> {
                 self.location.c()
             ),
         };
+        self.c89_warning();
         self.consume_universal(Self::no_restrict, "U", 8)
     }
 
-    fn consume_universal_short_identifier(&self) {
+    fn consume_universal_short_identifier(&self, initial: bool) {
         match self.text.peek() {
             Some('u') => self.text.next(),
             _ => panic!(
@@ -155,10 +169,10 @@ impl<'input, C> UnivEsc for UnivEscImpl<'input, C> {
                 self.location.c()
             ),
         };
-        self.consume_universal(Self::identifier_restrict, "u", 4)
+        self.consume_universal(|u| self.identifier_restrict(u, initial), "u", 4)
     }
 
-    fn consume_universal_long_identifier(&self) {
+    fn consume_universal_long_identifier(&self, initial: bool) {
         match self.text.peek() {
             Some('U') => self.text.next(),
             _ => panic!(
@@ -168,6 +182,6 @@ impl<'input, C> UnivEsc for UnivEscImpl<'input, C> {
                 self.location.c()
             ),
         };
-        self.consume_universal(Self::identifier_restrict, "U", 8)
+        self.consume_universal(|u| self.identifier_restrict(u, initial), "U", 8)
     }
 }
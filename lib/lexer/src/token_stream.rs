@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use diagnostics::{Diagnostics, Expansions};
+use lang::LangOptions;
+
+use crate::state::{Lex, LexStruct};
+use crate::string_literal;
+use crate::{lex_step, LocatedToken, LocationState, SourceMap};
+
+/// the tokens of a preprocessed unit, lexed as they are asked for rather than all up
+/// front. Anything wrong with the input is reported to the diagnostics as it is reached
+#[derive(Debug, Clone)]
+pub struct TokenStream<'a> {
+    state: LexStruct<'a>,
+    /// lexed and with adjacent string literals concatenated, but not yet taken
+    lookahead: VecDeque<LocatedToken>,
+}
+
+impl<'a> TokenStream<'a> {
+    /// the tokens of the preprocessed `input`, with `lang` deciding which spellings are
+    /// keywords and which features of later standards are reported
+    pub fn new(input: &'a str, lang: &LangOptions, diagnostics: &'a Diagnostics) -> Self {
+        Self::from_state(LexStruct::new(input, None, lang, diagnostics))
+    }
+
+    /// as `new`, with `expansions` saying which macro expansion each token of `input` came
+    /// out of
+    pub fn with_expansions(
+        input: &'a str,
+        expansions: &'a Expansions,
+        lang: &LangOptions,
+        diagnostics: &'a Diagnostics,
+    ) -> Self {
+        Self::from_state(LexStruct::new(input, Some(expansions), lang, diagnostics))
+    }
+
+    pub(crate) fn from_state(state: LexStruct<'a>) -> Self {
+        TokenStream {
+            state,
+            lookahead: VecDeque::new(),
+        }
+    }
+
+    /// the next token, without taking it
+    pub fn peek(&mut self) -> Option<&LocatedToken> {
+        self.peek_nth(0)
+    }
+
+    /// the token `n` after the next one, without taking any
+    pub fn peek_nth(&mut self, n: usize) -> Option<&LocatedToken> {
+        self.fill(n);
+        self.lookahead.get(n)
+    }
+
+    /// where the tokens lexed so far came from
+    pub fn source_map(&self) -> &SourceMap<'a> {
+        self.state.source_map()
+    }
+
+    pub fn into_source_map(self) -> SourceMap<'a> {
+        self.state.into_source_map()
+    }
+
+    /// the next token straight out of the lexer, before any concatenation. We stop early
+    /// once `-fmax-errors` has been reached
+    fn next_lexed(&mut self) -> Option<LocatedToken> {
+        loop {
+            if let Some(token) = self.state.next_lexed() {
+                return Some(token);
+            }
+            if self.state.is_empty() || self.state.diagnostics().limit_reached() {
+                return None;
+            }
+            lex_step(&mut self.state);
+        }
+    }
+
+    /// lex until there are more than `n` tokens in the lookahead or the input runs out. A
+    /// string literal can't be handed out until the token after it shows the run has ended
+    fn fill(&mut self, n: usize) {
+        while self.lookahead.len() <= n {
+            let Some(token) = self.next_lexed() else {
+                return;
+            };
+            if !string_literal::is_string_literal(token.token()) {
+                self.lookahead.push_back(token);
+                continue;
+            }
+
+            let mut run = vec![token];
+            let mut after = None;
+            while let Some(token) = self.next_lexed() {
+                if string_literal::is_string_literal(token.token()) {
+                    run.push(token);
+                } else {
+                    after = Some(token);
+                    break;
+                }
+            }
+            let state = &self.state;
+            let token = string_literal::concatenate(
+                state.source_map(),
+                run,
+                state.lang(),
+                state.diagnostics(),
+            );
+            self.lookahead.push_back(token);
+            self.lookahead.extend(after);
+        }
+    }
+}
+
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = LocatedToken;
+
+    fn next(&mut self) -> Option<LocatedToken> {
+        self.fill(0);
+        self.lookahead.pop_front()
+    }
+}
@@ -1,4 +1,7 @@
 use core::fmt::Debug;
+use core::fmt::Display;
+
+use crate::Symbol;
 
 #[derive(PartialEq, Clone)]
 pub enum Token {
@@ -16,6 +19,8 @@ pub enum Token {
     CharLit_u(i32),
     #[allow(non_camel_case_types)]
     CharLit_U(i32),
+    #[allow(non_camel_case_types)]
+    CharLit_u8(i32),
     Colon,
     Comma,
     Dash,
@@ -35,16 +40,26 @@ pub enum Token {
     GThEql,
     GThGTh,
     GThGThEql,
-    Identifier(String),
-    IntLitI32(i32),
-    IntLitI64(i64),
-    IntLitU32(u32),
-    IntLitU64(u64),
+    Identifier(Symbol),
+    /// a `wb` suffixed constant, the value and the width of the `_BitInt` it has
+    IntLitBitInt(u128, u32),
+    IntLitInt(i32),
+    IntLitLong(i64),
+    IntLitLongLong(i64),
+    /// a `uwb` suffixed constant, the value and the width of the `unsigned _BitInt` it has
+    IntLitUBitInt(u128, u32),
+    IntLitUInt(u32),
+    IntLitULong(u64),
+    IntLitULongLong(u64),
+    KwAlignas,
+    KwAlignof,
     KwAuto,
+    KwBool,
     KwBreak,
     KwCase,
     KwChar,
     KwConst,
+    KwConstexpr,
     KwContinue,
     KwDefault,
     KwDo,
@@ -52,6 +67,7 @@ pub enum Token {
     KwElse,
     KwEnum,
     KwExtern,
+    KwFalse,
     KwFloat,
     KwFor,
     KwGoto,
@@ -59,6 +75,7 @@ pub enum Token {
     KwInline,
     KwInt,
     KwLong,
+    KwNullptr,
     KwRegister,
     KwRestrict,
     KwReturn,
@@ -66,12 +83,21 @@ pub enum Token {
     KwSigned,
     KwSizeof,
     KwStatic,
+    #[allow(non_camel_case_types)]
+    KwStatic_assert,
     KwStruct,
     KwSwitch,
+    #[allow(non_camel_case_types)]
+    KwThread_local,
+    KwTrue,
     KwTypedef,
+    KwTypeof,
+    #[allow(non_camel_case_types)]
+    KwTypeof_unqual,
     KwUnion,
     KwUnsigned,
     KwVoid,
+    KwVolatile,
     KwWhile,
     #[allow(non_camel_case_types)]
     Kw_Alignas,
@@ -80,6 +106,8 @@ pub enum Token {
     #[allow(non_camel_case_types)]
     Kw_Atomic,
     #[allow(non_camel_case_types)]
+    Kw_BitInt,
+    #[allow(non_camel_case_types)]
     Kw_Bool,
     #[allow(non_camel_case_types)]
     Kw_Complex,
@@ -93,6 +121,24 @@ pub enum Token {
     Kw_Static_assert,
     #[allow(non_camel_case_types)]
     Kw_Thread_local,
+    #[allow(non_camel_case_types)]
+    Kw__asm__,
+    #[allow(non_camel_case_types)]
+    Kw__attribute__,
+    #[allow(non_camel_case_types)]
+    Kw__builtin_va_list,
+    #[allow(non_camel_case_types)]
+    Kw__extension__,
+    #[allow(non_camel_case_types)]
+    Kw__inline,
+    #[allow(non_camel_case_types)]
+    Kw__int128,
+    #[allow(non_camel_case_types)]
+    Kw__label__,
+    #[allow(non_camel_case_types)]
+    Kw__restrict,
+    #[allow(non_camel_case_types)]
+    Kw__typeof__,
     LBrace,
     LSquare,
     LParen,
@@ -134,6 +180,9 @@ pub struct F80 {
 }
 
 impl F80 {
+    /// x87 infinities keep the integer bit set
+    pub const INFINITY: F80 = F80{bits: 0x7fff_8000_0000_0000_0000};
+
     pub fn new(bits: u128) -> Self {
         Self{bits}
     }
@@ -143,6 +192,12 @@ impl F80 {
     }
 }
 
+impl Debug for F80 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        NumericLiteralDebug{i: *self}.fmt(f)
+    }
+}
+
 struct NumericLiteralDebug<I> {
     i: I,
 }
@@ -183,6 +238,12 @@ impl Debug for NumericLiteralDebug<u64> {
     }
 }
 
+impl Debug for NumericLiteralDebug<u128> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#034x}", self.i)
+    }
+}
+
 impl Debug for NumericLiteralDebug<f32> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let as_bits = self.i.to_bits();
@@ -208,170 +269,489 @@ impl Debug for NumericLiteralDebug<F80> {
         let as_bits = self.i.bits();
         let mantissa = as_bits & 0x7fff_ffff_ffff_ffff;
         let integer = (as_bits & 0x8000_0000_0000_0000) >> 63;
-        let exponent = (as_bits & 0x7ff_0000_0000_0000_0000) >> 64;
+        let exponent = (as_bits & 0x7fff_0000_0000_0000_0000) >> 64;
         let sign = (as_bits & 0x8000_0000_0000_0000_0000) >> 79;
         write!(f, "{:01b} {:015b} {:0b} {:063b}", sign, exponent, integer, mantissa)
     }
 }
 
+impl Token {
+    /// the name of the kind of token this is, the same for every value of it
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Amp => "Amp",
+            Self::AmpAmp => "AmpAmp",
+            Self::AmpEql => "AmpEql",
+            Self::Bang => "Bang",
+            Self::BangEql => "BangEql",
+            Self::Caret => "Caret",
+            Self::CaretEql => "CaretEql",
+            Self::CharLit(..) => "CharLit",
+            Self::CharLit_L(..) => "CharLit_L",
+            Self::CharLit_u(..) => "CharLit_u",
+            Self::CharLit_U(..) => "CharLit_U",
+            Self::CharLit_u8(..) => "CharLit_u8",
+            Self::Colon => "Colon",
+            Self::Comma => "Comma",
+            Self::Dash => "Dash",
+            Self::DashDash => "DashDash",
+            Self::DashEql => "DashEql",
+            Self::DashGTh => "DashGTh",
+            Self::Dot => "Dot",
+            Self::Ellipsis => "Ellipsis",
+            Self::Eql => "Eql",
+            Self::EqlEql => "EqlEql",
+            Self::FloatLit32(..) => "FloatLit32",
+            Self::FloatLit64(..) => "FloatLit64",
+            Self::FloatLit80(..) => "FloatLit80",
+            Self::FSl => "FSl",
+            Self::FSlEql => "FSlEql",
+            Self::GTh => "GTh",
+            Self::GThEql => "GThEql",
+            Self::GThGTh => "GThGTh",
+            Self::GThGThEql => "GThGThEql",
+            Self::Identifier(..) => "Identifier",
+            Self::IntLitBitInt(..) => "IntLitBitInt",
+            Self::IntLitInt(..) => "IntLitInt",
+            Self::IntLitLong(..) => "IntLitLong",
+            Self::IntLitLongLong(..) => "IntLitLongLong",
+            Self::IntLitUBitInt(..) => "IntLitUBitInt",
+            Self::IntLitUInt(..) => "IntLitUInt",
+            Self::IntLitULong(..) => "IntLitULong",
+            Self::IntLitULongLong(..) => "IntLitULongLong",
+            Self::KwAlignas => "KwAlignas",
+            Self::KwAlignof => "KwAlignof",
+            Self::KwAuto => "KwAuto",
+            Self::KwBool => "KwBool",
+            Self::KwBreak => "KwBreak",
+            Self::KwCase => "KwCase",
+            Self::KwChar => "KwChar",
+            Self::KwConst => "KwConst",
+            Self::KwConstexpr => "KwConstexpr",
+            Self::KwContinue => "KwContinue",
+            Self::KwDefault => "KwDefault",
+            Self::KwDo => "KwDo",
+            Self::KwDouble => "KwDouble",
+            Self::KwElse => "KwElse",
+            Self::KwEnum => "KwEnum",
+            Self::KwExtern => "KwExtern",
+            Self::KwFalse => "KwFalse",
+            Self::KwFloat => "KwFloat",
+            Self::KwFor => "KwFor",
+            Self::KwGoto => "KwGoto",
+            Self::KwIf => "KwIf",
+            Self::KwInline => "KwInline",
+            Self::KwInt => "KwInt",
+            Self::KwLong => "KwLong",
+            Self::KwNullptr => "KwNullptr",
+            Self::KwRegister => "KwRegister",
+            Self::KwRestrict => "KwRestrict",
+            Self::KwReturn => "KwReturn",
+            Self::KwShort => "KwShort",
+            Self::KwSigned => "KwSigned",
+            Self::KwSizeof => "KwSizeof",
+            Self::KwStatic => "KwStatic",
+            Self::KwStatic_assert => "KwStatic_assert",
+            Self::KwStruct => "KwStruct",
+            Self::KwSwitch => "KwSwitch",
+            Self::KwThread_local => "KwThread_local",
+            Self::KwTrue => "KwTrue",
+            Self::KwTypedef => "KwTypedef",
+            Self::KwTypeof => "KwTypeof",
+            Self::KwTypeof_unqual => "KwTypeof_unqual",
+            Self::KwUnion => "KwUnion",
+            Self::KwUnsigned => "KwUnsigned",
+            Self::KwVoid => "KwVoid",
+            Self::KwVolatile => "KwVolatile",
+            Self::KwWhile => "KwWhile",
+            Self::Kw_Alignas => "Kw_Alignas",
+            Self::Kw_Alignof => "Kw_Alignof",
+            Self::Kw_Atomic => "Kw_Atomic",
+            Self::Kw_BitInt => "Kw_BitInt",
+            Self::Kw_Bool => "Kw_Bool",
+            Self::Kw_Complex => "Kw_Complex",
+            Self::Kw_Generic => "Kw_Generic",
+            Self::Kw_Imaginary => "Kw_Imaginary",
+            Self::Kw_Noreturn => "Kw_Noreturn",
+            Self::Kw_Static_assert => "Kw_Static_assert",
+            Self::Kw_Thread_local => "Kw_Thread_local",
+            Self::Kw__asm__ => "Kw__asm__",
+            Self::Kw__attribute__ => "Kw__attribute__",
+            Self::Kw__builtin_va_list => "Kw__builtin_va_list",
+            Self::Kw__extension__ => "Kw__extension__",
+            Self::Kw__inline => "Kw__inline",
+            Self::Kw__int128 => "Kw__int128",
+            Self::Kw__label__ => "Kw__label__",
+            Self::Kw__restrict => "Kw__restrict",
+            Self::Kw__typeof__ => "Kw__typeof__",
+            Self::LBrace => "LBrace",
+            Self::LSquare => "LSquare",
+            Self::LParen => "LParen",
+            Self::LTh => "LTh",
+            Self::LThEql => "LThEql",
+            Self::LThLTh => "LThLTh",
+            Self::LThLThEql => "LThLThEql",
+            Self::Pct => "Pct",
+            Self::PctEql => "PctEql",
+            Self::Pipe => "Pipe",
+            Self::PipeEql => "PipeEql",
+            Self::PipePipe => "PipePipe",
+            Self::Plus => "Plus",
+            Self::PlusEql => "PlusEql",
+            Self::PlusPlus => "PlusPlus",
+            Self::Question => "Question",
+            Self::RBrace => "RBrace",
+            Self::RParen => "RParen",
+            Self::RSquare => "RSquare",
+            Self::Semi => "Semi",
+            Self::Star => "Star",
+            Self::StarEql => "StarEql",
+            Self::StringLit(..) => "StringLit",
+            Self::StringLit_L(..) => "StringLit_L",
+            Self::StringLit_u(..) => "StringLit_u",
+            Self::StringLit_u8(..) => "StringLit_u8",
+            Self::StringLit_U(..) => "StringLit_U",
+            Self::Tilde => "Tilde",
+            Self::Unknown(..) => "Unknown",
+        }
+    }
+}
+
 impl Debug for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Amp => write!(f, "Amp"),
-            Self::AmpAmp => write!(f, "AmpAmp"),
-            Self::AmpEql => write!(f, "AmpEql"),
-            Self::Bang => write!(f, "Bang"),
-            Self::BangEql => write!(f, "BangEql"),
-            Self::Caret => write!(f, "Caret"),
-            Self::CaretEql => write!(f, "CaretEql"),
             Self::CharLit(i) => {
                 let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("CharLit").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::CharLit_L(i) => {
                 let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("CharLit_L").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::CharLit_u(i) => {
                 let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("CharLit_u").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::CharLit_U(i) => {
                 let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("CharLit_U").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
+            }
+            Self::CharLit_u8(i) => {
+                let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
+                f.debug_tuple(self.name()).field(&o).finish()
             }
-            Self::Colon => write!(f, "Colon"),
-            Self::Comma => write!(f, "Comma"),
-            Self::Dash => write!(f, "Dash"),
-            Self::DashDash => write!(f, "DashDash"),
-            Self::DashEql => write!(f, "DashEql"),
-            Self::DashGTh => write!(f, "DashGTh"),
-            Self::Dot => write!(f, "Dot"),
-            Self::Ellipsis => write!(f, "Ellipsis"),
-            Self::Eql => write!(f, "Eql"),
-            Self::EqlEql => write!(f, "EqlEql"),
             Self::FloatLit32(i) => {
                 let o: NumericLiteralDebug<f32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("FloatLit32").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::FloatLit64(i) => {
                 let o: NumericLiteralDebug<f64> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("FloatLit64").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::FloatLit80(i) => {
                 let o = NumericLiteralDebug{i: *i};
-                f.debug_tuple("FloatLit80").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
-            Self::FSl => write!(f, "FSl"),
-            Self::FSlEql => write!(f, "FSlEql"),
-            Self::GTh => write!(f, "GTh"),
-            Self::GThEql => write!(f, "GThEql"),
-            Self::GThGTh => write!(f, "GThGTh"),
-            Self::GThGThEql => write!(f, "GThGThEql"),
-            Self::Identifier(arg0) => f.debug_tuple("Identifier").field(arg0).finish(),
-            Self::IntLitI32(i) => {
+            Self::Identifier(arg0) => f.debug_tuple(self.name()).field(arg0).finish(),
+            Self::IntLitBitInt(i, width) => {
+                let o: NumericLiteralDebug<u128> = NumericLiteralDebug{i: *i};
+                f.debug_tuple(self.name()).field(&o).field(width).finish()
+            }
+            Self::IntLitInt(i) => {
                 let o: NumericLiteralDebug<i32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("IntLitI32").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
+            }
+            Self::IntLitLong(i) => {
+                let o: NumericLiteralDebug<i64> = NumericLiteralDebug{i: *i};
+                f.debug_tuple(self.name()).field(&o).finish()
             }
-            Self::IntLitI64(i) => {
+            Self::IntLitLongLong(i) => {
                 let o: NumericLiteralDebug<i64> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("IntLitI64").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
+            }
+            Self::IntLitUBitInt(i, width) => {
+                let o: NumericLiteralDebug<u128> = NumericLiteralDebug{i: *i};
+                f.debug_tuple(self.name()).field(&o).field(width).finish()
             }
-            Self::IntLitU32(i) => {
+            Self::IntLitUInt(i) => {
                 let o: NumericLiteralDebug<u32> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("IntLitU32").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
-            Self::IntLitU64(i) => {
+            Self::IntLitULong(i) => {
                 let o: NumericLiteralDebug<u64> = NumericLiteralDebug{i: *i};
-                f.debug_tuple("IntLitU64").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
+            }
+            Self::IntLitULongLong(i) => {
+                let o: NumericLiteralDebug<u64> = NumericLiteralDebug{i: *i};
+                f.debug_tuple(self.name()).field(&o).finish()
             }
-            Self::KwAuto => write!(f, "KwAuto"),
-            Self::KwBreak => write!(f, "KwBreak"),
-            Self::KwCase => write!(f, "KwCase"),
-            Self::KwChar => write!(f, "KwChar"),
-            Self::KwConst => write!(f, "KwConst"),
-            Self::KwContinue => write!(f, "KwContinue"),
-            Self::KwDefault => write!(f, "KwDefault"),
-            Self::KwDo => write!(f, "KwDo"),
-            Self::KwDouble => write!(f, "KwDouble"),
-            Self::KwElse => write!(f, "KwElse"),
-            Self::KwEnum => write!(f, "KwEnum"),
-            Self::KwExtern => write!(f, "KwExtern"),
-            Self::KwFloat => write!(f, "KwFloat"),
-            Self::KwFor => write!(f, "KwFor"),
-            Self::KwGoto => write!(f, "KwGoto"),
-            Self::KwIf => write!(f, "KwIf"),
-            Self::KwInline => write!(f, "KwInline"),
-            Self::KwInt => write!(f, "KwInt"),
-            Self::KwLong => write!(f, "KwLong"),
-            Self::KwRegister => write!(f, "KwRegister"),
-            Self::KwRestrict => write!(f, "KwRestrict"),
-            Self::KwReturn => write!(f, "KwReturn"),
-            Self::KwShort => write!(f, "KwShort"),
-            Self::KwSigned => write!(f, "KwSigned"),
-            Self::KwSizeof => write!(f, "KwSizeof"),
-            Self::KwStatic => write!(f, "KwStatic"),
-            Self::KwStruct => write!(f, "KwStruct"),
-            Self::KwSwitch => write!(f, "KwSwitch"),
-            Self::KwTypedef => write!(f, "KwTypedef"),
-            Self::KwUnion => write!(f, "KwUnion"),
-            Self::KwUnsigned => write!(f, "KwUnsigned"),
-            Self::KwVoid => write!(f, "KwVoid"),
-            Self::KwWhile => write!(f, "KwWhile"),
-            Self::Kw_Alignas => write!(f, "Kw_Alignas"),
-            Self::Kw_Alignof => write!(f, "Kw_Alignof"),
-            Self::Kw_Atomic => write!(f, "Kw_Atomic"),
-            Self::Kw_Bool => write!(f, "Kw_Bool"),
-            Self::Kw_Complex => write!(f, "Kw_Complex"),
-            Self::Kw_Generic => write!(f, "Kw_Generic"),
-            Self::Kw_Imaginary => write!(f, "Kw_Imaginary"),
-            Self::Kw_Noreturn => write!(f, "Kw_Noreturn"),
-            Self::Kw_Static_assert => write!(f, "Kw_Static_assert"),
-            Self::Kw_Thread_local => write!(f, "Kw_Thread_local"),
-            Self::LBrace => write!(f, "LBrace"),
-            Self::LSquare => write!(f, "LSquare"),
-            Self::LParen => write!(f, "LParen"),
-            Self::LTh => write!(f, "LTh"),
-            Self::LThEql => write!(f, "LThEql"),
-            Self::LThLTh => write!(f, "LThLTh"),
-            Self::LThLThEql => write!(f, "LThLThEql"),
-            Self::Pct => write!(f, "Pct"),
-            Self::PctEql => write!(f, "PctEql"),
-            Self::Pipe => write!(f, "Pipe"),
-            Self::PipeEql => write!(f, "PipeEql"),
-            Self::PipePipe => write!(f, "PipePipe"),
-            Self::Plus => write!(f, "Plus"),
-            Self::PlusEql => write!(f, "PlusEql"),
-            Self::PlusPlus => write!(f, "PlusPlus"),
-            Self::Question => write!(f, "Question"),
-            Self::RBrace => write!(f, "RBrace"),
-            Self::RParen => write!(f, "RParen"),
-            Self::RSquare => write!(f, "RSquare"),
-            Self::Semi => write!(f, "Semi"),
-            Self::Star => write!(f, "Star"),
-            Self::StarEql => write!(f, "StarEql"),
             Self::StringLit(v) => {
                 let o: Vec<NumericLiteralDebug<i8>> = v.iter().map(|i| NumericLiteralDebug{i: *i}).collect();
-                f.debug_tuple("StringLit").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::StringLit_L(v) => {
                 let o: Vec<NumericLiteralDebug<i32>> = v.iter().map(|i| NumericLiteralDebug{i: *i}).collect();
-                f.debug_tuple("StringLit_L").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::StringLit_u(v) => {
                 let o: Vec<NumericLiteralDebug<i16>> = v.iter().map(|i| NumericLiteralDebug{i: *i}).collect();
-                f.debug_tuple("StringLit_u").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::StringLit_u8(v) => {
                 let o: Vec<NumericLiteralDebug<i8>> = v.iter().map(|i| NumericLiteralDebug{i: *i}).collect();
-                f.debug_tuple("StringLit_u8").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
             }
             Self::StringLit_U(v) => {
                 let o: Vec<NumericLiteralDebug<i32>> = v.iter().map(|i| NumericLiteralDebug{i: *i}).collect();
-                f.debug_tuple("StringLit_U").field(&o).finish()
+                f.debug_tuple(self.name()).field(&o).finish()
+            }
+            Self::Unknown(arg0) => f.debug_tuple(self.name()).field(arg0).finish(),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// writes `units`, the chars of a literal in its encoding, as they would be spelt between
+/// `quote`s. Anything which isn't printable ASCII becomes a hex escape, and since those
+/// don't end until a char which isn't a hex digit, so does any hex digit following one
+fn write_escaped(f: &mut std::fmt::Formatter<'_>, units: impl Iterator<Item = u32>, quote: char) -> std::fmt::Result {
+    let mut after_hex = false;
+    for unit in units {
+        let named = match unit {
+            0x07 => Some("\\a"),
+            0x08 => Some("\\b"),
+            0x09 => Some("\\t"),
+            0x0a => Some("\\n"),
+            0x0b => Some("\\v"),
+            0x0c => Some("\\f"),
+            0x0d => Some("\\r"),
+            0x5c => Some("\\\\"),
+            _ => None,
+        };
+        let printable = char::from_u32(unit).filter(|c| *c == ' ' || c.is_ascii_graphic());
+        match (named, printable) {
+            (Some(escape), _) => f.write_str(escape)?,
+            (None, Some(c)) if c == quote => write!(f, "\\{c}")?,
+            (None, Some(c)) if !(after_hex && c.is_ascii_hexdigit()) => write!(f, "{c}")?,
+            _ => {
+                write!(f, "\\x{unit:x}")?;
+                after_hex = true;
+                continue;
             }
-            Self::Tilde => write!(f, "Tilde"),
-            Self::Unknown(arg0) => f.debug_tuple("Unknown").field(arg0).finish(),
+        }
+        after_hex = false;
+    }
+    Ok(())
+}
+
+fn write_char(f: &mut std::fmt::Formatter<'_>, prefix: &str, units: impl Iterator<Item = u32>) -> std::fmt::Result {
+    write!(f, "{prefix}'")?;
+    write_escaped(f, units, '\'')?;
+    write!(f, "'")
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, prefix: &str, units: impl Iterator<Item = u32>) -> std::fmt::Result {
+    write!(f, "{prefix}\"")?;
+    write_escaped(f, units, '"')?;
+    write!(f, "\"")
+}
+
+/// the bytes of a plain char constant, more than one for a multi-char constant (which
+/// gcc packs most significant first)
+fn char_units(i: i32) -> Vec<u32> {
+    if (-128..=127).contains(&i) {
+        return vec![i as u8 as u32];
+    }
+    let bytes = (i as u32).to_be_bytes();
+    let first = bytes.iter().position(|b| *b != 0).unwrap_or(3);
+    bytes[first..].iter().map(|b| *b as u32).collect()
+}
+
+/// a float in the shortest decimal spelling which reads back as the same value, those
+/// too big for the type having been infinite
+fn write_float(f: &mut std::fmt::Formatter<'_>, spelling: String, suffix: &str) -> std::fmt::Result {
+    match spelling.as_str() {
+        "inf" => write!(f, "1e99999{suffix}"),
+        _ => write!(f, "{spelling}{suffix}"),
+    }
+}
+
+/// a long double in hex, which is exact, as 0x1.fp+3L
+fn write_f80(f: &mut std::fmt::Formatter<'_>, value: F80) -> std::fmt::Result {
+    let bits = value.bits();
+    let mantissa = (bits as u64) & 0x7fff_ffff_ffff_ffff;
+    let integer = (bits >> 63) & 1;
+    let exponent = ((bits >> 64) & 0x7fff) as i32;
+    if value == F80::INFINITY {
+        return write!(f, "0x1p+16384L");
+    }
+    // denormals have an exponent of zero but are scaled as if it were one
+    let exponent = match (integer, mantissa, exponent) {
+        (0, 0, _) => 0,
+        (_, _, 0) => 1 - 16383,
+        _ => exponent - 16383,
+    };
+    let fraction = format!("{:016x}", mantissa << 1);
+    let fraction = fraction.trim_end_matches('0');
+    match fraction {
+        "" => write!(f, "0x{integer}p{exponent:+}L"),
+        _ => write!(f, "0x{integer}.{fraction}p{exponent:+}L"),
+    }
+}
+
+impl Token {
+    /// how the punctuators and keywords are spelt, those with alternative spellings
+    /// (digraphs, the GNU keywords) having their usual one
+    fn spelling(&self) -> Option<&'static str> {
+        let spelling = match self {
+            Self::Amp => "&",
+            Self::AmpAmp => "&&",
+            Self::AmpEql => "&=",
+            Self::Bang => "!",
+            Self::BangEql => "!=",
+            Self::Caret => "^",
+            Self::CaretEql => "^=",
+            Self::Colon => ":",
+            Self::Comma => ",",
+            Self::Dash => "-",
+            Self::DashDash => "--",
+            Self::DashEql => "-=",
+            Self::DashGTh => "->",
+            Self::Dot => ".",
+            Self::Ellipsis => "...",
+            Self::Eql => "=",
+            Self::EqlEql => "==",
+            Self::FSl => "/",
+            Self::FSlEql => "/=",
+            Self::GTh => ">",
+            Self::GThEql => ">=",
+            Self::GThGTh => ">>",
+            Self::GThGThEql => ">>=",
+            Self::KwAlignas => "alignas",
+            Self::KwAlignof => "alignof",
+            Self::KwAuto => "auto",
+            Self::KwBool => "bool",
+            Self::KwBreak => "break",
+            Self::KwCase => "case",
+            Self::KwChar => "char",
+            Self::KwConst => "const",
+            Self::KwConstexpr => "constexpr",
+            Self::KwContinue => "continue",
+            Self::KwDefault => "default",
+            Self::KwDo => "do",
+            Self::KwDouble => "double",
+            Self::KwElse => "else",
+            Self::KwEnum => "enum",
+            Self::KwExtern => "extern",
+            Self::KwFalse => "false",
+            Self::KwFloat => "float",
+            Self::KwFor => "for",
+            Self::KwGoto => "goto",
+            Self::KwIf => "if",
+            Self::KwInline => "inline",
+            Self::KwInt => "int",
+            Self::KwLong => "long",
+            Self::KwNullptr => "nullptr",
+            Self::KwRegister => "register",
+            Self::KwRestrict => "restrict",
+            Self::KwReturn => "return",
+            Self::KwShort => "short",
+            Self::KwSigned => "signed",
+            Self::KwSizeof => "sizeof",
+            Self::KwStatic => "static",
+            Self::KwStatic_assert => "static_assert",
+            Self::KwStruct => "struct",
+            Self::KwSwitch => "switch",
+            Self::KwThread_local => "thread_local",
+            Self::KwTrue => "true",
+            Self::KwTypedef => "typedef",
+            Self::KwTypeof => "typeof",
+            Self::KwTypeof_unqual => "typeof_unqual",
+            Self::KwUnion => "union",
+            Self::KwUnsigned => "unsigned",
+            Self::KwVoid => "void",
+            Self::KwVolatile => "volatile",
+            Self::KwWhile => "while",
+            Self::Kw_Alignas => "_Alignas",
+            Self::Kw_Alignof => "_Alignof",
+            Self::Kw_Atomic => "_Atomic",
+            Self::Kw_BitInt => "_BitInt",
+            Self::Kw_Bool => "_Bool",
+            Self::Kw_Complex => "_Complex",
+            Self::Kw_Generic => "_Generic",
+            Self::Kw_Imaginary => "_Imaginary",
+            Self::Kw_Noreturn => "_Noreturn",
+            Self::Kw_Static_assert => "_Static_assert",
+            Self::Kw_Thread_local => "_Thread_local",
+            Self::Kw__asm__ => "__asm__",
+            Self::Kw__attribute__ => "__attribute__",
+            Self::Kw__builtin_va_list => "__builtin_va_list",
+            Self::Kw__extension__ => "__extension__",
+            Self::Kw__inline => "__inline",
+            Self::Kw__int128 => "__int128",
+            Self::Kw__label__ => "__label__",
+            Self::Kw__restrict => "__restrict",
+            Self::Kw__typeof__ => "__typeof__",
+            Self::LBrace => "{",
+            Self::LSquare => "[",
+            Self::LParen => "(",
+            Self::LTh => "<",
+            Self::LThEql => "<=",
+            Self::LThLTh => "<<",
+            Self::LThLThEql => "<<=",
+            Self::Pct => "%",
+            Self::PctEql => "%=",
+            Self::Pipe => "|",
+            Self::PipeEql => "|=",
+            Self::PipePipe => "||",
+            Self::Plus => "+",
+            Self::PlusEql => "+=",
+            Self::PlusPlus => "++",
+            Self::Question => "?",
+            Self::RBrace => "}",
+            Self::RParen => ")",
+            Self::RSquare => "]",
+            Self::Semi => ";",
+            Self::Star => "*",
+            Self::StarEql => "*=",
+            Self::Tilde => "~",
+            _ => return None,
+        };
+        Some(spelling)
+    }
+}
+
+/// the token as it would be written in the source, lexing that gives back the same token
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(spelling) = self.spelling() {
+            return f.write_str(spelling);
+        }
+        match self {
+            Self::CharLit(i) => write_char(f, "", char_units(*i).into_iter()),
+            Self::CharLit_L(i) => write_char(f, "L", std::iter::once(*i as u32)),
+            Self::CharLit_u(i) => write_char(f, "u", std::iter::once(*i as u32)),
+            Self::CharLit_U(i) => write_char(f, "U", std::iter::once(*i as u32)),
+            Self::CharLit_u8(i) => write_char(f, "u8", std::iter::once(*i as u32)),
+            Self::FloatLit32(i) => write_float(f, format!("{i:?}"), "f"),
+            Self::FloatLit64(i) => write_float(f, format!("{i:?}"), ""),
+            Self::FloatLit80(i) => write_f80(f, *i),
+            Self::Identifier(symbol) => f.write_str(symbol.as_str()),
+            Self::IntLitBitInt(i, _) => write!(f, "{i}wb"),
+            Self::IntLitInt(i) => write!(f, "{i}"),
+            Self::IntLitLong(i) => write!(f, "{i}l"),
+            Self::IntLitLongLong(i) => write!(f, "{i}ll"),
+            Self::IntLitUBitInt(i, _) => write!(f, "{i}uwb"),
+            Self::IntLitUInt(i) => write!(f, "{i}u"),
+            Self::IntLitULong(i) => write!(f, "{i}ul"),
+            Self::IntLitULongLong(i) => write!(f, "{i}ull"),
+            Self::StringLit(v) => write_string(f, "", v.iter().map(|i| *i as u8 as u32)),
+            Self::StringLit_L(v) => write_string(f, "L", v.iter().map(|i| *i as u32)),
+            Self::StringLit_u(v) => write_string(f, "u", v.iter().map(|i| *i as u16 as u32)),
+            Self::StringLit_u8(v) => write_string(f, "u8", v.iter().map(|i| *i as u8 as u32)),
+            Self::StringLit_U(v) => write_string(f, "U", v.iter().map(|i| *i as u32)),
+            Self::Unknown(spelling) => f.write_str(spelling),
+            _ => unreachable!("{} has a spelling", self.name()),
         }
     }
 }
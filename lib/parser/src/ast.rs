@@ -0,0 +1,74 @@
+//! the abstract syntax tree of a C11 translation unit, following the grammar of C11 Annex A.2.
+//! Every node has the span of the tokens it was parsed from, so anything reported about it
+//! can point back at the source
+
+#[cfg(test)]
+mod tests;
+
+mod declaration;
+mod expression;
+mod statement;
+
+pub use declaration::{
+    AlignmentSpecifier, ArraySize, Declaration, DeclarationKind, DeclarationSpecifier,
+    DeclarationSpecifierKind, DeclarationSpecifiers, Declarator, DeclaratorKind, Designator,
+    DesignatorKind, EnumSpecifier, Enumerator, FunctionParameters, FunctionSpecifier,
+    InitDeclarator, Initializer, InitializerKind, InitializerListItem, ParameterDeclaration,
+    Qualifier, StaticAssert, StorageClass, StructDeclaration, StructDeclarationKind,
+    StructDeclarator, StructOrUnion, StructOrUnionSpecifier, TypeName, TypeQualifier,
+    TypeSpecifier, TypeofOperand,
+};
+pub use expression::{
+    AssignmentOperator, BinaryOperator, Constant, Expression, ExpressionKind, GenericAssociation,
+    StringLiteral, UnaryOperator,
+};
+pub use statement::{BlockItem, CompoundStatement, ForInit, Statement, StatementKind};
+
+use lexer::{Span, Symbol};
+
+/// C11 6.9, the external declarations of a preprocessed source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationUnit {
+    pub declarations: Vec<ExternalDeclaration>,
+    pub span: Span,
+}
+
+/// C11 6.9, at file scope there are only function definitions and declarations
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalDeclaration {
+    FunctionDefinition(FunctionDefinition),
+    Declaration(Declaration),
+}
+
+impl ExternalDeclaration {
+    pub fn span(&self) -> Span {
+        match self {
+            ExternalDeclaration::FunctionDefinition(definition) => definition.span,
+            ExternalDeclaration::Declaration(declaration) => declaration.span,
+        }
+    }
+}
+
+/// C11 6.9.1, `declarations` are those of a K&R style definition's parameters, between the
+/// declarator and the body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionDefinition {
+    pub specifiers: DeclarationSpecifiers,
+    pub declarator: Declarator,
+    pub declarations: Vec<Declaration>,
+    pub body: CompoundStatement,
+    pub span: Span,
+}
+
+/// a name along with where it was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Identifier {
+    pub symbol: Symbol,
+    pub span: Span,
+}
+
+impl Identifier {
+    pub fn as_str(&self) -> &'static str {
+        self.symbol.as_str()
+    }
+}
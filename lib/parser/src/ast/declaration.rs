@@ -0,0 +1,432 @@
+use lexer::{Span, Token};
+
+use super::{Expression, Identifier};
+
+/// C11 6.7, either `specifiers` followed by the things being declared or a
+/// `_Static_assert`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub kind: DeclarationKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclarationKind {
+    /// `static int x = 1, *p;`, there may be no declarators when a tag is declared (`struct
+    /// s;`)
+    Declaration {
+        specifiers: DeclarationSpecifiers,
+        declarators: Vec<InitDeclarator>,
+    },
+    StaticAssert(StaticAssert),
+}
+
+/// C11 6.7.10, `_Static_assert(condition, "message");`. C23 lets the message be left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticAssert {
+    pub condition: Box<Expression>,
+    pub message: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+/// C11 6.7, the storage classes, type specifiers and qualifiers, function specifiers and
+/// alignment specifiers in the order they were written. A specifier-qualifier-list (C11
+/// 6.7.2.1) is the same with only type specifiers and qualifiers in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclarationSpecifiers {
+    pub specifiers: Vec<DeclarationSpecifier>,
+    pub span: Span,
+}
+
+impl DeclarationSpecifiers {
+    pub fn storage_classes(&self) -> impl Iterator<Item = StorageClass> + '_ {
+        self.specifiers
+            .iter()
+            .filter_map(|specifier| match specifier.kind {
+                DeclarationSpecifierKind::StorageClass(class) => Some(class),
+                _ => None,
+            })
+    }
+
+    pub fn type_specifiers(&self) -> impl Iterator<Item = &TypeSpecifier> + '_ {
+        self.specifiers
+            .iter()
+            .filter_map(|specifier| match &specifier.kind {
+                DeclarationSpecifierKind::Type(specifier) => Some(specifier),
+                _ => None,
+            })
+    }
+
+    pub fn qualifiers(&self) -> impl Iterator<Item = TypeQualifier> + '_ {
+        self.specifiers
+            .iter()
+            .filter_map(|specifier| match specifier.kind {
+                DeclarationSpecifierKind::Qualifier(qualifier) => Some(qualifier),
+                _ => None,
+            })
+    }
+
+    /// whether these declare typedef names rather than objects or functions
+    pub fn is_typedef(&self) -> bool {
+        self.storage_classes()
+            .any(|class| class == StorageClass::Typedef)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclarationSpecifier {
+    pub kind: DeclarationSpecifierKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclarationSpecifierKind {
+    StorageClass(StorageClass),
+    Type(TypeSpecifier),
+    Qualifier(TypeQualifier),
+    Function(FunctionSpecifier),
+    Alignment(AlignmentSpecifier),
+}
+
+/// C11 6.7.1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageClass {
+    Typedef,
+    Extern,
+    Static,
+    ThreadLocal,
+    Auto,
+    Register,
+}
+
+impl StorageClass {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::KwTypedef => Some(StorageClass::Typedef),
+            Token::KwExtern => Some(StorageClass::Extern),
+            Token::KwStatic => Some(StorageClass::Static),
+            Token::Kw_Thread_local | Token::KwThread_local => Some(StorageClass::ThreadLocal),
+            Token::KwAuto => Some(StorageClass::Auto),
+            Token::KwRegister => Some(StorageClass::Register),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.7.2, several of these may make up a type between them (`unsigned long int`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeSpecifier {
+    Void,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Signed,
+    Unsigned,
+    Bool,
+    Complex,
+    /// gcc's `__int128`, which `unsigned` and `signed` may go with as for `int`
+    Int128,
+    /// gcc's `__builtin_va_list`, which `<stdarg.h>` declares `va_list` as
+    BuiltinVaList,
+    /// C23 6.7.2, `_BitInt(width)`
+    BitInt(Box<Expression>),
+    /// `_Atomic(type-name)`, as opposed to the `_Atomic` qualifier
+    Atomic(Box<TypeName>),
+    /// C23 6.7.2.5, `typeof` (or gcc's `__typeof__`) and `typeof_unqual`
+    Typeof {
+        operand: TypeofOperand,
+        unqualified: bool,
+    },
+    StructOrUnion(StructOrUnionSpecifier),
+    Enum(EnumSpecifier),
+    TypedefName(Identifier),
+}
+
+impl TypeSpecifier {
+    /// the specifiers which are a single keyword
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::KwVoid => Some(TypeSpecifier::Void),
+            Token::KwChar => Some(TypeSpecifier::Char),
+            Token::KwShort => Some(TypeSpecifier::Short),
+            Token::KwInt => Some(TypeSpecifier::Int),
+            Token::KwLong => Some(TypeSpecifier::Long),
+            Token::KwFloat => Some(TypeSpecifier::Float),
+            Token::KwDouble => Some(TypeSpecifier::Double),
+            Token::KwSigned => Some(TypeSpecifier::Signed),
+            Token::KwUnsigned => Some(TypeSpecifier::Unsigned),
+            Token::Kw_Bool | Token::KwBool => Some(TypeSpecifier::Bool),
+            Token::Kw_Complex => Some(TypeSpecifier::Complex),
+            Token::Kw__int128 => Some(TypeSpecifier::Int128),
+            Token::Kw__builtin_va_list => Some(TypeSpecifier::BuiltinVaList),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.7.3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeQualifier {
+    Const,
+    Restrict,
+    Volatile,
+    Atomic,
+}
+
+impl TypeQualifier {
+    /// `_Atomic` is a qualifier unless it's followed by a `(`, which the caller has to check
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::KwConst => Some(TypeQualifier::Const),
+            Token::KwVolatile => Some(TypeQualifier::Volatile),
+            Token::KwRestrict | Token::Kw__restrict => Some(TypeQualifier::Restrict),
+            Token::Kw_Atomic => Some(TypeQualifier::Atomic),
+            _ => None,
+        }
+    }
+}
+
+/// a type qualifier on its own, as in a pointer declarator's `* const`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Qualifier {
+    pub kind: TypeQualifier,
+    pub span: Span,
+}
+
+/// C11 6.7.4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionSpecifier {
+    Inline,
+    Noreturn,
+}
+
+impl FunctionSpecifier {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::KwInline | Token::Kw__inline => Some(FunctionSpecifier::Inline),
+            Token::Kw_Noreturn => Some(FunctionSpecifier::Noreturn),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.7.5, `_Alignas(type-name)` or `_Alignas(constant-expression)`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlignmentSpecifier {
+    Type(Box<TypeName>),
+    Expression(Box<Expression>),
+}
+
+/// C23 6.7.2.5, what `typeof` is the type of
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeofOperand {
+    Type(Box<TypeName>),
+    Expression(Box<Expression>),
+}
+
+/// C11 6.7.2.1, `members` is `None` when the tag is only referred to (`struct s *p;`)
+/// rather than defined
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructOrUnionSpecifier {
+    pub kind: StructOrUnion,
+    pub tag: Option<Identifier>,
+    pub members: Option<Vec<StructDeclaration>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructOrUnion {
+    Struct,
+    Union,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructDeclaration {
+    pub kind: StructDeclarationKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructDeclarationKind {
+    /// there may be no declarators for an anonymous struct or union member (C11 6.7.2.1p13)
+    Member {
+        specifiers: DeclarationSpecifiers,
+        declarators: Vec<StructDeclarator>,
+    },
+    StaticAssert(StaticAssert),
+}
+
+/// C11 6.7.2.1, a member or a bit-field, which may be unnamed (`int : 3;`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructDeclarator {
+    pub declarator: Option<Declarator>,
+    pub width: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+/// C11 6.7.2.2, `enumerators` is `None` when the tag is only referred to
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumSpecifier {
+    pub tag: Option<Identifier>,
+    pub enumerators: Option<Vec<Enumerator>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enumerator {
+    pub name: Identifier,
+    pub value: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+/// C11 6.7, a declarator and the value it's initialised with, if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitDeclarator {
+    pub declarator: Declarator,
+    pub initializer: Option<Initializer>,
+    pub span: Span,
+}
+
+/// C11 6.7.6, nested as written: `int *f(void)` is a `Pointer` whose declarator is the
+/// `Function` declarator of `f`, while `int (*f)(void)` is a `Function` whose declarator is
+/// a `Pointer`. The parentheses used to group declarators don't need a node of their own
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declarator {
+    pub kind: DeclaratorKind,
+    pub span: Span,
+}
+
+impl Declarator {
+    /// the name being declared, which an abstract declarator doesn't have
+    pub fn identifier(&self) -> Option<Identifier> {
+        match &self.kind {
+            DeclaratorKind::Identifier(identifier) => Some(*identifier),
+            DeclaratorKind::Abstract => None,
+            DeclaratorKind::Pointer { declarator, .. }
+            | DeclaratorKind::Array { declarator, .. }
+            | DeclaratorKind::Function { declarator, .. } => declarator.identifier(),
+        }
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        self.identifier().is_none()
+    }
+
+    /// the parameters of the function being declared, if it is one: those of `f` in
+    /// `int (*f(int a))(char b)` are `(int a)`, while `int (*f)(int a)` declares a pointer
+    pub fn parameters(&self) -> Option<&FunctionParameters> {
+        match &self.kind {
+            DeclaratorKind::Function {
+                declarator,
+                parameters,
+            } if matches!(declarator.kind, DeclaratorKind::Identifier(_)) => Some(parameters),
+            DeclaratorKind::Identifier(_) | DeclaratorKind::Abstract => None,
+            DeclaratorKind::Pointer { declarator, .. }
+            | DeclaratorKind::Array { declarator, .. }
+            | DeclaratorKind::Function { declarator, .. } => declarator.parameters(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclaratorKind {
+    Identifier(Identifier),
+    /// where the name would be in an abstract declarator (C11 6.7.7), its span is empty
+    Abstract,
+    Pointer {
+        qualifiers: Vec<Qualifier>,
+        declarator: Box<Declarator>,
+    },
+    /// `qualifiers` and `is_static` are only allowed on a parameter (`int a[static const
+    /// 4]`)
+    Array {
+        declarator: Box<Declarator>,
+        qualifiers: Vec<Qualifier>,
+        is_static: bool,
+        size: ArraySize,
+    },
+    Function {
+        declarator: Box<Declarator>,
+        parameters: FunctionParameters,
+    },
+}
+
+/// C11 6.7.6.2
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArraySize {
+    /// `[]`
+    Unknown,
+    /// `[*]`, a variable length array of unspecified size in a prototype
+    Variable,
+    Expression(Box<Expression>),
+}
+
+/// C11 6.7.6.3
+#[derive(Debug, Clone, PartialEq)]
+pub enum FunctionParameters {
+    /// `(int a, char *)`, `(void)` is a prototype with a single unnamed `void` parameter
+    /// and the trailing `, ...` makes it variadic
+    Prototype {
+        parameters: Vec<ParameterDeclaration>,
+        variadic: bool,
+    },
+    /// `(a, b)` in a K&R style definition, `()` is an empty list of these
+    Identifiers(Vec<Identifier>),
+}
+
+/// C11 6.7.6.3, the declarator may be abstract
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDeclaration {
+    pub specifiers: DeclarationSpecifiers,
+    pub declarator: Declarator,
+    pub span: Span,
+}
+
+/// C11 6.7.7, a type written without a name being declared, as in a cast or `sizeof`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeName {
+    pub specifiers: DeclarationSpecifiers,
+    pub declarator: Declarator,
+    pub span: Span,
+}
+
+/// C11 6.7.9
+#[derive(Debug, Clone, PartialEq)]
+pub struct Initializer {
+    pub kind: InitializerKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitializerKind {
+    Expression(Box<Expression>),
+    /// `{ [0] = 1, .x = 2, 3 }`
+    List(Vec<InitializerListItem>),
+}
+
+/// one item of an initializer list, along with the designation saying which subobject it
+/// initialises
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitializerListItem {
+    pub designators: Vec<Designator>,
+    pub initializer: Initializer,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Designator {
+    pub kind: DesignatorKind,
+    pub span: Span,
+}
+
+/// C11 6.7.9p6
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignatorKind {
+    /// `[constant-expression]`
+    Index(Box<Expression>),
+    /// `.identifier`
+    Member(Identifier),
+}
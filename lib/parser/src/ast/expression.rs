@@ -0,0 +1,313 @@
+use lexer::{Span, Token, F80};
+
+use super::{Identifier, InitializerListItem, TypeName};
+
+/// C11 6.5
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    /// an object, a function or an enumeration constant
+    Identifier(Identifier),
+    Constant(Constant),
+    /// adjacent literals have already been concatenated by the lexer
+    StringLiteral(StringLiteral),
+    /// C11 6.5.1.1, `_Generic(controlling, type-name: expression, default: expression)`
+    Generic {
+        controlling: Box<Expression>,
+        associations: Vec<GenericAssociation>,
+    },
+    /// `array[index]`
+    Index {
+        array: Box<Expression>,
+        index: Box<Expression>,
+    },
+    Call {
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+    },
+    /// `object.member`
+    Member {
+        object: Box<Expression>,
+        member: Identifier,
+    },
+    /// `pointer->member`
+    PointerMember {
+        pointer: Box<Expression>,
+        member: Identifier,
+    },
+    /// C11 6.5.2.5, `(type-name){ initializer-list }`
+    CompoundLiteral {
+        type_name: Box<TypeName>,
+        initializers: Vec<InitializerListItem>,
+    },
+    /// the increments and decrements, prefix and postfix, as well as the unary operators
+    /// of C11 6.5.3.3
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    SizeofExpression(Box<Expression>),
+    SizeofType(Box<TypeName>),
+    /// `_Alignof` only takes a type name in C11, the expression form is a GNU extension
+    AlignofType(Box<TypeName>),
+    Cast {
+        type_name: Box<TypeName>,
+        operand: Box<Expression>,
+    },
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// `condition ? then : otherwise`
+    Conditional {
+        condition: Box<Expression>,
+        then: Box<Expression>,
+        otherwise: Box<Expression>,
+    },
+    Assignment {
+        operator: AssignmentOperator,
+        target: Box<Expression>,
+        value: Box<Expression>,
+    },
+    /// `left, right`
+    Comma {
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+/// C11 6.4.4, the value of a constant as the lexer worked it out, with the type it gave it
+/// by its suffix and magnitude
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    LongLong(i64),
+    ULongLong(u64),
+    /// the value and the width of the `_BitInt`
+    BitInt(u128, u32),
+    UBitInt(u128, u32),
+    Float(f32),
+    Double(f64),
+    LongDouble(F80),
+    Char(i32),
+    WChar(i32),
+    Char16(i32),
+    Char32(i32),
+    Utf8Char(i32),
+    /// C23's `true`, `false` and `nullptr`
+    True,
+    False,
+    Nullptr,
+}
+
+impl Constant {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        let constant = match *token {
+            Token::IntLitInt(v) => Constant::Int(v),
+            Token::IntLitUInt(v) => Constant::UInt(v),
+            Token::IntLitLong(v) => Constant::Long(v),
+            Token::IntLitULong(v) => Constant::ULong(v),
+            Token::IntLitLongLong(v) => Constant::LongLong(v),
+            Token::IntLitULongLong(v) => Constant::ULongLong(v),
+            Token::IntLitBitInt(v, width) => Constant::BitInt(v, width),
+            Token::IntLitUBitInt(v, width) => Constant::UBitInt(v, width),
+            Token::FloatLit32(v) => Constant::Float(v),
+            Token::FloatLit64(v) => Constant::Double(v),
+            Token::FloatLit80(v) => Constant::LongDouble(v),
+            Token::CharLit(v) => Constant::Char(v),
+            Token::CharLit_L(v) => Constant::WChar(v),
+            Token::CharLit_u(v) => Constant::Char16(v),
+            Token::CharLit_U(v) => Constant::Char32(v),
+            Token::CharLit_u8(v) => Constant::Utf8Char(v),
+            Token::KwTrue => Constant::True,
+            Token::KwFalse => Constant::False,
+            Token::KwNullptr => Constant::Nullptr,
+            _ => return None,
+        };
+        Some(constant)
+    }
+}
+
+/// C11 6.4.5, the code units of a string literal including its terminating null
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringLiteral {
+    Char(Vec<i8>),
+    Utf8(Vec<i8>),
+    Char16(Vec<i16>),
+    Char32(Vec<i32>),
+    WChar(Vec<i32>),
+}
+
+impl StringLiteral {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::StringLit(v) => Some(StringLiteral::Char(v.clone())),
+            Token::StringLit_u8(v) => Some(StringLiteral::Utf8(v.clone())),
+            Token::StringLit_u(v) => Some(StringLiteral::Char16(v.clone())),
+            Token::StringLit_U(v) => Some(StringLiteral::Char32(v.clone())),
+            Token::StringLit_L(v) => Some(StringLiteral::WChar(v.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.5.1.1, `type_name` is `None` for the `default` association
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericAssociation {
+    pub type_name: Option<TypeName>,
+    pub expression: Expression,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    PostIncrement,
+    PostDecrement,
+    PreIncrement,
+    PreDecrement,
+    /// `&`
+    AddressOf,
+    /// `*`
+    Dereference,
+    Plus,
+    Minus,
+    /// `~`
+    Complement,
+    /// `!`
+    Not,
+}
+
+impl UnaryOperator {
+    /// the operators written before their operand, other than `sizeof` and `_Alignof`
+    pub fn prefix_from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::PlusPlus => Some(UnaryOperator::PreIncrement),
+            Token::DashDash => Some(UnaryOperator::PreDecrement),
+            Token::Amp => Some(UnaryOperator::AddressOf),
+            Token::Star => Some(UnaryOperator::Dereference),
+            Token::Plus => Some(UnaryOperator::Plus),
+            Token::Dash => Some(UnaryOperator::Minus),
+            Token::Tilde => Some(UnaryOperator::Complement),
+            Token::Bang => Some(UnaryOperator::Not),
+            _ => None,
+        }
+    }
+
+    pub fn postfix_from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::PlusPlus => Some(UnaryOperator::PostIncrement),
+            Token::DashDash => Some(UnaryOperator::PostDecrement),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.5.5 to 6.5.14, the comma operator and assignments have nodes of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Multiply,
+    Divide,
+    Remainder,
+    Add,
+    Subtract,
+    ShiftLeft,
+    ShiftRight,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    BitAnd,
+    BitXor,
+    BitOr,
+    LogicalAnd,
+    LogicalOr,
+}
+
+impl BinaryOperator {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::Star => Some(BinaryOperator::Multiply),
+            Token::FSl => Some(BinaryOperator::Divide),
+            Token::Pct => Some(BinaryOperator::Remainder),
+            Token::Plus => Some(BinaryOperator::Add),
+            Token::Dash => Some(BinaryOperator::Subtract),
+            Token::LThLTh => Some(BinaryOperator::ShiftLeft),
+            Token::GThGTh => Some(BinaryOperator::ShiftRight),
+            Token::LTh => Some(BinaryOperator::Less),
+            Token::GTh => Some(BinaryOperator::Greater),
+            Token::LThEql => Some(BinaryOperator::LessEqual),
+            Token::GThEql => Some(BinaryOperator::GreaterEqual),
+            Token::EqlEql => Some(BinaryOperator::Equal),
+            Token::BangEql => Some(BinaryOperator::NotEqual),
+            Token::Amp => Some(BinaryOperator::BitAnd),
+            Token::Caret => Some(BinaryOperator::BitXor),
+            Token::Pipe => Some(BinaryOperator::BitOr),
+            Token::AmpAmp => Some(BinaryOperator::LogicalAnd),
+            Token::PipePipe => Some(BinaryOperator::LogicalOr),
+            _ => None,
+        }
+    }
+}
+
+/// C11 6.5.16
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignmentOperator {
+    Assign,
+    Multiply,
+    Divide,
+    Remainder,
+    Add,
+    Subtract,
+    ShiftLeft,
+    ShiftRight,
+    BitAnd,
+    BitXor,
+    BitOr,
+}
+
+impl AssignmentOperator {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::Eql => Some(AssignmentOperator::Assign),
+            Token::StarEql => Some(AssignmentOperator::Multiply),
+            Token::FSlEql => Some(AssignmentOperator::Divide),
+            Token::PctEql => Some(AssignmentOperator::Remainder),
+            Token::PlusEql => Some(AssignmentOperator::Add),
+            Token::DashEql => Some(AssignmentOperator::Subtract),
+            Token::LThLThEql => Some(AssignmentOperator::ShiftLeft),
+            Token::GThGThEql => Some(AssignmentOperator::ShiftRight),
+            Token::AmpEql => Some(AssignmentOperator::BitAnd),
+            Token::CaretEql => Some(AssignmentOperator::BitXor),
+            Token::PipeEql => Some(AssignmentOperator::BitOr),
+            _ => None,
+        }
+    }
+
+    /// the operator a compound assignment applies, `None` for plain `=`
+    pub fn binary_operator(&self) -> Option<BinaryOperator> {
+        match self {
+            AssignmentOperator::Assign => None,
+            AssignmentOperator::Multiply => Some(BinaryOperator::Multiply),
+            AssignmentOperator::Divide => Some(BinaryOperator::Divide),
+            AssignmentOperator::Remainder => Some(BinaryOperator::Remainder),
+            AssignmentOperator::Add => Some(BinaryOperator::Add),
+            AssignmentOperator::Subtract => Some(BinaryOperator::Subtract),
+            AssignmentOperator::ShiftLeft => Some(BinaryOperator::ShiftLeft),
+            AssignmentOperator::ShiftRight => Some(BinaryOperator::ShiftRight),
+            AssignmentOperator::BitAnd => Some(BinaryOperator::BitAnd),
+            AssignmentOperator::BitXor => Some(BinaryOperator::BitXor),
+            AssignmentOperator::BitOr => Some(BinaryOperator::BitOr),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use lexer::Span;
+
+use super::{Declaration, Expression, Identifier};
+
+/// C11 6.8
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementKind {
+    /// `label: statement`
+    Labeled {
+        label: Identifier,
+        statement: Box<Statement>,
+    },
+    Case {
+        value: Box<Expression>,
+        statement: Box<Statement>,
+    },
+    Default(Box<Statement>),
+    Compound(CompoundStatement),
+    /// `expression;`, or just `;` for the null statement
+    Expression(Option<Box<Expression>>),
+    If {
+        condition: Box<Expression>,
+        then: Box<Statement>,
+        otherwise: Option<Box<Statement>>,
+    },
+    Switch {
+        condition: Box<Expression>,
+        body: Box<Statement>,
+    },
+    While {
+        condition: Box<Expression>,
+        body: Box<Statement>,
+    },
+    DoWhile {
+        body: Box<Statement>,
+        condition: Box<Expression>,
+    },
+    /// any of the three clauses may be left out, a missing condition is always true
+    For {
+        init: ForInit,
+        condition: Option<Box<Expression>>,
+        step: Option<Box<Expression>>,
+        body: Box<Statement>,
+    },
+    Goto(Identifier),
+    Continue,
+    Break,
+    Return(Option<Box<Expression>>),
+}
+
+/// C11 6.8.5p1, the first clause of a `for` may declare the loop's variables
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForInit {
+    Expression(Option<Box<Expression>>),
+    Declaration(Declaration),
+}
+
+/// C11 6.8.2, `{ block-item-list }` which opens a new scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompoundStatement {
+    pub items: Vec<BlockItem>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockItem {
+    Declaration(Declaration),
+    Statement(Statement),
+}
+
+impl BlockItem {
+    pub fn span(&self) -> Span {
+        match self {
+            BlockItem::Declaration(declaration) => declaration.span,
+            BlockItem::Statement(statement) => statement.span,
+        }
+    }
+}
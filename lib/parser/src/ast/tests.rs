@@ -0,0 +1,162 @@
+use lexer::{Span, Symbol, Token};
+
+use super::*;
+
+fn identifier(name: &str, start: usize) -> Identifier {
+    Identifier {
+        symbol: Symbol::intern(name),
+        span: Span::new(start, start + name.len()),
+    }
+}
+
+fn specifier(kind: DeclarationSpecifierKind, start: usize, end: usize) -> DeclarationSpecifier {
+    DeclarationSpecifier {
+        kind,
+        span: Span::new(start, end),
+    }
+}
+
+#[test]
+fn test_declarator_identifier() {
+    // int (*f)(void)
+    let f = identifier("f", 6);
+    let pointer = Declarator {
+        kind: DeclaratorKind::Pointer {
+            qualifiers: vec![],
+            declarator: Box::new(Declarator {
+                kind: DeclaratorKind::Identifier(f),
+                span: f.span,
+            }),
+        },
+        span: Span::new(5, 7),
+    };
+    let function = Declarator {
+        kind: DeclaratorKind::Function {
+            declarator: Box::new(pointer),
+            parameters: FunctionParameters::Prototype {
+                parameters: vec![],
+                variadic: false,
+            },
+        },
+        span: Span::new(4, 14),
+    };
+    assert_eq!(Some(f), function.identifier());
+    assert_eq!("f", function.identifier().unwrap().as_str());
+    assert!(!function.is_abstract());
+
+    // the int * of a cast
+    let abstract_pointer = Declarator {
+        kind: DeclaratorKind::Pointer {
+            qualifiers: vec![Qualifier {
+                kind: TypeQualifier::Const,
+                span: Span::new(6, 11),
+            }],
+            declarator: Box::new(Declarator {
+                kind: DeclaratorKind::Abstract,
+                span: Span::new(11, 11),
+            }),
+        },
+        span: Span::new(4, 11),
+    };
+    assert_eq!(None, abstract_pointer.identifier());
+    assert!(abstract_pointer.is_abstract());
+}
+
+#[test]
+fn test_declaration_specifiers() {
+    // typedef const unsigned long T;
+    let specifiers = DeclarationSpecifiers {
+        specifiers: vec![
+            specifier(
+                DeclarationSpecifierKind::StorageClass(StorageClass::Typedef),
+                0,
+                7,
+            ),
+            specifier(
+                DeclarationSpecifierKind::Qualifier(TypeQualifier::Const),
+                8,
+                13,
+            ),
+            specifier(
+                DeclarationSpecifierKind::Type(TypeSpecifier::Unsigned),
+                14,
+                22,
+            ),
+            specifier(DeclarationSpecifierKind::Type(TypeSpecifier::Long), 23, 27),
+        ],
+        span: Span::new(0, 27),
+    };
+    assert!(specifiers.is_typedef());
+    assert_eq!(
+        vec![StorageClass::Typedef],
+        specifiers.storage_classes().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&TypeSpecifier::Unsigned, &TypeSpecifier::Long],
+        specifiers.type_specifiers().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![TypeQualifier::Const],
+        specifiers.qualifiers().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_specifiers_from_tokens() {
+    assert_eq!(
+        Some(StorageClass::ThreadLocal),
+        StorageClass::from_token(&Token::Kw_Thread_local)
+    );
+    assert_eq!(
+        Some(TypeSpecifier::Bool),
+        TypeSpecifier::from_token(&Token::Kw_Bool)
+    );
+    assert_eq!(
+        Some(TypeQualifier::Restrict),
+        TypeQualifier::from_token(&Token::Kw__restrict)
+    );
+    assert_eq!(
+        Some(FunctionSpecifier::Noreturn),
+        FunctionSpecifier::from_token(&Token::Kw_Noreturn)
+    );
+    assert_eq!(None, TypeSpecifier::from_token(&Token::KwConst));
+}
+
+#[test]
+fn test_operators_from_tokens() {
+    assert_eq!(
+        Some(BinaryOperator::Remainder),
+        BinaryOperator::from_token(&Token::Pct)
+    );
+    assert_eq!(None, BinaryOperator::from_token(&Token::Eql));
+    assert_eq!(
+        Some(UnaryOperator::PreIncrement),
+        UnaryOperator::prefix_from_token(&Token::PlusPlus)
+    );
+    assert_eq!(
+        Some(UnaryOperator::PostIncrement),
+        UnaryOperator::postfix_from_token(&Token::PlusPlus)
+    );
+    let shift = AssignmentOperator::from_token(&Token::LThLThEql).unwrap();
+    assert_eq!(AssignmentOperator::ShiftLeft, shift);
+    assert_eq!(Some(BinaryOperator::ShiftLeft), shift.binary_operator());
+    assert_eq!(None, AssignmentOperator::Assign.binary_operator());
+}
+
+#[test]
+fn test_constants_from_tokens() {
+    assert_eq!(
+        Some(Constant::ULong(7)),
+        Constant::from_token(&Token::IntLitULong(7))
+    );
+    assert_eq!(
+        Some(Constant::Char16(0x263a)),
+        Constant::from_token(&Token::CharLit_u(0x263a))
+    );
+    assert_eq!(
+        Some(StringLiteral::WChar(vec![104, 105, 0])),
+        StringLiteral::from_token(&Token::StringLit_L(vec![104, 105, 0]))
+    );
+    assert_eq!(None, Constant::from_token(&Token::StringLit(vec![0])));
+    assert_eq!(None, StringLiteral::from_token(&Token::IntLitInt(0)));
+}
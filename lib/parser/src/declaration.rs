@@ -0,0 +1,845 @@
+use lang::Standard;
+use lexer::{Span, Token};
+
+use crate::ast::{
+    AlignmentSpecifier, ArraySize, Declaration, DeclarationKind, DeclarationSpecifier,
+    DeclarationSpecifierKind, DeclarationSpecifiers, Declarator, DeclaratorKind, Designator,
+    DesignatorKind, EnumSpecifier, Enumerator, ExternalDeclaration, FunctionDefinition,
+    FunctionParameters, FunctionSpecifier, InitDeclarator, Initializer, InitializerKind,
+    InitializerListItem, ParameterDeclaration, Qualifier, StaticAssert, StorageClass,
+    StructDeclaration, StructDeclarationKind, StructDeclarator, StructOrUnion,
+    StructOrUnionSpecifier, TranslationUnit, TypeName, TypeQualifier, TypeSpecifier, TypeofOperand,
+};
+use crate::state::{Parsed, Parser};
+
+/// whether a declarator has to declare a name, mustn't, or may do either as a parameter's
+/// does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Naming {
+    Named,
+    Abstract,
+    Either,
+}
+
+impl<'a> Parser<'a> {
+    /// C11 6.9, the whole of the input. A syntax error in an external declaration is
+    /// reported and we carry on from the next `;` or `}`
+    pub fn translation_unit(&mut self) -> TranslationUnit {
+        let start = self.peek_span();
+        let mut declarations = vec![];
+        while !self.at_end() && !self.stopped() {
+            if self.peek_is(&Token::Semi) {
+                let message = "ISO C does not allow extra ';' outside of a function";
+                self.extension_at(self.position(), "pedantic", message);
+                self.next();
+                continue;
+            }
+            match self.external_declaration() {
+                Ok(declaration) => declarations.push(declaration),
+                Err(_) => {
+                    self.recover();
+                    // the error was in braces we're not in any more, or it was a stray `}`
+                    self.eat(&Token::RBrace);
+                }
+            }
+        }
+        TranslationUnit {
+            declarations,
+            span: self.since(start),
+        }
+    }
+
+    /// C11 6.9, a function definition only differs from a declaration once its declarator
+    /// is followed by a `{`, or by the declarations of a K&R style definition's parameters
+    fn external_declaration(&mut self) -> Parsed<ExternalDeclaration> {
+        if self.peek_is(&Token::Kw_Static_assert) || self.peek_is(&Token::KwStatic_assert) {
+            return Ok(ExternalDeclaration::Declaration(self.declaration()?));
+        }
+        let start = self.peek_span();
+        let specifiers = self.declaration_specifiers()?;
+        if self.eat(&Token::Semi) {
+            return Ok(ExternalDeclaration::Declaration(Declaration {
+                kind: DeclarationKind::Declaration {
+                    specifiers,
+                    declarators: vec![],
+                },
+                span: self.since(start),
+            }));
+        }
+        let declarator = self.declarator()?;
+        if self.starts_function_body(&declarator) {
+            let definition = self.function_definition(start, specifiers, declarator)?;
+            return Ok(ExternalDeclaration::FunctionDefinition(definition));
+        }
+        let declarators = self.init_declarators(specifiers.is_typedef(), declarator)?;
+        Ok(ExternalDeclaration::Declaration(Declaration {
+            kind: DeclarationKind::Declaration {
+                specifiers,
+                declarators,
+            },
+            span: self.since(start),
+        }))
+    }
+
+    /// whether a function's body, or the parameter declarations of a K&R style
+    /// definition, follow `declarator`
+    fn starts_function_body(&self, declarator: &Declarator) -> bool {
+        match declarator.parameters() {
+            Some(FunctionParameters::Identifiers(identifiers)) if !identifiers.is_empty() => {
+                self.peek_is(&Token::LBrace) || self.starts_declaration()
+            }
+            Some(_) => self.peek_is(&Token::LBrace),
+            None => false,
+        }
+    }
+
+    /// C11 6.9.1, after the declarator. The parameters are in scope in the body, and so
+    /// are those of a K&R style definition whether or not they're declared
+    fn function_definition(
+        &mut self,
+        start: Span,
+        specifiers: DeclarationSpecifiers,
+        declarator: Declarator,
+    ) -> Parsed<FunctionDefinition> {
+        self.scoped(|parser| {
+            match declarator.parameters() {
+                Some(FunctionParameters::Prototype { parameters, .. }) => {
+                    for parameter in parameters {
+                        if let Some(name) = parameter.declarator.identifier() {
+                            parser.declare(name, false);
+                        }
+                    }
+                }
+                Some(FunctionParameters::Identifiers(identifiers)) => {
+                    for &name in identifiers {
+                        parser.declare(name, false);
+                    }
+                }
+                None => (),
+            }
+            let mut declarations = vec![];
+            while !parser.peek_is(&Token::LBrace) {
+                declarations.push(parser.declaration()?);
+            }
+            let body = parser.compound_statement()?;
+            Ok(FunctionDefinition {
+                specifiers,
+                declarator,
+                declarations,
+                body,
+                span: parser.since(start),
+            })
+        })
+    }
+
+    /// C11 6.7, up to and including the `;`
+    pub fn declaration(&mut self) -> Parsed<Declaration> {
+        let start = self.peek_span();
+        if self.eat(&Token::Kw_Static_assert) || self.eat(&Token::KwStatic_assert) {
+            let assertion = self.static_assert(start)?;
+            return Ok(Declaration {
+                kind: DeclarationKind::StaticAssert(assertion),
+                span: self.since(start),
+            });
+        }
+        let specifiers = self.declaration_specifiers()?;
+        let declarators = match self.eat(&Token::Semi) {
+            true => vec![],
+            false => {
+                let declarator = self.declarator()?;
+                self.init_declarators(specifiers.is_typedef(), declarator)?
+            }
+        };
+        Ok(Declaration {
+            kind: DeclarationKind::Declaration {
+                specifiers,
+                declarators,
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.10, after the `_Static_assert` and up to and including the `;`
+    fn static_assert(&mut self, start: Span) -> Parsed<StaticAssert> {
+        // the lexer has reported the keyword itself before C11
+        let standard = self.lang().standard;
+        self.expect(&Token::LParen)?;
+        let condition = self.constant_expression()?;
+        let message = match self.eat(&Token::Comma) {
+            true => Some(Box::new(self.string_literal()?)),
+            false => None,
+        };
+        if message.is_none() && (Standard::C11..Standard::C23).contains(&standard) {
+            let text =
+                format!("ISO {standard} does not support omitting the string in '_Static_assert'");
+            self.extension_at(self.position(), "pedantic", &text);
+        }
+        self.expect(&Token::RParen)?;
+        self.expect(&Token::Semi)?;
+        Ok(StaticAssert {
+            condition: Box::new(condition),
+            message,
+            span: self.since(start),
+        })
+    }
+
+    /// the init-declarator-list of a declaration whose first declarator has been parsed,
+    /// up to and including the `;`
+    fn init_declarators(
+        &mut self,
+        is_typedef: bool,
+        first: Declarator,
+    ) -> Parsed<Vec<InitDeclarator>> {
+        let mut declarators = vec![self.init_declarator(is_typedef, first)?];
+        while self.eat(&Token::Comma) {
+            let declarator = self.declarator()?;
+            declarators.push(self.init_declarator(is_typedef, declarator)?);
+        }
+        self.expect(&Token::Semi)?;
+        Ok(declarators)
+    }
+
+    /// the name is in scope from the end of its declarator, so its own initializer can
+    /// refer to it. gcc's headers follow a declarator with an asm label, which renames the
+    /// symbol, and attributes, both of which we ignore
+    fn init_declarator(
+        &mut self,
+        is_typedef: bool,
+        declarator: Declarator,
+    ) -> Parsed<InitDeclarator> {
+        self.asm_label()?;
+        self.attributes()?;
+        if let Some(name) = declarator.identifier() {
+            self.declare(name, is_typedef);
+        }
+        let start = declarator.span;
+        let initializer = match self.eat(&Token::Eql) {
+            true => Some(self.initializer()?),
+            false => None,
+        };
+        Ok(InitDeclarator {
+            declarator,
+            initializer,
+            span: self.since(start),
+        })
+    }
+
+    /// whether the token `n` after the next one can start a type name, which is also how
+    /// a cast is told apart from a parenthesised expression
+    pub fn starts_type_name(&self, n: usize) -> bool {
+        match self.peek_nth(n) {
+            Some(&Token::Identifier(symbol)) => self.is_typedef_name(symbol),
+            Some(
+                Token::KwStruct
+                | Token::KwUnion
+                | Token::KwEnum
+                | Token::Kw_BitInt
+                | Token::KwTypeof
+                | Token::KwTypeof_unqual
+                | Token::Kw__typeof__,
+            ) => true,
+            Some(token) => {
+                TypeSpecifier::from_token(token).is_some()
+                    || TypeQualifier::from_token(token).is_some()
+            }
+            None => false,
+        }
+    }
+
+    /// whether the next token can start a declaration rather than a statement, either of
+    /// which gcc's `__extension__` can come before
+    pub fn starts_declaration(&self) -> bool {
+        let mut n = 0;
+        while self.peek_nth(n) == Some(&Token::Kw__extension__) {
+            n += 1;
+        }
+        let Some(token) = self.peek_nth(n) else {
+            return false;
+        };
+        self.starts_type_name(n)
+            || StorageClass::from_token(token).is_some()
+            || FunctionSpecifier::from_token(token).is_some()
+            || matches!(
+                token,
+                Token::Kw_Alignas
+                    | Token::KwAlignas
+                    | Token::Kw_Static_assert
+                    | Token::KwStatic_assert
+                    | Token::Kw__attribute__
+            )
+    }
+
+    /// C11 6.7, as many specifiers as there are, of which there has to be at least one
+    fn declaration_specifiers(&mut self) -> Parsed<DeclarationSpecifiers> {
+        self.specifiers(false, "declaration specifiers")
+    }
+
+    /// C11 6.7.2.1, the specifiers of a type name or a member, which can't be storage
+    /// classes or function specifiers
+    fn specifier_qualifiers(&mut self) -> Parsed<DeclarationSpecifiers> {
+        self.specifiers(true, "specifier-qualifier-list")
+    }
+
+    /// an identifier is only a typedef name until there's been a type specifier, so `T`
+    /// is declared rather than used in `typedef int T; void f(void) { long T; }`
+    fn specifiers(&mut self, only_types: bool, what: &str) -> Parsed<DeclarationSpecifiers> {
+        let start = self.peek_span();
+        let mut specifiers: Vec<DeclarationSpecifier> = vec![];
+        loop {
+            self.extensions()?;
+            if only_types
+                && self.peek().is_some_and(|t| {
+                    StorageClass::from_token(t).is_some()
+                        || FunctionSpecifier::from_token(t).is_some()
+                })
+            {
+                break;
+            }
+            let seen_type = specifiers
+                .iter()
+                .any(|specifier| matches!(specifier.kind, DeclarationSpecifierKind::Type(_)));
+            match self.declaration_specifier(seen_type)? {
+                Some(specifier) => specifiers.push(specifier),
+                None => break,
+            }
+        }
+        if specifiers.is_empty() {
+            return Err(self.expected(what));
+        }
+        Ok(DeclarationSpecifiers {
+            specifiers,
+            span: self.since(start),
+        })
+    }
+
+    fn declaration_specifier(&mut self, seen_type: bool) -> Parsed<Option<DeclarationSpecifier>> {
+        let start = self.peek_span();
+        let Some(token) = self.peek() else {
+            return Ok(None);
+        };
+        let kind = if let Some(class) = StorageClass::from_token(token) {
+            self.next();
+            DeclarationSpecifierKind::StorageClass(class)
+        } else if let Some(specifier) = TypeSpecifier::from_token(token) {
+            self.next();
+            DeclarationSpecifierKind::Type(specifier)
+        } else if token == &Token::Kw_Atomic && self.peek_nth(1) == Some(&Token::LParen) {
+            // `_Atomic(int)` rather than the qualifier of `_Atomic int`
+            self.next();
+            let type_name = self.parenthesised_type_name()?;
+            DeclarationSpecifierKind::Type(TypeSpecifier::Atomic(Box::new(type_name)))
+        } else if let Some(qualifier) = TypeQualifier::from_token(token) {
+            self.next();
+            DeclarationSpecifierKind::Qualifier(qualifier)
+        } else if let Some(specifier) = FunctionSpecifier::from_token(token) {
+            self.next();
+            DeclarationSpecifierKind::Function(specifier)
+        } else {
+            match token {
+                Token::KwStruct | Token::KwUnion => {
+                    DeclarationSpecifierKind::Type(self.struct_or_union_specifier()?)
+                }
+                Token::KwEnum => DeclarationSpecifierKind::Type(self.enum_specifier()?),
+                Token::Kw_BitInt => {
+                    self.next();
+                    self.expect(&Token::LParen)?;
+                    let width = self.constant_expression()?;
+                    self.expect(&Token::RParen)?;
+                    DeclarationSpecifierKind::Type(TypeSpecifier::BitInt(Box::new(width)))
+                }
+                Token::KwTypeof | Token::KwTypeof_unqual | Token::Kw__typeof__ => {
+                    DeclarationSpecifierKind::Type(self.typeof_specifier()?)
+                }
+                Token::Kw_Alignas | Token::KwAlignas => {
+                    self.next();
+                    DeclarationSpecifierKind::Alignment(self.alignment_specifier()?)
+                }
+                &Token::Identifier(symbol) if !seen_type && self.is_typedef_name(symbol) => {
+                    DeclarationSpecifierKind::Type(TypeSpecifier::TypedefName(self.identifier()?))
+                }
+                _ => return Ok(None),
+            }
+        };
+        Ok(Some(DeclarationSpecifier {
+            kind,
+            span: self.since(start),
+        }))
+    }
+
+    /// C23 6.7.2.5, `typeof (expression)` or `typeof (type-name)`
+    fn typeof_specifier(&mut self) -> Parsed<TypeSpecifier> {
+        let unqualified = self.next().map(|t| t.token()) == Some(&Token::KwTypeof_unqual);
+        self.expect(&Token::LParen)?;
+        let operand = match self.starts_type_name(0) {
+            true => TypeofOperand::Type(Box::new(self.type_name()?)),
+            false => TypeofOperand::Expression(Box::new(self.expression()?)),
+        };
+        self.expect(&Token::RParen)?;
+        Ok(TypeSpecifier::Typeof {
+            operand,
+            unqualified,
+        })
+    }
+
+    /// gcc's `__extension__`, which only stops gcc warning about what follows, and its
+    /// attributes, both of which we skip
+    fn extensions(&mut self) -> Parsed<()> {
+        while self.eat(&Token::Kw__extension__) {}
+        self.attributes()
+    }
+
+    /// gcc's `__attribute__((...))`, any number of them. They don't change what is
+    /// declared as far as the syntax goes so we skip them
+    fn attributes(&mut self) -> Parsed<()> {
+        while self.eat(&Token::Kw__attribute__) {
+            self.expect(&Token::LParen)?;
+            self.expect(&Token::LParen)?;
+            let mut depth = 2;
+            while depth > 0 {
+                match self.peek() {
+                    Some(Token::LParen) => depth += 1,
+                    Some(Token::RParen) => depth -= 1,
+                    Some(_) => (),
+                    None => return Err(self.expected("')'")),
+                }
+                self.next();
+            }
+        }
+        Ok(())
+    }
+
+    /// gcc's `__asm__("name")` after a declarator, which gives the symbol another name
+    fn asm_label(&mut self) -> Parsed<()> {
+        if self.eat(&Token::Kw__asm__) {
+            self.expect(&Token::LParen)?;
+            self.string_literal()?;
+            self.expect(&Token::RParen)?;
+        }
+        Ok(())
+    }
+
+    /// C11 6.7.5, after the `_Alignas`
+    fn alignment_specifier(&mut self) -> Parsed<AlignmentSpecifier> {
+        self.expect(&Token::LParen)?;
+        let specifier = match self.starts_type_name(0) {
+            true => AlignmentSpecifier::Type(Box::new(self.type_name()?)),
+            false => AlignmentSpecifier::Expression(Box::new(self.constant_expression()?)),
+        };
+        self.expect(&Token::RParen)?;
+        Ok(specifier)
+    }
+
+    /// C11 6.7.2.1, the tag may only be left out when the members follow
+    fn struct_or_union_specifier(&mut self) -> Parsed<TypeSpecifier> {
+        let start = self.peek_span();
+        let kind = match self.next().map(|t| t.token()) {
+            Some(Token::KwUnion) => StructOrUnion::Union,
+            _ => StructOrUnion::Struct,
+        };
+        self.attributes()?;
+        let tag = match self.peek() {
+            Some(Token::Identifier(_)) => Some(self.identifier()?),
+            _ => None,
+        };
+        let members = match (tag, self.peek_is(&Token::LBrace)) {
+            (_, true) => Some(self.struct_declarations(kind)?),
+            (Some(_), false) => None,
+            (None, false) => return Err(self.expected("identifier or '{'")),
+        };
+        Ok(TypeSpecifier::StructOrUnion(StructOrUnionSpecifier {
+            kind,
+            tag,
+            members,
+            span: self.since(start),
+        }))
+    }
+
+    /// C11 6.7.2.1, `{ struct-declaration-list }`. gcc allows the list to be empty
+    fn struct_declarations(&mut self, kind: StructOrUnion) -> Parsed<Vec<StructDeclaration>> {
+        self.expect(&Token::LBrace)?;
+        if self.peek_is(&Token::RBrace) {
+            let message = match kind {
+                StructOrUnion::Struct => "struct has no members",
+                StructOrUnion::Union => "union has no members",
+            };
+            self.extension_at(self.position(), "pedantic", message);
+        }
+        let mut members = vec![];
+        while !self.eat(&Token::RBrace) {
+            members.push(self.struct_declaration()?);
+        }
+        Ok(members)
+    }
+
+    /// C11 6.7.2.1, up to and including the `;`. The members are in a namespace of their
+    /// own so they don't hide typedef names
+    fn struct_declaration(&mut self) -> Parsed<StructDeclaration> {
+        let start = self.peek_span();
+        let kind = if self.eat(&Token::Kw_Static_assert) || self.eat(&Token::KwStatic_assert) {
+            StructDeclarationKind::StaticAssert(self.static_assert(start)?)
+        } else {
+            let specifiers = self.specifier_qualifiers()?;
+            let mut declarators = vec![];
+            if !self.eat(&Token::Semi) {
+                declarators.push(self.struct_declarator()?);
+                while self.eat(&Token::Comma) {
+                    declarators.push(self.struct_declarator()?);
+                }
+                self.expect(&Token::Semi)?;
+            }
+            StructDeclarationKind::Member {
+                specifiers,
+                declarators,
+            }
+        };
+        Ok(StructDeclaration {
+            kind,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.2.1, a member or a bit-field, whose name may be left out (`int : 3;`)
+    fn struct_declarator(&mut self) -> Parsed<StructDeclarator> {
+        let start = self.peek_span();
+        let declarator = match self.peek_is(&Token::Colon) {
+            true => None,
+            false => Some(self.declarator()?),
+        };
+        let width = match self.eat(&Token::Colon) {
+            true => Some(Box::new(self.constant_expression()?)),
+            false => None,
+        };
+        self.attributes()?;
+        Ok(StructDeclarator {
+            declarator,
+            width,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.2.2, the tag may only be left out when the enumerators follow
+    fn enum_specifier(&mut self) -> Parsed<TypeSpecifier> {
+        let start = self.peek_span();
+        self.expect(&Token::KwEnum)?;
+        self.attributes()?;
+        let tag = match self.peek() {
+            Some(Token::Identifier(_)) => Some(self.identifier()?),
+            _ => None,
+        };
+        let enumerators = match (tag, self.peek_is(&Token::LBrace)) {
+            (_, true) => Some(self.enumerators()?),
+            (Some(_), false) => None,
+            (None, false) => return Err(self.expected("identifier or '{'")),
+        };
+        Ok(TypeSpecifier::Enum(EnumSpecifier {
+            tag,
+            enumerators,
+            span: self.since(start),
+        }))
+    }
+
+    /// C11 6.7.2.2, `{ enumerator-list }` with an optional trailing `,`. Enumerators are
+    /// ordinary identifiers, each in scope from the end of its own definition
+    fn enumerators(&mut self) -> Parsed<Vec<Enumerator>> {
+        self.expect(&Token::LBrace)?;
+        let mut enumerators = vec![];
+        loop {
+            let start = self.peek_span();
+            let name = self.identifier()?;
+            self.attributes()?;
+            let value = match self.eat(&Token::Eql) {
+                true => Some(Box::new(self.constant_expression()?)),
+                false => None,
+            };
+            self.declare(name, false);
+            enumerators.push(Enumerator {
+                name,
+                value,
+                span: self.since(start),
+            });
+            if self.eat(&Token::RBrace) {
+                return Ok(enumerators);
+            }
+            self.expect(&Token::Comma)?;
+            if self.peek_is(&Token::RBrace) {
+                if !self.lang().at_least(Standard::C99) {
+                    let message = "comma at end of enumerator list";
+                    self.extension_at(self.position() - 1, "pedantic", message);
+                }
+                self.next();
+                return Ok(enumerators);
+            }
+        }
+    }
+
+    /// the qualifiers after a `*` or in an array parameter's `[]`, among which gcc allows
+    /// attributes
+    fn qualifiers(&mut self) -> Parsed<Vec<Qualifier>> {
+        let mut qualifiers = vec![];
+        loop {
+            self.attributes()?;
+            let Some(kind) = self.peek().and_then(TypeQualifier::from_token) else {
+                return Ok(qualifiers);
+            };
+            qualifiers.push(Qualifier {
+                kind,
+                span: self.peek_span(),
+            });
+            self.next();
+        }
+    }
+
+    /// C11 6.7.7
+    pub fn type_name(&mut self) -> Parsed<TypeName> {
+        let start = self.peek_span();
+        let specifiers = self.specifier_qualifiers()?;
+        let declarator = self.declarator_naming(Naming::Abstract)?;
+        Ok(TypeName {
+            specifiers,
+            declarator,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.6, a declarator which declares a name
+    fn declarator(&mut self) -> Parsed<Declarator> {
+        self.declarator_naming(Naming::Named)
+    }
+
+    fn declarator_naming(&mut self, naming: Naming) -> Parsed<Declarator> {
+        let start = self.peek_span();
+        if !self.eat(&Token::Star) {
+            return self.direct_declarator(naming);
+        }
+        let qualifiers = self.qualifiers()?;
+        let declarator = self.declarator_naming(naming)?;
+        Ok(Declarator {
+            kind: DeclaratorKind::Pointer {
+                qualifiers,
+                declarator: Box::new(declarator),
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.6 and 6.7.7, a name or a parenthesised declarator followed by any number of
+    /// array and function suffixes
+    fn direct_declarator(&mut self, naming: Naming) -> Parsed<Declarator> {
+        let start = self.peek_span();
+        let mut declarator = match self.peek() {
+            Some(&Token::Identifier(_)) if naming != Naming::Abstract => Declarator {
+                kind: DeclaratorKind::Identifier(self.identifier()?),
+                span: start,
+            },
+            Some(Token::LParen) if self.groups_declarator(naming) => {
+                self.next();
+                self.attributes()?;
+                let declarator = self.declarator_naming(naming)?;
+                self.expect(&Token::RParen)?;
+                declarator
+            }
+            _ if naming != Naming::Named => Declarator {
+                kind: DeclaratorKind::Abstract,
+                span: Span::new(start.start(), start.start()),
+            },
+            _ => return Err(self.expected("identifier or '('")),
+        };
+
+        loop {
+            let kind = match self.peek() {
+                Some(Token::LSquare) => {
+                    self.next();
+                    self.array_declarator(declarator)?
+                }
+                Some(Token::LParen) => {
+                    self.next();
+                    DeclaratorKind::Function {
+                        declarator: Box::new(declarator),
+                        parameters: self.parameters()?,
+                    }
+                }
+                _ => return Ok(declarator),
+            };
+            declarator = Declarator {
+                kind,
+                span: self.since(start),
+            };
+        }
+    }
+
+    /// whether the `(` next is around a declarator rather than the parameters of a
+    /// function whose declarator was left out, as in `int (*)(int)`
+    fn groups_declarator(&self, naming: Naming) -> bool {
+        match naming {
+            Naming::Named => true,
+            Naming::Abstract | Naming::Either => {
+                !(self.peek_nth(1) == Some(&Token::RParen)
+                    || self.starts_type_name(1)
+                    || self
+                        .peek_nth(1)
+                        .is_some_and(|t| StorageClass::from_token(t).is_some()))
+            }
+        }
+    }
+
+    /// C11 6.7.6.2, after the `[`
+    fn array_declarator(&mut self, declarator: Declarator) -> Parsed<DeclaratorKind> {
+        let mut is_static = self.eat(&Token::KwStatic);
+        let qualifiers = self.qualifiers()?;
+        is_static = is_static || self.eat(&Token::KwStatic);
+        let size = if self.peek_is(&Token::Star) && self.peek_nth(1) == Some(&Token::RSquare) {
+            self.next();
+            ArraySize::Variable
+        } else if self.peek_is(&Token::RSquare) {
+            ArraySize::Unknown
+        } else {
+            ArraySize::Expression(Box::new(self.assignment_expression()?))
+        };
+        self.expect(&Token::RSquare)?;
+        Ok(DeclaratorKind::Array {
+            declarator: Box::new(declarator),
+            qualifiers,
+            is_static,
+            size,
+        })
+    }
+
+    /// C11 6.7.6.3, after the `(` and up to and including the `)`. An identifier which
+    /// isn't a typedef name starts the identifier list of a K&R style definition
+    fn parameters(&mut self) -> Parsed<FunctionParameters> {
+        if self.eat(&Token::RParen) {
+            return Ok(FunctionParameters::Identifiers(vec![]));
+        }
+        match self.peek() {
+            Some(&Token::Identifier(symbol)) if !self.is_typedef_name(symbol) => {
+                self.identifier_list()
+            }
+            _ => self.scoped(Self::parameter_type_list),
+        }
+    }
+
+    /// the parameters of a prototype, which are in scope until the end of its declarator.
+    /// A trailing `...` makes the function variadic
+    fn parameter_type_list(&mut self) -> Parsed<FunctionParameters> {
+        let mut parameters = vec![];
+        loop {
+            if self.peek_is(&Token::Ellipsis) {
+                if parameters.is_empty() && !self.lang().at_least(Standard::C23) {
+                    let message = "ISO C requires a named argument before '...'";
+                    self.error_at(self.position(), "named-argument", message);
+                }
+                self.next();
+                self.expect(&Token::RParen)?;
+                return Ok(FunctionParameters::Prototype {
+                    parameters,
+                    variadic: true,
+                });
+            }
+            let parameter = self.parameter_declaration()?;
+            if let Some(name) = parameter.declarator.identifier() {
+                self.declare(name, false);
+            }
+            parameters.push(parameter);
+            if self.eat(&Token::RParen) {
+                return Ok(FunctionParameters::Prototype {
+                    parameters,
+                    variadic: false,
+                });
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+
+    /// the `(a, b)` of a K&R style definition, up to and including the `)`
+    fn identifier_list(&mut self) -> Parsed<FunctionParameters> {
+        let mut identifiers = vec![self.identifier()?];
+        while self.eat(&Token::Comma) {
+            identifiers.push(self.identifier()?);
+        }
+        self.expect(&Token::RParen)?;
+        Ok(FunctionParameters::Identifiers(identifiers))
+    }
+
+    fn parameter_declaration(&mut self) -> Parsed<ParameterDeclaration> {
+        let start = self.peek_span();
+        let specifiers = self.declaration_specifiers()?;
+        let declarator = self.declarator_naming(Naming::Either)?;
+        self.attributes()?;
+        Ok(ParameterDeclaration {
+            specifiers,
+            declarator,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.7.9
+    fn initializer(&mut self) -> Parsed<Initializer> {
+        let start = self.peek_span();
+        let kind = match self.peek_is(&Token::LBrace) {
+            true => InitializerKind::List(self.braced_initializer_list()?),
+            false => InitializerKind::Expression(Box::new(self.assignment_expression()?)),
+        };
+        Ok(Initializer {
+            kind,
+            span: self.since(start),
+        })
+    }
+
+    /// `{ initializer-list }` with an optional trailing `,`. C23 allows the list to be
+    /// empty, which gcc accepts in earlier modes too
+    pub fn braced_initializer_list(&mut self) -> Parsed<Vec<InitializerListItem>> {
+        self.expect(&Token::LBrace)?;
+        let mut items = vec![];
+        if self.peek_is(&Token::RBrace) && !self.lang().at_least(Standard::C23) {
+            let message = "ISO C forbids empty initializer braces before C23";
+            self.extension_at(self.position(), "pedantic", message);
+        }
+        while !self.eat(&Token::RBrace) {
+            items.push(self.initializer_list_item()?);
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RBrace)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn initializer_list_item(&mut self) -> Parsed<InitializerListItem> {
+        let start = self.peek_span();
+        let position = self.position();
+        let mut designators = vec![];
+        loop {
+            let designator_start = self.peek_span();
+            let kind = match self.peek() {
+                Some(Token::LSquare) => {
+                    self.next();
+                    let index = self.constant_expression()?;
+                    self.expect(&Token::RSquare)?;
+                    DesignatorKind::Index(Box::new(index))
+                }
+                Some(Token::Dot) => {
+                    self.next();
+                    DesignatorKind::Member(self.identifier()?)
+                }
+                _ => break,
+            };
+            designators.push(Designator {
+                kind,
+                span: self.since(designator_start),
+            });
+        }
+        if !designators.is_empty() {
+            if !self.lang().at_least(Standard::C99) {
+                let message = "ISO C90 forbids specifying subobject to initialize";
+                self.extension_at(position, "pedantic", message);
+            }
+            self.expect(&Token::Eql)?;
+        }
+        let initializer = self.initializer()?;
+        Ok(InitializerListItem {
+            designators,
+            initializer,
+            span: self.since(start),
+        })
+    }
+}
@@ -0,0 +1,391 @@
+use lang::Standard;
+use lexer::{Span, Token};
+
+use crate::ast::{
+    AssignmentOperator, BinaryOperator, Constant, Expression, ExpressionKind, GenericAssociation,
+    Identifier, StringLiteral, TypeName, UnaryOperator,
+};
+use crate::state::{Parsed, Parser};
+
+/// C11 6.5.5 to 6.5.14 from the loosest binding up, the binary operators of each level are
+/// left associative. Anything tighter than a multiplicative operator is a cast expression
+fn precedence(operator: BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::LogicalOr => 1,
+        BinaryOperator::LogicalAnd => 2,
+        BinaryOperator::BitOr => 3,
+        BinaryOperator::BitXor => 4,
+        BinaryOperator::BitAnd => 5,
+        BinaryOperator::Equal | BinaryOperator::NotEqual => 6,
+        BinaryOperator::Less
+        | BinaryOperator::Greater
+        | BinaryOperator::LessEqual
+        | BinaryOperator::GreaterEqual => 7,
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 8,
+        BinaryOperator::Add | BinaryOperator::Subtract => 9,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => 10,
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// C11 6.5.17, assignment expressions separated by commas
+    pub fn expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let mut left = self.assignment_expression()?;
+        while self.eat(&Token::Comma) {
+            let right = self.assignment_expression()?;
+            left = Expression {
+                kind: ExpressionKind::Comma {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span: self.since(start),
+            };
+        }
+        Ok(left)
+    }
+
+    /// C11 6.5.16, assignments are right associative. The grammar only allows a unary
+    /// expression to be assigned to but anything else isn't an lvalue either, which is
+    /// for whoever checks the types to report
+    pub fn assignment_expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let target = self.conditional_expression()?;
+        let Some(operator) = self.peek().and_then(AssignmentOperator::from_token) else {
+            return Ok(target);
+        };
+        self.next();
+        let value = self.assignment_expression()?;
+        Ok(Expression {
+            kind: ExpressionKind::Assignment {
+                operator,
+                target: Box::new(target),
+                value: Box::new(value),
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.6, which constraints it has to meet are checked once we know what it means
+    pub fn constant_expression(&mut self) -> Parsed<Expression> {
+        self.conditional_expression()
+    }
+
+    /// C11 6.5.15, `condition ? then : otherwise` where `then` may be any expression
+    fn conditional_expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let condition = self.binary_expression(1)?;
+        if !self.eat(&Token::Question) {
+            return Ok(condition);
+        }
+        let then = self.expression()?;
+        self.expect(&Token::Colon)?;
+        let otherwise = self.conditional_expression()?;
+        Ok(Expression {
+            kind: ExpressionKind::Conditional {
+                condition: Box::new(condition),
+                then: Box::new(then),
+                otherwise: Box::new(otherwise),
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// precedence climbing over the binary operators which bind at least as tightly as
+    /// `minimum`
+    fn binary_expression(&mut self, minimum: u8) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let mut left = self.cast_expression()?;
+        loop {
+            let operator = match self.peek().and_then(BinaryOperator::from_token) {
+                Some(operator) if precedence(operator) >= minimum => operator,
+                _ => return Ok(left),
+            };
+            self.next();
+            let right = self.binary_expression(precedence(operator) + 1)?;
+            left = Expression {
+                kind: ExpressionKind::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span: self.since(start),
+            };
+        }
+    }
+
+    /// C11 6.5.4, a parenthesised type name is either a cast or the start of a compound
+    /// literal, any other `(` starts a primary expression
+    fn cast_expression(&mut self) -> Parsed<Expression> {
+        if !(self.peek_is(&Token::LParen) && self.starts_type_name(1)) {
+            return self.unary_expression();
+        }
+        let start = self.peek_span();
+        let type_name = self.parenthesised_type_name()?;
+        if self.peek_is(&Token::LBrace) {
+            let literal = self.compound_literal(start, type_name)?;
+            return self.postfix_operators(start, literal);
+        }
+        let operand = self.cast_expression()?;
+        Ok(Expression {
+            kind: ExpressionKind::Cast {
+                type_name: Box::new(type_name),
+                operand: Box::new(operand),
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.5.3
+    fn unary_expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let kind = match self.peek() {
+            Some(Token::KwSizeof) => {
+                self.next();
+                self.sizeof_operand()?
+            }
+            Some(Token::Kw_Alignof | Token::KwAlignof) => {
+                self.next();
+                ExpressionKind::AlignofType(Box::new(self.parenthesised_type_name()?))
+            }
+            Some(Token::Kw__extension__) => {
+                // gcc's, it only stops gcc warning about the operand
+                self.next();
+                return self.cast_expression();
+            }
+            Some(token) => match UnaryOperator::prefix_from_token(token) {
+                Some(operator) => {
+                    self.next();
+                    // an increment or decrement applies to a unary expression, the other
+                    // operators to a cast expression
+                    let operand = match operator {
+                        UnaryOperator::PreIncrement | UnaryOperator::PreDecrement => {
+                            self.unary_expression()?
+                        }
+                        _ => self.cast_expression()?,
+                    };
+                    ExpressionKind::Unary {
+                        operator,
+                        operand: Box::new(operand),
+                    }
+                }
+                None => return self.postfix_expression(),
+            },
+            None => return self.postfix_expression(),
+        };
+        Ok(Expression {
+            kind,
+            span: self.since(start),
+        })
+    }
+
+    /// `sizeof (type-name)` unless the parentheses are those of a compound literal, which
+    /// like any other operand is a unary expression
+    fn sizeof_operand(&mut self) -> Parsed<ExpressionKind> {
+        if !(self.peek_is(&Token::LParen) && self.starts_type_name(1)) {
+            let operand = self.unary_expression()?;
+            return Ok(ExpressionKind::SizeofExpression(Box::new(operand)));
+        }
+        let start = self.peek_span();
+        let type_name = self.parenthesised_type_name()?;
+        if !self.peek_is(&Token::LBrace) {
+            return Ok(ExpressionKind::SizeofType(Box::new(type_name)));
+        }
+        let literal = self.compound_literal(start, type_name)?;
+        let operand = self.postfix_operators(start, literal)?;
+        Ok(ExpressionKind::SizeofExpression(Box::new(operand)))
+    }
+
+    pub fn parenthesised_type_name(&mut self) -> Parsed<TypeName> {
+        self.expect(&Token::LParen)?;
+        let type_name = self.type_name()?;
+        self.expect(&Token::RParen)?;
+        Ok(type_name)
+    }
+
+    /// C11 6.5.2.5, `(type-name){ initializer-list }` with the type name already parsed
+    fn compound_literal(&mut self, start: Span, type_name: TypeName) -> Parsed<Expression> {
+        if !self.lang().at_least(Standard::C99) {
+            let message = "ISO C90 forbids compound literals";
+            self.extension_at(self.position(), "pedantic", message);
+        }
+        let initializers = self.braced_initializer_list()?;
+        Ok(Expression {
+            kind: ExpressionKind::CompoundLiteral {
+                type_name: Box::new(type_name),
+                initializers,
+            },
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.5.2
+    fn postfix_expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let primary = self.primary_expression()?;
+        self.postfix_operators(start, primary)
+    }
+
+    /// any number of subscripts, calls, member accesses and postfix increments and
+    /// decrements applied to `operand`, which started at `start`
+    fn postfix_operators(&mut self, start: Span, mut operand: Expression) -> Parsed<Expression> {
+        loop {
+            let kind = match self.peek() {
+                Some(Token::LSquare) => {
+                    self.next();
+                    let index = self.expression()?;
+                    self.expect(&Token::RSquare)?;
+                    ExpressionKind::Index {
+                        array: Box::new(operand),
+                        index: Box::new(index),
+                    }
+                }
+                Some(Token::LParen) => {
+                    self.next();
+                    let arguments = self.arguments()?;
+                    ExpressionKind::Call {
+                        function: Box::new(operand),
+                        arguments,
+                    }
+                }
+                Some(Token::Dot) => {
+                    self.next();
+                    ExpressionKind::Member {
+                        object: Box::new(operand),
+                        member: self.identifier()?,
+                    }
+                }
+                Some(Token::DashGTh) => {
+                    self.next();
+                    ExpressionKind::PointerMember {
+                        pointer: Box::new(operand),
+                        member: self.identifier()?,
+                    }
+                }
+                Some(token) => match UnaryOperator::postfix_from_token(token) {
+                    Some(operator) => {
+                        self.next();
+                        ExpressionKind::Unary {
+                            operator,
+                            operand: Box::new(operand),
+                        }
+                    }
+                    None => return Ok(operand),
+                },
+                None => return Ok(operand),
+            };
+            operand = Expression {
+                kind,
+                span: self.since(start),
+            };
+        }
+    }
+
+    /// the arguments of a call, after its `(` and up to and including its `)`
+    fn arguments(&mut self) -> Parsed<Vec<Expression>> {
+        let mut arguments = vec![];
+        if self.eat(&Token::RParen) {
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.assignment_expression()?);
+            if self.eat(&Token::RParen) {
+                return Ok(arguments);
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+
+    /// C11 6.5.1
+    fn primary_expression(&mut self) -> Parsed<Expression> {
+        let start = self.peek_span();
+        let kind = match self.peek() {
+            Some(Token::Identifier(_)) => ExpressionKind::Identifier(self.identifier()?),
+            Some(Token::LParen) => {
+                self.next();
+                let expression = self.expression()?;
+                self.expect(&Token::RParen)?;
+                // the parentheses are part of the expression as far as spans go
+                return Ok(Expression {
+                    kind: expression.kind,
+                    span: self.since(start),
+                });
+            }
+            Some(Token::Kw_Generic) => {
+                self.next();
+                self.generic_selection()?
+            }
+            Some(token) => {
+                if let Some(constant) = Constant::from_token(token) {
+                    self.next();
+                    ExpressionKind::Constant(constant)
+                } else if let Some(literal) = StringLiteral::from_token(token) {
+                    self.next();
+                    ExpressionKind::StringLiteral(literal)
+                } else {
+                    return Err(self.expected("expression"));
+                }
+            }
+            None => return Err(self.expected("expression")),
+        };
+        Ok(Expression {
+            kind,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.5.1.1, after the `_Generic`
+    fn generic_selection(&mut self) -> Parsed<ExpressionKind> {
+        self.expect(&Token::LParen)?;
+        let controlling = self.assignment_expression()?;
+        let mut associations = vec![];
+        while self.eat(&Token::Comma) {
+            let start = self.peek_span();
+            let type_name = match self.eat(&Token::KwDefault) {
+                true => None,
+                false => Some(self.type_name()?),
+            };
+            self.expect(&Token::Colon)?;
+            let expression = self.assignment_expression()?;
+            associations.push(GenericAssociation {
+                type_name,
+                expression,
+                span: self.since(start),
+            });
+        }
+        if associations.is_empty() {
+            return Err(self.expected("','"));
+        }
+        self.expect(&Token::RParen)?;
+        Ok(ExpressionKind::Generic {
+            controlling: Box::new(controlling),
+            associations,
+        })
+    }
+
+    /// a string literal on its own, as in a `_Static_assert`
+    pub fn string_literal(&mut self) -> Parsed<Expression> {
+        let span = self.peek_span();
+        match self.peek().and_then(StringLiteral::from_token) {
+            Some(literal) => {
+                self.next();
+                Ok(Expression {
+                    kind: ExpressionKind::StringLiteral(literal),
+                    span,
+                })
+            }
+            None => Err(self.expected("string literal")),
+        }
+    }
+
+    pub fn identifier(&mut self) -> Parsed<Identifier> {
+        match self.peek() {
+            Some(&Token::Identifier(symbol)) => {
+                let span = self.peek_span();
+                self.next();
+                Ok(Identifier { symbol, span })
+            }
+            _ => Err(self.expected("identifier")),
+        }
+    }
+}
@@ -1,17 +1,36 @@
-use lexer::{self, LocatedToken};
+use diagnostics::Diagnostics;
+use lang::LangOptions;
+use lexer::{LocatedToken, SourceMap};
 
-pub type AbstractSyntaxTree = ();
-
-pub fn parse(_tokens: &[LocatedToken]) -> Result<AbstractSyntaxTree, ()> {
-    todo!()
-}
+pub mod ast;
+mod declaration;
+mod error;
+mod expression;
+mod state;
+mod statement;
 
 #[cfg(test)]
-mod tests {
-    use super::*;
+mod tests;
+
+pub use error::Error;
+
+use state::Parser;
+
+pub type AbstractSyntaxTree = ast::TranslationUnit;
 
-    #[test]
-    fn empty_test() {
-        todo!()
+/// the translation unit made of `tokens`, with `lang` deciding which constructs the
+/// standard allows. Syntax errors are reported to `diagnostics`, pointing into the text
+/// `source_map` is of, and parsing carries on after each of them
+pub fn parse(
+    tokens: &[LocatedToken],
+    source_map: &SourceMap,
+    lang: &LangOptions,
+    diagnostics: &Diagnostics,
+) -> Result<AbstractSyntaxTree, Error> {
+    let already_reported = diagnostics.diagnostics().len();
+    let unit = Parser::new(tokens, source_map, lang, diagnostics).translation_unit();
+    match Error::from_reported(&diagnostics.diagnostics()[already_reported..]) {
+        Some(e) => Err(e),
+        None => Ok(unit),
     }
 }
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use diagnostics::{Diagnostic, Diagnostics, Severity};
+use lang::LangOptions;
+use lexer::{LocatedToken, SourceMap, Span, Symbol, Token};
+
+use crate::ast::Identifier;
+
+/// a syntax error has been reported to the diagnostics, the caller should give up on what
+/// it was parsing and let whoever can recover do so
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Reported;
+
+pub(crate) type Parsed<T> = Result<T, Reported>;
+
+/// where we are in the tokens of the translation unit being parsed, and which of the
+/// ordinary identifiers in scope there are typedef names. `T * x;` declares `x` if `T` is
+/// a typedef name and multiplies otherwise, and the lexer can't tell which
+pub(crate) struct Parser<'a> {
+    tokens: &'a [LocatedToken],
+    position: usize,
+    source_map: &'a SourceMap<'a>,
+    lang: &'a LangOptions,
+    diagnostics: &'a Diagnostics,
+    /// innermost last, each maps an identifier declared in that scope to whether it was
+    /// declared as a typedef name
+    scopes: Vec<HashMap<Symbol, bool>>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(
+        tokens: &'a [LocatedToken],
+        source_map: &'a SourceMap<'a>,
+        lang: &'a LangOptions,
+        diagnostics: &'a Diagnostics,
+    ) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+            source_map,
+            lang,
+            diagnostics,
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    pub fn lang(&self) -> &LangOptions {
+        self.lang
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// once `-fmax-errors` has been reached there's no point carrying on
+    pub fn stopped(&self) -> bool {
+        self.diagnostics.limit_reached()
+    }
+
+    pub fn peek(&self) -> Option<&'a Token> {
+        self.peek_nth(0)
+    }
+
+    /// the token `n` after the next one
+    pub fn peek_nth(&self, n: usize) -> Option<&'a Token> {
+        self.tokens.get(self.position + n).map(LocatedToken::token)
+    }
+
+    pub fn peek_is(&self, token: &Token) -> bool {
+        self.peek() == Some(token)
+    }
+
+    /// the span of the next token, or an empty one just past the last at the end of input
+    pub fn peek_span(&self) -> Span {
+        match self.tokens.get(self.position) {
+            Some(token) => token.span(),
+            None => {
+                let end = self.tokens.last().map_or(0, |token| token.span().end());
+                Span::new(end, end)
+            }
+        }
+    }
+
+    pub fn next(&mut self) -> Option<&'a LocatedToken> {
+        let token = self.tokens.get(self.position)?;
+        self.position += 1;
+        Some(token)
+    }
+
+    /// take the next token if it's `token`
+    pub fn eat(&mut self, token: &Token) -> bool {
+        if self.peek_is(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// take the next token, which has to be `token`
+    pub fn expect(&mut self, token: &Token) -> Parsed<Span> {
+        let span = self.peek_span();
+        match self.eat(token) {
+            true => Ok(span),
+            false => Err(self.expected(&format!("'{token}'"))),
+        }
+    }
+
+    /// from `start` to the end of the last token taken, the span of whatever was parsed
+    /// since `start` was the `peek_span`
+    pub fn since(&self, start: Span) -> Span {
+        match self
+            .position
+            .checked_sub(1)
+            .and_then(|n| self.tokens.get(n))
+        {
+            Some(previous) if previous.span().end() >= start.start() => start.to(previous.span()),
+            _ => Span::new(start.start(), start.start()),
+        }
+    }
+
+    /// report that we wanted `what` rather than the next token
+    pub fn expected(&self, what: &str) -> Reported {
+        // the lexer has already reported whatever it couldn't make a token of
+        if let Some(Token::Unknown(_)) = self.peek() {
+            return Reported;
+        }
+        let message = match self.peek() {
+            Some(token) => format!("expected {what} before '{token}' token"),
+            None => format!("expected {what} at end of input"),
+        };
+        self.error_at(self.position, "expected", &message)
+    }
+
+    /// report an error about the token at `position`
+    pub fn error_at(&self, position: usize, code: &'static str, message: &str) -> Reported {
+        self.report_at(position, Diagnostic::error(code, message));
+        Reported
+    }
+
+    /// something the standard doesn't have which we accept anyway, only reported if
+    /// `-pedantic` asked for it. As in the lexer, `-pedantic-errors` only warns about
+    /// system headers
+    pub fn extension_at(&self, position: usize, code: &'static str, message: &str) {
+        let Some(severity) = self.lang.extension_severity() else {
+            return;
+        };
+        let severity = match self.in_system_header(position) {
+            true => severity.min(Severity::Warning),
+            false => severity,
+        };
+        self.report_at(position, Diagnostic::new(severity, code, message));
+    }
+
+    fn in_system_header(&self, position: usize) -> bool {
+        let span = self.span_at(position);
+        self.source_map.location(span).system_header().is_system()
+    }
+
+    fn span_at(&self, position: usize) -> Span {
+        match self.tokens.get(position) {
+            Some(token) => token.span(),
+            None => self.peek_span(),
+        }
+    }
+
+    fn report_at(&self, position: usize, diagnostic: Diagnostic) {
+        let span = self.span_at(position);
+        let diagnostic = diagnostic.with_span(self.source_map.diagnostic_span(span));
+        let diagnostic = match self.tokens.get(position).and_then(LocatedToken::expansion) {
+            Some(expansion) => diagnostic.with_expansion(expansion),
+            None => diagnostic,
+        };
+        let diagnostic = match self.in_system_header(position) {
+            true => diagnostic.in_system_header(),
+            false => diagnostic,
+        };
+        self.diagnostics.report(diagnostic);
+    }
+
+    /// run `parse` in a scope of its own, a block or a function prototype, whose
+    /// declarations are forgotten once it's done
+    pub fn scoped<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        self.scopes.push(HashMap::new());
+        let parsed = parse(self);
+        self.scopes.pop();
+        parsed
+    }
+
+    /// `name` has been declared in the innermost scope, hiding any declaration of it in
+    /// the scopes around
+    pub fn declare(&mut self, name: Identifier, is_typedef: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.symbol, is_typedef);
+        }
+    }
+
+    /// whether `symbol` names a type here, as the declaration of it in the innermost scope
+    /// which has one says
+    pub fn is_typedef_name(&self, symbol: Symbol) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&symbol))
+            .is_some_and(|&is_typedef| is_typedef)
+    }
+
+    /// the position of the next token, to point back at it once we know what was wrong
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// skip ahead to where parsing can pick up again after an error: past the next `;` or
+    /// just before the `}` closing the block we're in. Brackets opened along the way are
+    /// skipped whole, so an error in a nested block doesn't end the one around it
+    pub fn recover(&mut self) {
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            match token {
+                Token::LParen | Token::LSquare | Token::LBrace => depth += 1,
+                Token::RParen | Token::RSquare => depth = depth.saturating_sub(1),
+                Token::RBrace if depth == 0 => return,
+                Token::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.position += 1;
+                        return;
+                    }
+                }
+                Token::Semi if depth == 0 => {
+                    self.position += 1;
+                    return;
+                }
+                _ => (),
+            }
+            self.position += 1;
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use lang::Standard;
+use lexer::Token;
+
+use crate::ast::{BlockItem, CompoundStatement, Expression, ForInit, Statement, StatementKind};
+use crate::state::{Parsed, Parser, Reported};
+
+impl<'a> Parser<'a> {
+    /// C11 6.8.2, a syntax error in one of the block items is reported and we carry on
+    /// with the next, so only a missing `}` gives up on the block
+    pub fn compound_statement(&mut self) -> Parsed<CompoundStatement> {
+        self.scoped(Self::block)
+    }
+
+    fn block(&mut self) -> Parsed<CompoundStatement> {
+        let start = self.expect(&Token::LBrace)?;
+        let mut items: Vec<BlockItem> = vec![];
+        while !self.eat(&Token::RBrace) {
+            if self.at_end() {
+                return Err(self.expected("'}'"));
+            }
+            if self.stopped() {
+                return Err(Reported);
+            }
+            let after_statement = items
+                .iter()
+                .any(|item| matches!(item, BlockItem::Statement(_)));
+            match self.block_item(after_statement) {
+                Ok(item) => items.push(item),
+                Err(_) => self.recover(),
+            }
+        }
+        Ok(CompoundStatement {
+            items,
+            span: self.since(start),
+        })
+    }
+
+    /// C11 6.8.2, a declaration or a statement. C90 wanted all of a block's declarations
+    /// before any of its statements
+    fn block_item(&mut self, after_statement: bool) -> Parsed<BlockItem> {
+        if self.starts_label() || !self.starts_declaration() {
+            return Ok(BlockItem::Statement(self.statement()?));
+        }
+        if after_statement && !self.lang().at_least(Standard::C99) {
+            let message = "ISO C90 forbids mixed declarations and code";
+            self.extension_at(self.position(), "pedantic", message);
+        }
+        Ok(BlockItem::Declaration(self.declaration()?))
+    }
+
+    /// C11 6.8
+    pub fn statement(&mut self) -> Parsed<Statement> {
+        let start = self.peek_span();
+        let kind = match self.peek() {
+            Some(Token::Identifier(_)) if self.starts_label() => {
+                let label = self.identifier()?;
+                self.next();
+                StatementKind::Labeled {
+                    label,
+                    statement: Box::new(self.statement()?),
+                }
+            }
+            Some(Token::KwCase) => {
+                self.next();
+                let value = self.constant_expression()?;
+                self.expect(&Token::Colon)?;
+                StatementKind::Case {
+                    value: Box::new(value),
+                    statement: Box::new(self.statement()?),
+                }
+            }
+            Some(Token::KwDefault) => {
+                self.next();
+                self.expect(&Token::Colon)?;
+                StatementKind::Default(Box::new(self.statement()?))
+            }
+            Some(Token::LBrace) => StatementKind::Compound(self.compound_statement()?),
+            Some(Token::KwIf) => {
+                self.next();
+                let condition = self.parenthesised_expression()?;
+                let then = self.statement()?;
+                let otherwise = match self.eat(&Token::KwElse) {
+                    true => Some(Box::new(self.statement()?)),
+                    false => None,
+                };
+                StatementKind::If {
+                    condition: Box::new(condition),
+                    then: Box::new(then),
+                    otherwise,
+                }
+            }
+            Some(Token::KwSwitch) => {
+                self.next();
+                let condition = self.parenthesised_expression()?;
+                StatementKind::Switch {
+                    condition: Box::new(condition),
+                    body: Box::new(self.statement()?),
+                }
+            }
+            Some(Token::KwWhile) => {
+                self.next();
+                let condition = self.parenthesised_expression()?;
+                StatementKind::While {
+                    condition: Box::new(condition),
+                    body: Box::new(self.statement()?),
+                }
+            }
+            Some(Token::KwDo) => {
+                self.next();
+                let body = self.statement()?;
+                self.expect(&Token::KwWhile)?;
+                let condition = self.parenthesised_expression()?;
+                self.expect(&Token::Semi)?;
+                StatementKind::DoWhile {
+                    body: Box::new(body),
+                    condition: Box::new(condition),
+                }
+            }
+            Some(Token::KwFor) => {
+                self.next();
+                self.for_statement()?
+            }
+            Some(Token::KwGoto) => {
+                self.next();
+                let label = self.identifier()?;
+                self.expect(&Token::Semi)?;
+                StatementKind::Goto(label)
+            }
+            Some(Token::KwContinue) => {
+                self.next();
+                self.expect(&Token::Semi)?;
+                StatementKind::Continue
+            }
+            Some(Token::KwBreak) => {
+                self.next();
+                self.expect(&Token::Semi)?;
+                StatementKind::Break
+            }
+            Some(Token::KwReturn) => {
+                self.next();
+                StatementKind::Return(self.optional_expression(&Token::Semi)?)
+            }
+            _ => StatementKind::Expression(self.optional_expression(&Token::Semi)?),
+        };
+        Ok(Statement {
+            kind,
+            span: self.since(start),
+        })
+    }
+
+    /// `label:`, which a typedef name may be used as
+    fn starts_label(&self) -> bool {
+        matches!(self.peek(), Some(Token::Identifier(_))) && self.peek_nth(1) == Some(&Token::Colon)
+    }
+
+    /// C11 6.8.5.3, after the `for`. A declaration is in scope until the end of the loop
+    fn for_statement(&mut self) -> Parsed<StatementKind> {
+        self.scoped(Self::for_clauses)
+    }
+
+    fn for_clauses(&mut self) -> Parsed<StatementKind> {
+        self.expect(&Token::LParen)?;
+        let init = match self.starts_declaration() {
+            true => {
+                if !self.lang().at_least(Standard::C99) {
+                    let message = "ISO C90 does not support 'for' loop initial declarations";
+                    self.extension_at(self.position(), "pedantic", message);
+                }
+                ForInit::Declaration(self.declaration()?)
+            }
+            false => ForInit::Expression(self.optional_expression(&Token::Semi)?),
+        };
+        let condition = self.optional_expression(&Token::Semi)?;
+        let step = self.optional_expression(&Token::RParen)?;
+        Ok(StatementKind::For {
+            init,
+            condition,
+            step,
+            body: Box::new(self.statement()?),
+        })
+    }
+
+    /// `( expression )` as in the condition of an `if`
+    fn parenthesised_expression(&mut self) -> Parsed<Expression> {
+        self.expect(&Token::LParen)?;
+        let expression = self.expression()?;
+        self.expect(&Token::RParen)?;
+        Ok(expression)
+    }
+
+    /// an expression unless `end` comes first, and then `end` itself
+    fn optional_expression(&mut self, end: &Token) -> Parsed<Option<Box<Expression>>> {
+        if self.eat(end) {
+            return Ok(None);
+        }
+        let expression = self.expression()?;
+        self.expect(end)?;
+        Ok(Some(Box::new(expression)))
+    }
+}
@@ -0,0 +1,128 @@
+mod declaration;
+mod expression;
+mod statement;
+
+use diagnostics::{Diagnostic, Diagnostics};
+use lang::{LangOptions, Pedantic, Standard};
+
+use crate::ast::{BlockItem, Expression, ExpressionKind, ExternalDeclaration, StatementKind};
+use crate::{parse, AbstractSyntaxTree, Error};
+
+/// the translation unit of `source` under `lang`, along with everything reported about it
+fn parse_with(
+    source: &str,
+    lang: &LangOptions,
+) -> (Result<AbstractSyntaxTree, Error>, Vec<Diagnostic>) {
+    let diagnostics = Diagnostics::new();
+    let result = {
+        let lexed = lexer::lex(source, lang, &diagnostics).expect("test source should lex");
+        parse(lexed.tokens(), lexed.source_map(), lang, &diagnostics)
+    };
+    (result, diagnostics.diagnostics())
+}
+
+/// the translation unit of `source`, which mustn't have any syntax errors
+fn parse_ok(source: &str) -> AbstractSyntaxTree {
+    let (result, diagnostics) = parse_with(source, &LangOptions::default());
+    assert_eq!(Vec::<Diagnostic>::new(), diagnostics, "for {source}");
+    result.expect("test source should parse")
+}
+
+/// the codes of what was reported about `source`
+fn codes(source: &str, lang: &LangOptions) -> Vec<&'static str> {
+    let (_, diagnostics) = parse_with(source, lang);
+    diagnostics.iter().map(|d| d.code()).collect()
+}
+
+fn pedantic(standard: Standard) -> LangOptions {
+    LangOptions {
+        pedantic: Pedantic::Warn,
+        ..LangOptions::new(standard)
+    }
+}
+
+/// the expression statement `source` is on its own, inside a function body
+fn expression(source: &str) -> Expression {
+    let unit = parse_ok(&format!("void f(void) {{ {source}; }}"));
+    let Some(ExternalDeclaration::FunctionDefinition(definition)) = unit.declarations.first()
+    else {
+        panic!("expected a function definition for {source}");
+    };
+    match definition.body.items.as_slice() {
+        [BlockItem::Statement(statement)] => match &statement.kind {
+            StatementKind::Expression(Some(expression)) => (**expression).clone(),
+            kind => panic!("expected an expression statement for {source}, not {kind:?}"),
+        },
+        items => panic!("expected a single statement for {source}, not {items:?}"),
+    }
+}
+
+/// `expression` written out with its structure made explicit, as a lisp would
+fn sexp(expression: &Expression) -> String {
+    let list = |head: &str, operands: &[&Expression]| {
+        let mut items = vec![head.to_string()];
+        items.extend(operands.iter().map(|e| sexp(e)));
+        format!("({})", items.join(" "))
+    };
+    match &expression.kind {
+        ExpressionKind::Identifier(identifier) => identifier.as_str().to_string(),
+        ExpressionKind::Constant(constant) => format!("{constant:?}"),
+        ExpressionKind::StringLiteral(_) => "string".to_string(),
+        ExpressionKind::Generic {
+            controlling,
+            associations,
+        } => {
+            let associations: Vec<String> = associations
+                .iter()
+                .map(|association| match association.type_name {
+                    Some(_) => format!("type:{}", sexp(&association.expression)),
+                    None => format!("default:{}", sexp(&association.expression)),
+                })
+                .collect();
+            format!(
+                "(_Generic {} {})",
+                sexp(controlling),
+                associations.join(" ")
+            )
+        }
+        ExpressionKind::Index { array, index } => list("index", &[array, index]),
+        ExpressionKind::Call {
+            function,
+            arguments,
+        } => {
+            let mut operands = vec![&**function];
+            operands.extend(arguments);
+            list("call", &operands)
+        }
+        ExpressionKind::Member { object, member } => {
+            format!("(. {} {})", sexp(object), member.as_str())
+        }
+        ExpressionKind::PointerMember { pointer, member } => {
+            format!("(-> {} {})", sexp(pointer), member.as_str())
+        }
+        ExpressionKind::CompoundLiteral { initializers, .. } => {
+            format!("(literal type {})", initializers.len())
+        }
+        ExpressionKind::Unary { operator, operand } => list(&format!("{operator:?}"), &[operand]),
+        ExpressionKind::SizeofExpression(operand) => list("sizeof", &[operand]),
+        ExpressionKind::SizeofType(_) => "(sizeof type)".to_string(),
+        ExpressionKind::AlignofType(_) => "(_Alignof type)".to_string(),
+        ExpressionKind::Cast { operand, .. } => list("cast type", &[operand]),
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => list(&format!("{operator:?}"), &[left, right]),
+        ExpressionKind::Conditional {
+            condition,
+            then,
+            otherwise,
+        } => list("?", &[condition, then, otherwise]),
+        ExpressionKind::Assignment {
+            operator,
+            target,
+            value,
+        } => list(&format!("{operator:?}="), &[target, value]),
+        ExpressionKind::Comma { left, right } => list(",", &[left, right]),
+    }
+}
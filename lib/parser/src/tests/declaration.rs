@@ -0,0 +1,674 @@
+use super::*;
+use crate::ast::{
+    AlignmentSpecifier, ArraySize, Declaration, DeclarationKind, DeclarationSpecifierKind,
+    DeclarationSpecifiers, Declarator, DeclaratorKind, DesignatorKind, FunctionParameters,
+    InitDeclarator, InitializerKind, StorageClass, StructDeclarationKind, TypeQualifier,
+    TypeSpecifier, TypeofOperand,
+};
+
+/// the last declaration of `source`, any before it are there to declare typedef names
+fn declaration(source: &str) -> Declaration {
+    let unit = parse_ok(source);
+    match unit.declarations.last() {
+        Some(ExternalDeclaration::Declaration(declaration)) => declaration.clone(),
+        declaration => panic!("expected a declaration at the end of {source}, not {declaration:?}"),
+    }
+}
+
+fn declarators(source: &str) -> Vec<InitDeclarator> {
+    match declaration(source).kind {
+        DeclarationKind::Declaration { declarators, .. } => declarators,
+        kind => panic!("expected declarators for {source}, not {kind:?}"),
+    }
+}
+
+/// `declarator` inside out, as it would be read aloud
+fn describe(declarator: &Declarator) -> String {
+    match &declarator.kind {
+        DeclaratorKind::Identifier(identifier) => identifier.as_str().to_string(),
+        DeclaratorKind::Abstract => "_".to_string(),
+        DeclaratorKind::Pointer { declarator, .. } => {
+            format!("{} pointer to", describe(declarator))
+        }
+        DeclaratorKind::Array { declarator, .. } => format!("{} array of", describe(declarator)),
+        DeclaratorKind::Function {
+            declarator,
+            parameters,
+        } => {
+            let count = match parameters {
+                FunctionParameters::Prototype { parameters, .. } => parameters.len(),
+                FunctionParameters::Identifiers(identifiers) => identifiers.len(),
+            };
+            format!("{} function of {count} returning", describe(declarator))
+        }
+    }
+}
+
+/// the specifiers of the last declaration of `source`
+fn specifiers(source: &str) -> DeclarationSpecifiers {
+    match declaration(source).kind {
+        DeclarationKind::Declaration { specifiers, .. } => specifiers,
+        kind => panic!("expected specifiers for {source}, not {kind:?}"),
+    }
+}
+
+/// the one type specifier among `specifiers`
+fn type_specifier(specifiers: &DeclarationSpecifiers) -> TypeSpecifier {
+    match specifiers.type_specifiers().collect::<Vec<_>>().as_slice() {
+        [specifier] => (*specifier).clone(),
+        specifiers => panic!("expected a single type specifier, not {specifiers:?}"),
+    }
+}
+
+/// whether each item of the body of the function `source` ends with is a declaration
+fn block_declarations(source: &str) -> Vec<bool> {
+    let unit = parse_ok(source);
+    let Some(ExternalDeclaration::FunctionDefinition(definition)) = unit.declarations.last() else {
+        panic!("expected a function definition at the end of {source}");
+    };
+    definition
+        .body
+        .items
+        .iter()
+        .map(|item| matches!(item, BlockItem::Declaration(_)))
+        .collect()
+}
+
+fn check(source: &str, expected: &str) {
+    let declarators = declarators(source);
+    assert_eq!(
+        expected,
+        describe(&declarators[0].declarator),
+        "for {source}"
+    );
+}
+
+#[test]
+fn test_declarators() {
+    check("int x;", "x");
+    check("int *x;", "x pointer to");
+    check("int **x;", "x pointer to pointer to");
+    check("int x[3];", "x array of");
+    check("int *x[3];", "x array of pointer to");
+    check("int (*x)[3];", "x pointer to array of");
+    check("int x[2][3];", "x array of array of");
+    check("int f(void);", "f function of 1 returning");
+    check("int *f(int, char);", "f function of 2 returning pointer to");
+    check("int (*f)(int);", "f pointer to function of 1 returning");
+    check("int f();", "f function of 0 returning");
+    check(
+        "void (*signal(int, void (*)(int)))(int);",
+        "signal function of 2 returning pointer to function of 1 returning",
+    );
+    check("int ((x));", "x");
+}
+
+#[test]
+fn test_specifiers() {
+    let DeclarationKind::Declaration { specifiers, .. } =
+        declaration("static const unsigned long int volatile_ = 1;").kind
+    else {
+        panic!("expected a declaration");
+    };
+    assert_eq!(
+        vec![StorageClass::Static],
+        specifiers.storage_classes().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![TypeQualifier::Const],
+        specifiers.qualifiers().collect::<Vec<_>>()
+    );
+    let types: Vec<&TypeSpecifier> = specifiers.type_specifiers().collect();
+    assert_eq!(
+        vec![
+            &TypeSpecifier::Unsigned,
+            &TypeSpecifier::Long,
+            &TypeSpecifier::Int
+        ],
+        types
+    );
+    assert!(!specifiers.is_typedef());
+
+    let DeclarationKind::Declaration { specifiers, .. } =
+        declaration("typedef struct s *p, t;").kind
+    else {
+        panic!("expected a declaration");
+    };
+    assert!(specifiers.is_typedef());
+    assert!(matches!(
+        specifiers.type_specifiers().next(),
+        Some(TypeSpecifier::StructOrUnion(_))
+    ));
+    assert!(matches!(
+        declaration("enum e;").kind,
+        DeclarationKind::Declaration { ref declarators, .. } if declarators.is_empty()
+    ));
+}
+
+#[test]
+fn test_arrays() {
+    let sizes = |source: &str| match declarators(source)[0].declarator.kind.clone() {
+        DeclaratorKind::Array {
+            size,
+            is_static,
+            qualifiers,
+            ..
+        } => (size, is_static, qualifiers.len()),
+        kind => panic!("expected an array, not {kind:?}"),
+    };
+    assert!(matches!(sizes("int a[];"), (ArraySize::Unknown, false, 0)));
+    assert!(matches!(
+        sizes("int a[4];"),
+        (ArraySize::Expression(_), false, 0)
+    ));
+    assert!(matches!(
+        sizes("int a[*];"),
+        (ArraySize::Variable, false, 0)
+    ));
+
+    // qualifiers and static only in a parameter's outermost array
+    let DeclaratorKind::Function { parameters, .. } =
+        &declarators("void f(int a[static const 4], int b[restrict]);")[0]
+            .declarator
+            .kind
+    else {
+        panic!("expected a function");
+    };
+    let FunctionParameters::Prototype { parameters, .. } = parameters else {
+        panic!("expected a prototype");
+    };
+    assert!(matches!(
+        &parameters[0].declarator.kind,
+        DeclaratorKind::Array { is_static: true, qualifiers, size: ArraySize::Expression(_), .. }
+            if qualifiers.len() == 1
+    ));
+    assert!(matches!(
+        &parameters[1].declarator.kind,
+        DeclaratorKind::Array { is_static: false, qualifiers, size: ArraySize::Unknown, .. }
+            if qualifiers.len() == 1
+    ));
+}
+
+#[test]
+fn test_abstract_parameters() {
+    let DeclaratorKind::Function { parameters, .. } =
+        &declarators("int f(int, char *, int (*)(void), int [3]);")[0]
+            .declarator
+            .kind
+    else {
+        panic!("expected a function");
+    };
+    let FunctionParameters::Prototype { parameters, .. } = parameters else {
+        panic!("expected a prototype");
+    };
+    let described: Vec<String> = parameters.iter().map(|p| describe(&p.declarator)).collect();
+    assert_eq!(
+        vec![
+            "_",
+            "_ pointer to",
+            "_ pointer to function of 1 returning",
+            "_ array of"
+        ],
+        described
+    );
+    assert!(parameters.iter().all(|p| p.declarator.is_abstract()));
+}
+
+#[test]
+fn test_initializers() {
+    let initialized = declarators("int a = 1, b, c[] = {1, [2] = 3, }, d = {{0}};");
+    assert_eq!(4, initialized.len());
+    assert!(matches!(
+        initialized[0].initializer.as_ref().map(|i| &i.kind),
+        Some(InitializerKind::Expression(_))
+    ));
+    assert!(initialized[1].initializer.is_none());
+    let Some(InitializerKind::List(items)) = initialized[2].initializer.as_ref().map(|i| &i.kind)
+    else {
+        panic!("expected an initializer list");
+    };
+    assert_eq!(2, items.len());
+    assert!(items[0].designators.is_empty());
+    assert!(matches!(
+        items[1].designators[0].kind,
+        DesignatorKind::Index(_)
+    ));
+    let Some(InitializerKind::List(items)) = initialized[3].initializer.as_ref().map(|i| &i.kind)
+    else {
+        panic!("expected an initializer list");
+    };
+    assert!(matches!(
+        items[0].initializer.kind,
+        InitializerKind::List(_)
+    ));
+
+    let initialized = declarators("struct s x = {.a.b[1] = 2, .c = {3}};");
+    let Some(InitializerKind::List(items)) = initialized[0].initializer.as_ref().map(|i| &i.kind)
+    else {
+        panic!("expected an initializer list");
+    };
+    let designators: Vec<bool> = items[0]
+        .designators
+        .iter()
+        .map(|d| matches!(d.kind, DesignatorKind::Member(_)))
+        .collect();
+    assert_eq!(vec![true, true, false], designators);
+}
+
+#[test]
+fn test_function_definitions() {
+    let unit = parse_ok("int f(void); int g(int a) { return a; } int x, h(void);");
+    assert_eq!(3, unit.declarations.len());
+    assert!(matches!(
+        unit.declarations[0],
+        ExternalDeclaration::Declaration(_)
+    ));
+    let ExternalDeclaration::FunctionDefinition(definition) = &unit.declarations[1] else {
+        panic!("expected a function definition");
+    };
+    assert_eq!("g", definition.declarator.identifier().unwrap().as_str());
+    assert_eq!(1, definition.body.items.len());
+    assert!(matches!(
+        unit.declarations[2],
+        ExternalDeclaration::Declaration(_)
+    ));
+}
+
+#[test]
+fn test_static_assert() {
+    let DeclarationKind::StaticAssert(assertion) =
+        declaration("_Static_assert(sizeof(int) == 4, \"int\");").kind
+    else {
+        panic!("expected a static assertion");
+    };
+    assert!(assertion.message.is_some());
+
+    let lang = pedantic(Standard::C11);
+    let (_, diagnostics) = parse_with("_Static_assert(1);", &lang);
+    assert_eq!(
+        "ISO C11 does not support omitting the string in '_Static_assert'",
+        diagnostics[0].message()
+    );
+    assert!(codes("_Static_assert(1);", &pedantic(Standard::C23)).is_empty());
+    assert!(codes("static_assert(1);", &pedantic(Standard::C23)).is_empty());
+    // before C11 the lexer reports the keyword and the parser has nothing to add
+    let (_, diagnostics) = parse_with("_Static_assert(1);", &pedantic(Standard::C99));
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message()).collect();
+    assert_eq!(vec!["ISO C99 does not support '_Static_assert'"], messages);
+}
+
+#[test]
+fn test_initializer_extensions() {
+    let designated = "int a[2] = {[1] = 1};";
+    assert_eq!(
+        vec!["pedantic"],
+        codes(designated, &pedantic(Standard::C89))
+    );
+    assert!(codes(designated, &pedantic(Standard::C99)).is_empty());
+
+    let empty = "int a[2] = {};";
+    assert_eq!(vec!["pedantic"], codes(empty, &pedantic(Standard::C17)));
+    assert!(codes(empty, &pedantic(Standard::C23)).is_empty());
+    assert!(codes(empty, &LangOptions::new(Standard::C17)).is_empty());
+}
+
+#[test]
+fn test_errors() {
+    let lang = LangOptions::default();
+    assert_eq!(vec!["expected"], codes("int;x", &lang));
+    assert_eq!(vec!["expected"], codes("int x y; int z;", &lang));
+    assert_eq!(vec!["expected"], codes("int (*)(void);", &lang));
+    assert_eq!(vec!["expected"], codes("int a[3;", &lang));
+    assert_eq!(vec!["expected"], codes("int x = {1, 2;", &lang));
+
+    let stray = "int a;; void f(void) {};";
+    assert_eq!(
+        vec!["pedantic", "pedantic"],
+        codes(stray, &pedantic(Standard::C11))
+    );
+    assert!(codes(stray, &lang).is_empty());
+
+    // the next declaration is parsed once the bad one has been skipped
+    let (result, diagnostics) = parse_with("int a b; int c;", &lang);
+    assert_eq!(1, diagnostics.len());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_typedef_names() {
+    let source = "typedef int T; T * x;";
+    assert!(matches!(
+        type_specifier(&specifiers(source)),
+        TypeSpecifier::TypedefName(name) if name.as_str() == "T"
+    ));
+    check(source, "x pointer to");
+
+    // at block scope `T * x;` is a declaration or a multiplication, depending on `T`
+    assert_eq!(
+        vec![true],
+        block_declarations("typedef int T; void f(void) { T * x; }")
+    );
+    assert_eq!(
+        vec![false],
+        block_declarations("int T; void f(void) { T * x; }")
+    );
+    assert_eq!(vec![false], block_declarations("void f(void) { T * x; }"));
+
+    // a typedef name is a type in casts, sizeof and compound literals too
+    let casts = "typedef int T; void f(void) { (T)x; sizeof (T); (T){0}; (T)(x); }";
+    assert_eq!(vec![false, false, false, false], block_declarations(casts));
+
+    // several typedef names in one declaration, the second of them a pointer
+    let pointers = "typedef int T, *P; void f(void) { P p; T t; }";
+    assert_eq!(vec![true, true], block_declarations(pointers));
+
+    // qualifiers may come either side of a typedef name
+    let qualified = specifiers("typedef int T; const T volatile x;");
+    let qualifiers: Vec<TypeQualifier> = qualified.qualifiers().collect();
+    assert_eq!(
+        vec![TypeQualifier::Const, TypeQualifier::Volatile],
+        qualifiers
+    );
+}
+
+#[test]
+fn test_typedef_scopes() {
+    // the declaration of an object in an inner scope hides the typedef name
+    assert_eq!(
+        vec![true, false],
+        block_declarations("typedef int T; void f(void) { int T; T * x; }")
+    );
+    assert_eq!(
+        vec![true, false],
+        block_declarations("typedef int T; void f(void) { long T; T * x; }")
+    );
+    assert_eq!(
+        vec![false],
+        block_declarations("typedef int T; void f(int T) { T * x; }")
+    );
+    assert_eq!(
+        vec![true, false],
+        block_declarations("typedef int T; void f(void) { enum { T }; T * x; }")
+    );
+
+    // and only until the end of its block, or of the loop it was declared in
+    assert_eq!(
+        vec![false, true],
+        block_declarations("typedef int T; void f(void) { { int T; } T * x; }")
+    );
+    assert_eq!(
+        vec![false, true],
+        block_declarations("typedef int T; void f(void) { for (int T; ;) ; T * x; }")
+    );
+    assert_eq!(
+        vec![false, false],
+        block_declarations("void f(void) { { typedef int U; } U * x; }")
+    );
+
+    // a prototype's parameters are only in scope in the prototype
+    let prototype = "typedef int T; void g(int T); void f(void) { T * x; }";
+    assert_eq!(vec![true], block_declarations(prototype));
+
+    // a typedef name can still be used as a label, and as a member's name
+    let label = "typedef int T; void f(void) { T: ; }";
+    assert_eq!(vec![false], block_declarations(label));
+    let member = "typedef int T; struct s { T T; }; void f(void) { T * x; }";
+    assert_eq!(vec![true], block_declarations(member));
+}
+
+#[test]
+fn test_parameters() {
+    let parameters = |source: &str| match declarators(source)[0].declarator.parameters() {
+        Some(parameters) => parameters.clone(),
+        None => panic!("expected a function for {source}"),
+    };
+    assert!(matches!(
+        parameters("int printf(const char *, ...);"),
+        FunctionParameters::Prototype { parameters, variadic: true } if parameters.len() == 1
+    ));
+    assert!(matches!(
+        parameters("int f(int a, char b);"),
+        FunctionParameters::Prototype {
+            variadic: false,
+            ..
+        }
+    ));
+    assert!(matches!(
+        parameters("int f(a, b);"),
+        FunctionParameters::Identifiers(identifiers) if identifiers.len() == 2
+    ));
+    // a typedef name is a parameter's type, not its name
+    let FunctionParameters::Prototype { parameters, .. } =
+        parameters("typedef int T; int f(T, T (x), T (*)(T));")
+    else {
+        panic!("expected a prototype");
+    };
+    let described: Vec<String> = parameters.iter().map(|p| describe(&p.declarator)).collect();
+    assert_eq!(
+        vec!["_", "x", "_ pointer to function of 1 returning"],
+        described
+    );
+
+    let pointer = declarators("int (*f)(int), (*g(int a))(char);");
+    assert!(pointer[0].declarator.parameters().is_none());
+    assert!(matches!(
+        pointer[1].declarator.parameters(),
+        Some(FunctionParameters::Prototype { parameters, .. })
+            if parameters[0].declarator.identifier().unwrap().as_str() == "a"
+    ));
+
+    assert_eq!(
+        vec!["named-argument"],
+        codes("int f(...);", &LangOptions::default())
+    );
+    assert!(codes("int f(...);", &LangOptions::new(Standard::C23)).is_empty());
+}
+
+#[test]
+fn test_kr_definition() {
+    let unit = parse_ok("int f(a, b) int a; char *b; { return a; } int g() { return 0; }");
+    let ExternalDeclaration::FunctionDefinition(definition) = &unit.declarations[0] else {
+        panic!("expected a function definition");
+    };
+    assert_eq!(2, definition.declarations.len());
+    assert!(matches!(
+        definition.declarator.parameters(),
+        Some(FunctionParameters::Identifiers(identifiers)) if identifiers.len() == 2
+    ));
+    let ExternalDeclaration::FunctionDefinition(definition) = &unit.declarations[1] else {
+        panic!("expected a function definition");
+    };
+    assert!(definition.declarations.is_empty());
+
+    // undeclared parameters default to int
+    assert_eq!(1, parse_ok("int f(a) { return a; }").declarations.len());
+    assert_eq!(
+        vec!["expected"],
+        codes("int f(a) int a; int b", &LangOptions::default())
+    );
+}
+
+#[test]
+fn test_struct_members() {
+    let source = "struct s { int a, *b; unsigned c : 3, : 0; struct { int d; }; \
+                  _Static_assert(1, \"\"); union u { long e; } f; } x;";
+    let TypeSpecifier::StructOrUnion(specifier) = type_specifier(&specifiers(source)) else {
+        panic!("expected a struct");
+    };
+    assert_eq!("s", specifier.tag.unwrap().as_str());
+    let members = specifier.members.expect("expected members");
+    assert_eq!(5, members.len());
+    let shapes: Vec<(usize, usize)> = members
+        .iter()
+        .filter_map(|member| match &member.kind {
+            StructDeclarationKind::Member { declarators, .. } => Some((
+                declarators.len(),
+                declarators.iter().filter(|d| d.width.is_some()).count(),
+            )),
+            StructDeclarationKind::StaticAssert(_) => None,
+        })
+        .collect();
+    assert_eq!(vec![(2, 0), (2, 2), (0, 0), (1, 0)], shapes);
+    let StructDeclarationKind::Member { declarators, .. } = &members[1].kind else {
+        panic!("expected a bit-field");
+    };
+    assert!(declarators[1].declarator.is_none());
+
+    let TypeSpecifier::StructOrUnion(specifier) = type_specifier(&specifiers("struct { int a; };"))
+    else {
+        panic!("expected a struct");
+    };
+    assert!(specifier.tag.is_none());
+
+    assert_eq!(
+        vec!["pedantic"],
+        codes("struct s {};", &pedantic(Standard::C11))
+    );
+    let lang = LangOptions::default();
+    assert_eq!(vec!["expected"], codes("struct;", &lang));
+    assert_eq!(
+        vec!["expected"],
+        codes("struct s { static int a; };", &lang)
+    );
+}
+
+#[test]
+fn test_enums() {
+    let TypeSpecifier::Enum(specifier) =
+        type_specifier(&specifiers("enum e { A, B = 2, C = B + 1, } x;"))
+    else {
+        panic!("expected an enum");
+    };
+    let enumerators = specifier.enumerators.expect("expected enumerators");
+    let names: Vec<&str> = enumerators.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(vec!["A", "B", "C"], names);
+    assert!(enumerators[0].value.is_none() && enumerators[2].value.is_some());
+
+    let source = "enum { A, };";
+    assert_eq!(vec!["pedantic"], codes(source, &pedantic(Standard::C89)));
+    assert!(codes(source, &pedantic(Standard::C99)).is_empty());
+    assert_eq!(
+        vec!["expected"],
+        codes("enum e {};", &LangOptions::default())
+    );
+}
+
+#[test]
+fn test_alignas_and_atomic() {
+    let alignments: Vec<DeclarationSpecifierKind> =
+        specifiers("_Alignas(16) _Alignas(double) _Atomic int x;")
+            .specifiers
+            .into_iter()
+            .map(|specifier| specifier.kind)
+            .collect();
+    assert!(matches!(
+        alignments.as_slice(),
+        [
+            DeclarationSpecifierKind::Alignment(AlignmentSpecifier::Expression(_)),
+            DeclarationSpecifierKind::Alignment(AlignmentSpecifier::Type(_)),
+            DeclarationSpecifierKind::Qualifier(TypeQualifier::Atomic),
+            DeclarationSpecifierKind::Type(TypeSpecifier::Int),
+        ]
+    ));
+    assert!(matches!(
+        type_specifier(&specifiers("_Atomic(long) x;")),
+        TypeSpecifier::Atomic(_)
+    ));
+    assert_eq!(
+        vec![true, true],
+        block_declarations("void f(void) { _Alignas(8) char c; _Atomic(int) *p; }")
+    );
+}
+
+#[test]
+fn test_gnu_extensions() {
+    // attributes and asm labels are skipped wherever gcc's headers put them
+    check(
+        "int f(int) __attribute__((noreturn));",
+        "f function of 1 returning",
+    );
+    check(
+        "extern int g(void) __asm__(\"h\") __attribute__((__nothrow__, __leaf__));",
+        "g function of 1 returning",
+    );
+    check("char *__attribute__((aligned(8))) p;", "p pointer to");
+    check(
+        "void f(int x __attribute__((unused)));",
+        "f function of 1 returning",
+    );
+    let packed = "struct __attribute__((packed)) s { int i __attribute__((aligned(8))); } \
+                  __attribute__((aligned)) x;";
+    assert!(matches!(
+        type_specifier(&specifiers(packed)),
+        TypeSpecifier::StructOrUnion(_)
+    ));
+    parse_ok(
+        "__attribute__((visibility(\"default\"))) enum e { A __attribute__((deprecated)) = 1 };",
+    );
+
+    // __extension__ is skipped before a declaration or an expression
+    let int128 = specifiers("__extension__ typedef unsigned __int128 u128;");
+    assert!(int128.is_typedef());
+    assert_eq!(
+        vec![&TypeSpecifier::Unsigned, &TypeSpecifier::Int128],
+        int128.type_specifiers().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![true, false],
+        block_declarations("void f(void) { __extension__ long long x; __extension__ (x); }")
+    );
+    let extension = "struct s { __extension__ union { int a; }; };";
+    assert!(matches!(
+        type_specifier(&specifiers(extension)),
+        TypeSpecifier::StructOrUnion(_)
+    ));
+
+    assert_eq!(
+        vec!["expected"],
+        codes("int x __attribute__((unused);", &LangOptions::default())
+    );
+}
+
+#[test]
+fn test_typeof_and_bit_int() {
+    let lang = LangOptions::new(Standard::C23);
+    let source =
+        "int *p; typeof(int *) q; typeof_unqual(*p) r; __typeof__(p + 1) s; _BitInt(24) b;";
+    let (result, diagnostics) = parse_with(source, &lang);
+    assert!(diagnostics.is_empty());
+    let unit = result.expect("expected the declarations to parse");
+    let types: Vec<TypeSpecifier> = unit.declarations[1..]
+        .iter()
+        .map(|declaration| match declaration {
+            ExternalDeclaration::Declaration(Declaration {
+                kind: DeclarationKind::Declaration { specifiers, .. },
+                ..
+            }) => type_specifier(specifiers),
+            declaration => panic!("expected a declaration, not {declaration:?}"),
+        })
+        .collect();
+    assert!(matches!(
+        types.as_slice(),
+        [
+            TypeSpecifier::Typeof {
+                operand: TypeofOperand::Type(_),
+                unqualified: false,
+            },
+            TypeSpecifier::Typeof {
+                operand: TypeofOperand::Expression(_),
+                unqualified: true,
+            },
+            TypeSpecifier::Typeof {
+                operand: TypeofOperand::Expression(_),
+                unqualified: false,
+            },
+            TypeSpecifier::BitInt(_),
+        ]
+    ));
+
+    // a typeof is a type specifier, so what follows it is declared
+    assert_eq!(
+        vec![true, false],
+        block_declarations("typedef int T; void f(void) { __typeof__(T) T; T = 1; }")
+    );
+}
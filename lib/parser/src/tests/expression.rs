@@ -0,0 +1,202 @@
+use lexer::Span;
+
+use super::*;
+
+fn check(source: &str, expected: &str) {
+    assert_eq!(expected, sexp(&expression(source)), "for {source}");
+}
+
+#[test]
+fn test_primary() {
+    check("x", "x");
+    check("1", "Int(1)");
+    check("'a'", "Char(97)");
+    check("\"a\" \"b\"", "string");
+    check("(x)", "x");
+    check("((x))", "x");
+}
+
+#[test]
+fn test_binary_precedence() {
+    check("a || b && c", "(LogicalOr a (LogicalAnd b c))");
+    check("a && b | c", "(LogicalAnd a (BitOr b c))");
+    check("a | b ^ c", "(BitOr a (BitXor b c))");
+    check("a ^ b & c", "(BitXor a (BitAnd b c))");
+    check("a & b == c", "(BitAnd a (Equal b c))");
+    check("a != b < c", "(NotEqual a (Less b c))");
+    check("a >= b << c", "(GreaterEqual a (ShiftLeft b c))");
+    check("a >> b + c", "(ShiftRight a (Add b c))");
+    check("a - b * c", "(Subtract a (Multiply b c))");
+    check("a * b / c % d", "(Remainder (Divide (Multiply a b) c) d)");
+    check("a * b + c", "(Add (Multiply a b) c)");
+    check("(a + b) * c", "(Multiply (Add a b) c)");
+}
+
+#[test]
+fn test_binary_left_associative() {
+    check("a - b - c", "(Subtract (Subtract a b) c)");
+    check("a < b > c <= d", "(LessEqual (Greater (Less a b) c) d)");
+    check("a || b || c", "(LogicalOr (LogicalOr a b) c)");
+}
+
+#[test]
+fn test_assignment() {
+    check("a = b = c", "(Assign= a (Assign= b c))");
+    check("a += b -= c", "(Add= a (Subtract= b c))");
+    check("a <<= b | c", "(ShiftLeft= a (BitOr b c))");
+    check("a %= b", "(Remainder= a b)");
+    check("a = b ? c : d", "(Assign= a (? b c d))");
+}
+
+#[test]
+fn test_conditional() {
+    check("a ? b : c ? d : e", "(? a b (? c d e))");
+    check("a || b ? c , d : e", "(? (LogicalOr a b) (, c d) e)");
+}
+
+#[test]
+fn test_comma() {
+    check("a, b, c", "(, (, a b) c)");
+    check("f(a, (b, c))", "(call f a (, b c))");
+}
+
+#[test]
+fn test_unary() {
+    check("-a * b", "(Multiply (Minus a) b)");
+    check("!~+a", "(Not (Complement (Plus a)))");
+    check("*&a", "(Dereference (AddressOf a))");
+    check("++a--", "(PreIncrement (PostDecrement a))");
+    check("--*p", "(PreDecrement (Dereference p))");
+    check("- -a", "(Minus (Minus a))");
+    check("__extension__ a + b", "(Add a b)");
+}
+
+#[test]
+fn test_postfix() {
+    check("a[1][2]", "(index (index a Int(1)) Int(2))");
+    check("f()", "(call f)");
+    check("f(a, b = c)", "(call f a (Assign= b c))");
+    check("s.a->b", "(-> (. s a) b)");
+    check(
+        "f(x)[0].y++",
+        "(PostIncrement (. (index (call f x) Int(0)) y))",
+    );
+    check("*p++", "(Dereference (PostIncrement p))");
+}
+
+#[test]
+fn test_digraphs() {
+    check("a<:1:>", "(index a Int(1))");
+}
+
+#[test]
+fn test_cast() {
+    check("(int)x", "(cast type x)");
+    check("(unsigned long *)p + 1", "(Add (cast type p) Int(1))");
+    check("(char)(short)x", "(cast type (cast type x))");
+    check("(int)-x", "(cast type (Minus x))");
+    check("(x)-y", "(Subtract x y)");
+    check("(const struct s *)0", "(cast type Int(0))");
+}
+
+#[test]
+fn test_sizeof() {
+    check("sizeof x", "(sizeof x)");
+    check("sizeof (x)", "(sizeof x)");
+    check("sizeof (int)", "(sizeof type)");
+    check("sizeof (int[4])", "(sizeof type)");
+    check("sizeof x + 1", "(Add (sizeof x) Int(1))");
+    check("sizeof -x", "(sizeof (Minus x))");
+    check("sizeof (int){1}", "(sizeof (literal type 1))");
+    check(
+        "sizeof (int){1}[0]",
+        "(sizeof (index (literal type 1) Int(0)))",
+    );
+    check("sizeof sizeof x", "(sizeof (sizeof x))");
+    check("_Alignof (double)", "(_Alignof type)");
+}
+
+#[test]
+fn test_compound_literal() {
+    check("(int[]){1, 2, 3}", "(literal type 3)");
+    check("(struct s){.a = 1, .b = 2,}", "(literal type 2)");
+    check("(int[2]){1, 2}[1]", "(index (literal type 2) Int(1))");
+    check("(struct s){0}.a", "(. (literal type 1) a)");
+}
+
+#[test]
+fn test_generic() {
+    check(
+        "_Generic(x, int: 1, default: 2)",
+        "(_Generic x type:Int(1) default:Int(2))",
+    );
+    check(
+        "_Generic(x, char *: f, const long: g)(x)",
+        "(call (_Generic x type:f type:g) x)",
+    );
+}
+
+#[test]
+fn test_spans() {
+    //                        0         1         2
+    //                        0123456789012345678901234
+    let unit = parse_ok("void f(void) { a = (b)[1] + c; }");
+    let ExternalDeclaration::FunctionDefinition(definition) = &unit.declarations[0] else {
+        panic!("expected a function definition");
+    };
+    let BlockItem::Statement(statement) = &definition.body.items[0] else {
+        panic!("expected a statement");
+    };
+    assert_eq!(Span::new(15, 30), statement.span);
+    let StatementKind::Expression(Some(assignment)) = &statement.kind else {
+        panic!("expected an expression statement");
+    };
+    assert_eq!(Span::new(15, 29), assignment.span);
+    let ExpressionKind::Assignment { target, value, .. } = &assignment.kind else {
+        panic!("expected an assignment");
+    };
+    assert_eq!(Span::new(15, 16), target.span);
+    assert_eq!(Span::new(19, 29), value.span);
+    let ExpressionKind::Binary { left, right, .. } = &value.kind else {
+        panic!("expected an addition");
+    };
+    assert_eq!(Span::new(19, 25), left.span);
+    assert_eq!(Span::new(28, 29), right.span);
+    let ExpressionKind::Index { array, .. } = &left.kind else {
+        panic!("expected a subscript");
+    };
+    assert_eq!(Span::new(19, 22), array.span);
+}
+
+#[test]
+fn test_errors() {
+    let lang = LangOptions::default();
+    assert_eq!(vec!["expected"], codes("void f(void) { a + ; }", &lang));
+    assert_eq!(vec!["expected"], codes("void f(void) { f(a b); }", &lang));
+    assert_eq!(vec!["expected"], codes("void f(void) { a ? b; }", &lang));
+    assert_eq!(
+        vec!["expected"],
+        codes("void f(void) { _Generic(x); }", &lang)
+    );
+    assert_eq!(vec!["expected"], codes("void f(void) { (int)\n; }", &lang));
+}
+
+#[test]
+fn test_error_message() {
+    let (result, diagnostics) = parse_with("int x = a + ;", &LangOptions::default());
+    assert!(matches!(result, Err(Error::Diagnosed(ref errors)) if errors.len() == 1));
+    assert_eq!(
+        "expected expression before ';' token",
+        diagnostics[0].message()
+    );
+    let (_, diagnostics) = parse_with("int x = (a", &LangOptions::default());
+    assert_eq!("expected ')' at end of input", diagnostics[0].message());
+}
+
+#[test]
+fn test_compound_literal_c89() {
+    let source = "int *p = (int[]){1};";
+    assert_eq!(vec!["pedantic"], codes(source, &pedantic(Standard::C89)));
+    assert!(codes(source, &pedantic(Standard::C99)).is_empty());
+    assert!(codes(source, &LangOptions::new(Standard::C89)).is_empty());
+}
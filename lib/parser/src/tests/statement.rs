@@ -0,0 +1,209 @@
+use diagnostics::Expansions;
+
+use super::*;
+use crate::ast::{CompoundStatement, ForInit, Statement};
+
+/// the statements of the body of `void f(void) { source }`
+fn body(source: &str) -> CompoundStatement {
+    let unit = parse_ok(&format!("void f(void) {{ {source} }}"));
+    match unit.declarations.into_iter().next() {
+        Some(ExternalDeclaration::FunctionDefinition(definition)) => definition.body,
+        declaration => panic!("expected a function definition, not {declaration:?}"),
+    }
+}
+
+fn statement(source: &str) -> Statement {
+    match body(source).items.as_slice() {
+        [BlockItem::Statement(statement)] => statement.clone(),
+        items => panic!("expected a single statement for {source}, not {items:?}"),
+    }
+}
+
+#[test]
+fn test_selection() {
+    let StatementKind::If { otherwise, .. } = statement("if (a) b; else c;").kind else {
+        panic!("expected an if");
+    };
+    assert!(otherwise.is_some());
+
+    // the else belongs to the nearest if
+    let StatementKind::If {
+        then, otherwise, ..
+    } = statement("if (a) if (b) c; else d;").kind
+    else {
+        panic!("expected an if");
+    };
+    assert!(otherwise.is_none());
+    assert!(matches!(
+        then.kind,
+        StatementKind::If {
+            otherwise: Some(_),
+            ..
+        }
+    ));
+
+    let StatementKind::Switch { body, .. } =
+        statement("switch (x) { case 1: case 2: break; default: ; }").kind
+    else {
+        panic!("expected a switch");
+    };
+    let StatementKind::Compound(body) = body.kind else {
+        panic!("expected a compound body");
+    };
+    assert_eq!(2, body.items.len());
+    let BlockItem::Statement(case) = &body.items[0] else {
+        panic!("expected a statement");
+    };
+    let StatementKind::Case { statement, .. } = &case.kind else {
+        panic!("expected a case");
+    };
+    assert!(matches!(statement.kind, StatementKind::Case { .. }));
+}
+
+#[test]
+fn test_iteration() {
+    assert!(matches!(
+        statement("while (a) a--;").kind,
+        StatementKind::While { .. }
+    ));
+    assert!(matches!(
+        statement("do a--; while (a);").kind,
+        StatementKind::DoWhile { .. }
+    ));
+
+    let StatementKind::For {
+        init,
+        condition,
+        step,
+        ..
+    } = statement("for (;;) ;").kind
+    else {
+        panic!("expected a for");
+    };
+    assert_eq!(ForInit::Expression(None), init);
+    assert!(condition.is_none() && step.is_none());
+
+    let StatementKind::For {
+        init,
+        condition,
+        step,
+        ..
+    } = statement("for (int i = 0, j; i < 10; i++) {}").kind
+    else {
+        panic!("expected a for");
+    };
+    assert!(matches!(init, ForInit::Declaration(_)));
+    assert!(condition.is_some() && step.is_some());
+
+    let StatementKind::For { init, .. } = statement("for (i = 0; ; ) break;").kind else {
+        panic!("expected a for");
+    };
+    assert!(matches!(init, ForInit::Expression(Some(_))));
+}
+
+#[test]
+fn test_jumps() {
+    let items = body("goto end; continue; break; return; return 1; end: ;").items;
+    let kinds: Vec<&StatementKind> = items
+        .iter()
+        .map(|item| match item {
+            BlockItem::Statement(statement) => &statement.kind,
+            BlockItem::Declaration(_) => panic!("expected only statements"),
+        })
+        .collect();
+    assert!(matches!(kinds[0], StatementKind::Goto(label) if label.as_str() == "end"));
+    assert_eq!(&StatementKind::Continue, kinds[1]);
+    assert_eq!(&StatementKind::Break, kinds[2]);
+    assert_eq!(&StatementKind::Return(None), kinds[3]);
+    assert!(matches!(kinds[4], StatementKind::Return(Some(_))));
+    assert!(matches!(kinds[5], StatementKind::Labeled { label, .. } if label.as_str() == "end"));
+}
+
+#[test]
+fn test_block_items() {
+    let items = body("int a; a = 1; { int b; } ; const long c = a;").items;
+    assert_eq!(5, items.len());
+    assert!(matches!(items[0], BlockItem::Declaration(_)));
+    assert!(matches!(items[1], BlockItem::Statement(_)));
+    let BlockItem::Statement(Statement {
+        kind: StatementKind::Compound(nested),
+        ..
+    }) = &items[2]
+    else {
+        panic!("expected a nested block");
+    };
+    assert_eq!(1, nested.items.len());
+    assert!(matches!(
+        &items[3],
+        BlockItem::Statement(Statement {
+            kind: StatementKind::Expression(None),
+            ..
+        })
+    ));
+    assert!(matches!(items[4], BlockItem::Declaration(_)));
+}
+
+#[test]
+fn test_recovery() {
+    let lang = LangOptions::default();
+    // each bad statement is reported and the rest of the block is still parsed
+    let source = "void f(void) { a = ; b = 1; if (x { y; } c + ; }\nint g;";
+    let (result, diagnostics) = parse_with(source, &lang);
+    let codes: Vec<&str> = diagnostics.iter().map(|d| d.code()).collect();
+    assert_eq!(vec!["expected", "expected", "expected"], codes);
+    assert!(matches!(result, Err(Error::Diagnosed(errors)) if errors.len() == 3));
+
+    assert_eq!(
+        vec!["expected"],
+        super::codes("void f(void) { a = 1;", &lang)
+    );
+    assert_eq!(
+        vec!["expected"],
+        super::codes("void f(void) { do x; (y); }", &lang)
+    );
+    assert_eq!(
+        vec!["expected"],
+        super::codes("void f(void) { case: ; }", &lang)
+    );
+}
+
+#[test]
+fn test_after_lexer_errors() {
+    let lang = LangOptions::default();
+    let diagnostics = Diagnostics::new();
+    let expansions = Expansions::new();
+    let source = "int x = 'a;\nint y = 1;\nint z = ;";
+    let (lexed, lexing) = lexer::lex_recovering(source, &expansions, &lang, &diagnostics);
+    assert!(lexing.is_err());
+    assert!(parse(lexed.tokens(), lexed.source_map(), &lang, &diagnostics).is_err());
+    // what the lexer couldn't make a token of isn't reported again
+    let codes: Vec<&str> = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+    assert_eq!(vec!["unterminated-char-literal", "expected"], codes);
+}
+
+#[test]
+fn test_max_errors() {
+    let lang = LangOptions::default();
+    let diagnostics = Diagnostics::new().with_max_errors(2);
+    let source = "int a = ; int b = ; int c = ; int d = ;";
+    let lexed = lexer::lex(source, &lang, &diagnostics).expect("test source should lex");
+    assert!(parse(lexed.tokens(), lexed.source_map(), &lang, &diagnostics).is_err());
+    let codes: Vec<&str> = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+    assert_eq!(vec!["expected", "expected", "max-errors"], codes);
+}
+
+#[test]
+fn test_c89_declarations() {
+    let mixed = "void f(void) { int a; a = 1; int b; }";
+    assert_eq!(vec!["pedantic"], codes(mixed, &pedantic(Standard::C89)));
+    assert!(codes(mixed, &pedantic(Standard::C99)).is_empty());
+    assert!(codes(
+        "void f(void) { int a; int b; a = 1; }",
+        &pedantic(Standard::C89)
+    )
+    .is_empty());
+
+    let for_loop = "void f(void) { for (int i = 0; i < 1; i++) ; }";
+    assert_eq!(vec!["pedantic"], codes(for_loop, &pedantic(Standard::C89)));
+    assert!(codes(for_loop, &pedantic(Standard::C11)).is_empty());
+}
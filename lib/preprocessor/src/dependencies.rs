@@ -0,0 +1,67 @@
+//! Make rules for the files a source file depends on, written the way gcc's `-M` options
+//! write them.
+
+#[cfg(test)]
+mod tests;
+
+/// how wide gcc lets a line of a rule get before continuing it on the next
+const WIDTH: usize = 72;
+
+/// `name` with the characters make treats specially escaped, as gcc does
+pub fn make_quote(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len());
+    for (i, c) in name.char_indices() {
+        match c {
+            // a backslash before whitespace must be doubled, but not anywhere else
+            ' ' | '\t' => {
+                let backslashes = name[..i].chars().rev().take_while(|&b| b == '\\').count();
+                quoted.extend(std::iter::repeat_n('\\', backslashes + 1));
+            }
+            '$' => quoted.push('$'),
+            '#' => quoted.push('\\'),
+            _ => (),
+        }
+        quoted.push(c);
+    }
+    quoted
+}
+
+/// the rule making `targets` depend on `dependencies`, the first of which should be the
+/// source file. The targets are written as they are and the dependencies are quoted.
+/// `phony` adds an empty rule for each dependency after the first, so make carries on if
+/// a header is removed rather than having no way to remake it
+pub fn make_rule(targets: &[String], dependencies: &[String], phony: bool) -> String {
+    let mut rule = String::new();
+    let mut column = 0;
+    for target in targets {
+        column = write_name(&mut rule, column, target);
+    }
+    rule.push(':');
+    column += 1;
+    for dependency in dependencies {
+        column = write_name(&mut rule, column, &make_quote(dependency));
+    }
+    rule.push('\n');
+
+    if phony {
+        for dependency in dependencies.iter().skip(1) {
+            rule.push_str(&format!("{}:\n", make_quote(dependency)));
+        }
+    }
+    rule
+}
+
+/// write `name` into `rule` at `column`, continuing the line first if it would be too
+/// long, and return the column after it
+fn write_name(rule: &mut String, mut column: usize, name: &str) -> usize {
+    if column > 0 {
+        if column + name.len() > WIDTH {
+            rule.push_str(" \\\n");
+            column = 0;
+        }
+        rule.push(' ');
+        column += 1;
+    }
+    rule.push_str(name);
+    column + name.len()
+}
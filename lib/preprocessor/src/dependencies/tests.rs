@@ -0,0 +1,39 @@
+use super::*;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_make_quote() {
+    assert_eq!("plain/file.h", make_quote("plain/file.h"));
+    assert_eq!("a\\ b/c$$d\\#e.h", make_quote("a b/c$d#e.h"));
+    // backslashes before a space are doubled, those elsewhere are left alone
+    assert_eq!("a\\\\\\ b\\c", make_quote("a\\ b\\c"));
+}
+
+#[test]
+fn test_make_rule() {
+    let rule = make_rule(&names(&["dm.o"]), &names(&["dm.c", "s.h", "a b.h"]), false);
+    assert_eq!("dm.o: dm.c s.h a\\ b.h\n", rule);
+}
+
+#[test]
+fn test_make_rule_targets_and_phony() {
+    let rule = make_rule(&names(&["foo", "bar$x"]), &names(&["dm.c", "s.h"]), true);
+    assert_eq!("foo bar$x: dm.c s.h\ns.h:\n", rule);
+}
+
+#[test]
+fn test_make_rule_wraps() {
+    let header = |i| format!("longdirectoryname_abcdefghijklmnop/header_number_{i}.h");
+    let mut dependencies = names(&["lg.c", "/usr/include/stdc-predef.h"]);
+    dependencies.extend((1..=2).map(header));
+    let exp = concat!(
+        "lg.o: lg.c /usr/include/stdc-predef.h \\\n",
+        " longdirectoryname_abcdefghijklmnop/header_number_1.h \\\n",
+        " longdirectoryname_abcdefghijklmnop/header_number_2.h\n",
+    );
+
+    assert_eq!(exp, make_rule(&names(&["lg.o"]), &dependencies, false));
+}
@@ -0,0 +1,89 @@
+use std::{error, fmt, io, path::PathBuf};
+
+use diagnostics::{Diagnostic, ErrorKind, Severity, StageError};
+
+#[derive(Debug)]
+pub enum Error {
+    /// gcc couldn't be started
+    Spawn(io::Error),
+    /// gcc was started but we couldn't wait for it to finish
+    Wait(io::Error),
+    /// gcc exited with a nonzero code, it will have reported why on stderr
+    Failed(i32),
+    /// gcc was killed by a signal
+    Signalled,
+    /// the preprocessed output couldn't be read back
+    Read { path: PathBuf, source: io::Error },
+    /// the source had errors, which were also reported to the `Diagnostics` passed in
+    Diagnosed(Vec<Diagnostic>),
+    /// the preprocessed output couldn't be written
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl Error {
+    /// the errors among `reported`, if there were any
+    pub(crate) fn from_reported(reported: &[Diagnostic]) -> Option<Self> {
+        let errors: Vec<Diagnostic> = reported
+            .iter()
+            .filter(|d| d.severity() >= Severity::Error)
+            .cloned()
+            .collect();
+        if errors.is_empty() {
+            None
+        } else {
+            Some(Error::Diagnosed(errors))
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Spawn(_) => write!(f, "unable to run the preprocessor"),
+            Error::Wait(_) => write!(f, "failed to wait on the preprocessor"),
+            Error::Failed(code) => write!(f, "preprocessor exited with nonzero code: {code}"),
+            Error::Signalled => write!(f, "preprocessor terminated due to signal"),
+            Error::Read { path, .. } => {
+                write!(f, "unable to read preprocessed output {}", path.display())
+            }
+            Error::Diagnosed(errors) => {
+                write!(f, "preprocessing failed with {} error(s)", errors.len())
+            }
+            Error::Write { path, .. } => {
+                write!(f, "unable to write preprocessed output {}", path.display())
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Spawn(e)
+            | Error::Wait(e)
+            | Error::Read { source: e, .. }
+            | Error::Write { source: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl StageError for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Failed(_) | Error::Diagnosed(_) => ErrorKind::User,
+            Error::Spawn(_)
+            | Error::Wait(_)
+            | Error::Signalled
+            | Error::Read { .. }
+            | Error::Write { .. } => ErrorKind::Environment,
+        }
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Error::Diagnosed(errors) => errors,
+            _ => &[],
+        }
+    }
+}
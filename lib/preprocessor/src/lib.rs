@@ -1,17 +1,57 @@
 use std::{
-    fs::{self, File},
-    io::{self, Read, Write},
+    fs, io,
     path::Path,
-    process::{Child, Command, Stdio},
-    thread,
+    process::{Child, Command},
 };
 
+use diagnostics::{Diagnostics, Expansions};
+
+mod dependencies;
+mod error;
+mod native;
+mod options;
+
+pub use dependencies::{make_quote, make_rule};
+pub use error::Error;
+pub use native::{SearchDir, SearchPath, SystemHeader};
+pub use options::{MacroOption, Options};
+
+/// the preprocessed source, in the layout `gcc -E` writes, along with the macro expansion
+/// each token in it which came from a macro was produced by
+#[derive(Debug, Default, PartialEq)]
+pub struct Preprocessed {
+    text: String,
+    expansions: Expansions,
+    dependencies: Vec<String>,
+}
+
+impl Preprocessed {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn expansions(&self) -> &Expansions {
+        &self.expansions
+    }
+
+    /// the source file then each header it included, in the order they were first
+    /// included, named as they are in linemarkers
+    pub fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+}
+
 trait IoTrait {
     fn write(&self, path: &Path, contents: &str) -> io::Result<()>;
 }
 
 trait CompilerProc {
-    fn wrap_and_spawn(&self, src_path: &Path, out_path: &Path) -> Result<Child, io::Error>;
+    fn wrap_and_spawn(
+        &self,
+        src_path: &Path,
+        out_path: &Path,
+        args: &[String],
+    ) -> Result<Child, io::Error>;
 }
 
 struct DefaultImpl;
@@ -23,8 +63,14 @@ impl IoTrait for DefaultImpl {
 }
 
 impl CompilerProc for DefaultImpl {
-    fn wrap_and_spawn(&self, src_path: &Path, out_path: &Path) -> Result<Child, io::Error> {
+    fn wrap_and_spawn(
+        &self,
+        src_path: &Path,
+        out_path: &Path,
+        args: &[String],
+    ) -> Result<Child, io::Error> {
         Command::new("gcc")
+            .args(args)
             .args(["-E", src_path.to_str().unwrap(), "-o", out_path.to_str().unwrap()])
             .spawn()
     }
@@ -33,61 +79,88 @@ impl CompilerProc for DefaultImpl {
 fn preprocess_internal(
     src_path: &Path,
     out_path: &Path,
-    io_handle: &impl IoTrait,
+    options: &Options,
     compiler: &impl CompilerProc,
-) -> io::Result<String> {
-
+) -> Result<String, Error> {
     let mut preprocess = compiler
-        .wrap_and_spawn(src_path, out_path)
-        .expect("Error spawning preprocessor");
+        .wrap_and_spawn(src_path, out_path, &options.gcc_args())
+        .map_err(Error::Spawn)?;
 
     let exit = preprocess
         .wait()
-        .expect("Failed to wait on preprocessor");
+        .map_err(Error::Wait)?;
 
-    match &exit.code() {
-        Some(0) => println!("Preprocessor ran successfully"),
-        Some(c) => panic!("Preprocessor exited with nonzero code: {c}"),
-        None => panic!("Preprocessor terminated due to signal"),
+    match exit.code() {
+        Some(0) => (),
+        Some(c) => return Err(Error::Failed(c)),
+        None => return Err(Error::Signalled),
     };
 
-    let result = fs::read_to_string(out_path).expect("Unable to read written preprocessor file");
+    let result = fs::read_to_string(out_path).map_err(|source| Error::Read {
+        path: out_path.to_path_buf(),
+        source,
+    })?;
+
+    Ok(result)
+}
+
+/// preprocess with `gcc -E` rather than our own preprocessor
+pub fn preprocess_with_gcc(
+    src_path: &Path,
+    out_path: &Path,
+    options: &Options,
+) -> Result<String, Error> {
+    preprocess_internal(src_path, out_path, options, &DefaultImpl)
+}
 
-    if ! result.is_ascii() {
-        panic!("Naive implementation only handles input in ASCII");
+fn preprocess_native(
+    src_path: &Path,
+    out_path: &Path,
+    options: &Options,
+    system: SearchPath,
+    io_handle: &impl IoTrait,
+    diagnostics: &Diagnostics,
+) -> Result<Preprocessed, Error> {
+    let already_reported = diagnostics.diagnostics().len();
+    let result = native::preprocess(src_path, options, system, diagnostics);
+    if let Some(e) = Error::from_reported(&diagnostics.diagnostics()[already_reported..]) {
+        return Err(e);
     }
 
+    io_handle.write(out_path, result.text()).map_err(|source| Error::Write {
+        path: out_path.to_path_buf(),
+        source,
+    })?;
+
     Ok(result)
 }
 
-pub fn preprocess(src_path: &Path, out_path: &Path) -> io::Result<String> {
-    let default_impl = DefaultImpl;
-    preprocess_internal(src_path, out_path, &default_impl, &default_impl)
+/// preprocess `src_path` into `out_path` as `options` say, reporting any problems in the
+/// source to `diagnostics`
+pub fn preprocess(
+    src_path: &Path,
+    out_path: &Path,
+    options: &Options,
+    diagnostics: &Diagnostics,
+) -> Result<Preprocessed, Error> {
+    let system = SearchPath::system_default();
+    preprocess_native(src_path, out_path, options, system, &DefaultImpl, diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{error, path::PathBuf};
 
     use super::*;
 
-    use tempfile::{self, NamedTempFile};
-
-    struct IncludeCompiler;
-    impl CompilerProc for IncludeCompiler {
-        fn wrap_and_spawn(&self, src_path: &Path, out_path: &Path) -> Result<Child, io::Error> {
-            Command::new("gcc")
-                .stdout(Stdio::piped())
-                .args(["-I", "src/asset", "-E", src_path.to_str().unwrap(), "-o", out_path.to_str().unwrap()])
-                .spawn()
-        }
-    }
+    use diagnostics::{ErrorKind, StageError};
+    use lang::{LangOptions, Pedantic, Standard};
 
     #[test]
     fn test_no_include() {
         struct IoTest;
         impl IoTrait for IoTest {
-            fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+            fn write(&self, _: &Path, _: &str) -> io::Result<()> {
                 Ok(())
             }
         }
@@ -97,19 +170,22 @@ mod tests {
         let src_path: PathBuf = PathBuf::from("src/asset/return_0.c");
         let out_path: PathBuf = PathBuf::from(tmp.path());
         let io_test: IoTest = IoTest;
-        let compiler = DefaultImpl;
-        let result = preprocess_internal(&src_path, &out_path, &io_test, &compiler)
+        let diagnostics = Diagnostics::new();
+        let system = SearchPath::system_default();
+        let options = Options::default();
+        let result =
+            preprocess_native(&src_path, &out_path, &options, system, &io_test, &diagnostics)
             .expect("preprocessor should return");
         let exp = include_str!("asset/return_0.i");
 
-        assert_eq!(exp, &result);
+        assert_eq!(exp, result.text());
     }
 
     #[test]
     fn test_include() {
         struct IoTest;
         impl IoTrait for IoTest {
-            fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+            fn write(&self, _: &Path, _: &str) -> io::Result<()> {
                 Ok(())
             }
         }
@@ -119,11 +195,78 @@ mod tests {
         let src_path: PathBuf = PathBuf::from("src/asset/include_a.c");
         let out_path: PathBuf = PathBuf::from(tmp.path());
         let io_test: IoTest = IoTest;
-        let compiler = IncludeCompiler;
-        let result = preprocess_internal(&src_path, &out_path, &io_test, &compiler)
+        let diagnostics = Diagnostics::new();
+        let system = SearchPath::system_default();
+        let options = Options::default();
+        let result =
+            preprocess_native(&src_path, &out_path, &options, system, &io_test, &diagnostics)
             .expect("preprocessor should return");
         let exp = include_str!("asset/include_a.i");
 
-        assert_eq!(exp, &result);
+        assert_eq!(exp, result.text());
+    }
+
+    #[test]
+    fn test_missing_source_is_user_error() {
+        let tmp = tempfile::NamedTempFile::new().expect("Unable to create temp file for test");
+
+        let src_path: PathBuf = PathBuf::from("src/asset/does_not_exist.c");
+        let out_path: PathBuf = PathBuf::from(tmp.path());
+        let options = Options::default();
+        let result = preprocess_internal(&src_path, &out_path, &options, &DefaultImpl);
+
+        let err = result.expect_err("gcc should fail on a missing file");
+        assert!(matches!(err, Error::Failed(_)));
+        assert_eq!(ErrorKind::User, err.kind());
+    }
+
+    #[test]
+    fn test_missing_compiler_is_environment_error() {
+        struct MissingCompiler;
+        impl CompilerProc for MissingCompiler {
+            fn wrap_and_spawn(&self, _: &Path, _: &Path, _: &[String]) -> Result<Child, io::Error> {
+                Command::new("does-not-exist-gcc").spawn()
+            }
+        }
+
+        let src_path: PathBuf = PathBuf::from("src/asset/return_0.c");
+        let out_path: PathBuf = PathBuf::from("unused.i");
+        let options = Options::default();
+        let result = preprocess_internal(&src_path, &out_path, &options, &MissingCompiler);
+
+        let err = result.expect_err("spawning should fail");
+        assert!(matches!(err, Error::Spawn(_)));
+        assert_eq!(ErrorKind::Environment, err.kind());
+        assert!(error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_gcc_args() {
+        let options = Options {
+            include_dirs: vec![PathBuf::from("src/asset")],
+            system_include_dirs: vec![PathBuf::from("/opt/include")],
+            macros: vec![
+                MacroOption::Define("X=1".to_string()),
+                MacroOption::Undefine("Y".to_string()),
+            ],
+            includes: vec![PathBuf::from("config.h")],
+            lang: LangOptions {
+                standard: Standard::C99,
+                pedantic: Pedantic::Warn,
+            },
+        };
+        let exp = vec![
+            "-std=c99",
+            "-pedantic",
+            "-Isrc/asset",
+            "-isystem",
+            "/opt/include",
+            "-DX=1",
+            "-UY",
+            "-include",
+            "config.h",
+        ];
+
+        assert_eq!(exp, options.gcc_args());
     }
 }
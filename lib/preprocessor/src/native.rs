@@ -0,0 +1,1177 @@
+//! A C11 preprocessor (translation phases 1 to 4) writing its output in the same layout
+//! as `gcc -E`, linemarkers included, so the lexer can follow where each line came from.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use diagnostics::{Diagnostic, Diagnostics, Span};
+use lang::LangOptions;
+
+use crate::{Options, Preprocessed};
+
+mod builtins;
+mod expand;
+mod expr;
+mod macros;
+mod output;
+mod search;
+mod source;
+mod token;
+
+#[cfg(test)]
+mod tests;
+
+pub use output::SystemHeader;
+pub use search::{SearchDir, SearchPath};
+
+use expand::{Input, Parsing};
+use macros::Macro;
+use output::Output;
+use source::{LexProblem, Line};
+use token::{Kind, PpToken};
+
+/// gcc's default limit on how deeply `#include` may nest
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+/// the name of the file the command line options which act like directives are read from
+const COMMAND_LINE: &str = "<command-line>";
+
+/// an `#if` and its `#elif`s and `#else`, C11 6.10.1
+#[derive(Debug)]
+struct Conditional {
+    /// the `if`, `ifdef` or `ifndef` which opened it, for reporting it unterminated
+    directive: PpToken,
+    /// the group we are in is being processed
+    active: bool,
+    /// one of its groups has been (or, when skipping, can't be) processed
+    taken: bool,
+    seen_else: bool,
+}
+
+#[derive(Debug)]
+struct File {
+    /// the presumed name, which `#line` can change
+    name: Rc<str>,
+    /// the real path, for recognising the file when it is included again
+    canonical: PathBuf,
+    /// where quoted `#include`s in the file look first
+    dir: PathBuf,
+    lines: VecDeque<Line>,
+    problems: VecDeque<LexProblem>,
+    /// the presumed line number minus the physical one, from `#line`
+    line_delta: i64,
+    conditionals: Vec<Conditional>,
+    system: SystemHeader,
+    /// where in the search path the file was found, for `#include_next`
+    found_at: Option<usize>,
+    /// the directives from the command line rather than a real file, everything in it is
+    /// at line 0 as in gcc
+    command_line: bool,
+    /// the line in the including file after the `#include`, where the output goes back to
+    return_line: u32,
+}
+
+impl File {
+    fn presumed(&self, physical: u32) -> u32 {
+        if self.command_line {
+            return 0;
+        }
+        (physical as i64 + self.line_delta).clamp(0, u32::MAX as i64) as u32
+    }
+}
+
+pub struct Preprocessor<'d> {
+    diagnostics: &'d Diagnostics,
+    lang: LangOptions,
+    search: SearchPath,
+    macros: HashMap<Rc<str>, Rc<Macro>>,
+    /// the definitions saved by `#pragma push_macro`
+    pushed: HashMap<Rc<str>, Vec<Option<Rc<Macro>>>>,
+    poisoned: HashSet<Rc<str>>,
+    /// the files with `#pragma once`
+    once: HashSet<PathBuf>,
+    /// the macros guarding the files which have them
+    guards: HashMap<PathBuf, Rc<str>>,
+    /// the name of each file read in the order they were first read, with the real paths
+    /// of those read so far
+    dependencies: Vec<String>,
+    read: HashSet<PathBuf>,
+    files: Vec<File>,
+    /// the `-include` files still to be read at the end of the command line
+    forced: VecDeque<PathBuf>,
+    output: Output,
+    /// the rest of the line being read
+    line: VecDeque<PpToken>,
+    /// tokens to read before the source, like the result of expanding a macro
+    pending: VecDeque<PpToken>,
+    parsing: Parsing,
+    counter: u32,
+    base_file: Rc<str>,
+    date_time: Option<(String, String)>,
+    /// an error has stopped preprocessing, like a missing `#include`
+    fatal: bool,
+}
+
+/// preprocess the file at `path` with `options` and the `system` include directories,
+/// reporting any problems to `diagnostics`. The output is returned even if there were
+/// errors, so whether it can be used is up to the caller
+pub fn preprocess(
+    path: &Path,
+    options: &Options,
+    system: SearchPath,
+    diagnostics: &Diagnostics,
+) -> Preprocessed {
+    let name: Rc<str> = path.to_string_lossy().into();
+    let text = match fs::read(path) {
+        Ok(bytes) => match decode(&name, bytes, diagnostics) {
+            Some(text) => text,
+            None => return Preprocessed::default(),
+        },
+        Err(e) => {
+            let message = format!("{name}: {}", io_message(&e));
+            diagnostics.report(Diagnostic::fatal("missing-include", &message));
+            return Preprocessed::default();
+        }
+    };
+    let newline = match text.find('\n') {
+        Some(i) if text[..i].ends_with('\r') => "\r\n",
+        _ => "\n",
+    };
+
+    let mut preprocessor = Preprocessor {
+        diagnostics,
+        lang: options.lang,
+        search: options.search_path(system),
+        macros: HashMap::new(),
+        pushed: HashMap::new(),
+        poisoned: HashSet::new(),
+        once: HashSet::new(),
+        guards: HashMap::new(),
+        dependencies: vec![],
+        read: HashSet::new(),
+        files: vec![],
+        forced: VecDeque::new(),
+        output: Output::new(&name, newline),
+        line: VecDeque::new(),
+        pending: VecDeque::new(),
+        parsing: Parsing::Text,
+        counter: 0,
+        base_file: name.clone(),
+        date_time: None,
+        fatal: false,
+    };
+    preprocessor.predefine();
+    preprocessor.push_file(name, &text, path, SystemHeader::No, None, 0);
+    preprocessor.push_command_line(options);
+    preprocessor.run()
+}
+
+/// the message for an io error without the "(os error 2)" std adds
+fn io_message(e: &std::io::Error) -> String {
+    let message = e.to_string();
+    match message.find(" (os error") {
+        Some(i) => message[..i].to_string(),
+        None => message,
+    }
+}
+
+/// the text of the file `name`, if it isn't valid UTF-8 the first byte which isn't is
+/// reported rather than letting the replacement character into the source
+fn decode(name: &str, bytes: Vec<u8>, diagnostics: &Diagnostics) -> Option<String> {
+    let e = match String::from_utf8(bytes) {
+        Ok(text) => return Some(text),
+        Err(e) => e,
+    };
+    let offset = e.utf8_error().valid_up_to();
+    let before = &e.as_bytes()[..offset];
+    let line = before.iter().filter(|b| **b == b'\n').count() as u32 + 1;
+    let line_start = before.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    let column = std::str::from_utf8(&before[line_start..])
+        .expect("valid up to the offset")
+        .chars()
+        .count()
+        + 1;
+    let message = format!("{name}: invalid UTF-8 at byte offset {offset}");
+    let span = Span::point(name, line, column);
+    diagnostics.report(Diagnostic::error("invalid-utf8", &message).with_span(span));
+    None
+}
+
+fn dir_of(path: &Path) -> PathBuf {
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// the spelling of `tokens` with a space wherever there was whitespace between them
+fn spelling(tokens: &[PpToken]) -> String {
+    let mut spelling = String::new();
+    for (i, token) in tokens.iter().filter(|t| !t.is_padding()).enumerate() {
+        if i > 0 && token.white {
+            spelling.push(' ');
+        }
+        spelling.push_str(&token.text);
+    }
+    spelling
+}
+
+/// the contents of a plain string literal, undoing the escapes of quotes and backslashes
+/// as `_Pragma` (C11 6.10.9) and `#line` do
+fn destringize(literal: &PpToken) -> Option<String> {
+    if literal.kind != Kind::StringLit {
+        return None;
+    }
+    let text = literal.text.strip_prefix('L').unwrap_or(&literal.text);
+    let body = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('\\' | '"'))) => {
+                out.push(next);
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+impl<'d> Preprocessor<'d> {
+    fn predefine(&mut self) {
+        let file: Rc<str> = "<built-in>".into();
+        let mut predefined = builtins::PREDEFINED.to_string();
+        if let Some(version) = self.lang.standard.version() {
+            predefined.push_str(&format!("#define __STDC_VERSION__ {version}\n"));
+        }
+        let (lines, _) = source::lex(&predefined, &self.lang);
+        for line in lines {
+            if let [_, define, rest @ ..] = &line.tokens[..] {
+                if let Ok((name, defined)) = macros::parse_define(define, rest, &file) {
+                    self.macros.insert(name, Rc::new(defined));
+                }
+            }
+        }
+        for (name, builtin) in builtins::DYNAMIC {
+            self.macros
+                .insert(name.into(), Rc::new(Macro::builtin(builtin)));
+        }
+    }
+
+    fn push_file(
+        &mut self,
+        name: Rc<str>,
+        text: &str,
+        path: &Path,
+        system: SystemHeader,
+        found_at: Option<usize>,
+        return_line: u32,
+    ) {
+        let (lines, problems) = source::lex(text, &self.lang);
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if let Some(guard) = source::include_guard(&lines) {
+            self.guards.insert(canonical.clone(), guard);
+        }
+        if self.read.insert(canonical.clone()) {
+            self.dependencies.push(name.to_string());
+        }
+        self.files.push(File {
+            name,
+            canonical,
+            dir: dir_of(path),
+            lines: lines.into(),
+            problems: problems.into(),
+            line_delta: 0,
+            conditionals: vec![],
+            system,
+            found_at,
+            return_line,
+            command_line: false,
+        });
+    }
+
+    /// the `-D` and `-U` options as the directives they stand for, which are read from a
+    /// file of their own before the main file. The `-include` files are read as it ends,
+    /// by path so that they needn't be spelt as a directive
+    fn push_command_line(&mut self, options: &Options) {
+        let mut text = String::new();
+        for option in &options.macros {
+            text.push_str(&option.directive());
+            text.push('\n');
+        }
+        self.forced = options.includes.iter().cloned().collect();
+        let (lines, problems) = source::lex(&text, &self.lang);
+        self.files.push(File {
+            name: COMMAND_LINE.into(),
+            canonical: PathBuf::new(),
+            // `-include` looks in the working directory first rather than that of the
+            // main file
+            dir: PathBuf::from("."),
+            lines: lines.into(),
+            problems: problems.into(),
+            line_delta: 0,
+            conditionals: vec![],
+            system: SystemHeader::No,
+            found_at: None,
+            return_line: 0,
+            command_line: true,
+        });
+    }
+
+    fn run(mut self) -> Preprocessed {
+        while !self.stopped() {
+            let Some(token) = self.expand_next(&mut Input::Source) else {
+                if self.leave_file() {
+                    continue;
+                }
+                break;
+            };
+            if token.is_identifier("_Pragma") {
+                self.pragma_operator(&token);
+                continue;
+            }
+            self.output.token(&token);
+        }
+        Preprocessed {
+            dependencies: self.dependencies,
+            ..self.output.finish()
+        }
+    }
+
+    /// finish with the current file, false if that was the main file
+    fn leave_file(&mut self) -> bool {
+        if self.files.last().is_some_and(|f| f.command_line) {
+            if let Some(include) = self.forced.pop_front() {
+                // as if `#include "file"` were the next line of the command line
+                let at = Span::point(COMMAND_LINE, 0, 1);
+                self.include_file(&include, false, false, at, 0, 0);
+                return true;
+            }
+        }
+        let Some(file) = self.files.last() else {
+            return false;
+        };
+        for conditional in &file.conditionals {
+            let directive = &conditional.directive;
+            let message = format!("unterminated #{}", directive.text);
+            self.error("unterminated-conditional", directive, &message);
+        }
+        let file = self.files.pop().expect("just looked at it");
+        match self.files.last() {
+            Some(main) if file.command_line => {
+                let name = main.name.clone();
+                self.output.main_file(&name);
+                true
+            }
+            Some(parent) => {
+                let (name, system) = (parent.name.clone(), parent.system);
+                self.output.leave_file(file.return_line, &name, system);
+                true
+            }
+            None => {
+                self.files.push(file);
+                false
+            }
+        }
+    }
+
+    pub(super) fn file_name(&self) -> Rc<str> {
+        match self.files.last() {
+            Some(file) => file.name.clone(),
+            None => self.base_file.clone(),
+        }
+    }
+
+    fn span(&self, at: &PpToken) -> Span {
+        let (line, column) = at.position();
+        Span::new(
+            &self.file_name(),
+            line,
+            column as usize,
+            at.text.len().max(1),
+        )
+    }
+
+    /// `diagnostic` at `at`, along with the macro expansions it came out of
+    fn located(&self, diagnostic: Diagnostic, at: &PpToken) -> Diagnostic {
+        let diagnostic = diagnostic.with_span(self.span(at));
+        let diagnostic = match &at.expansion {
+            Some(expansion) => diagnostic.with_expansion(expansion),
+            None => diagnostic,
+        };
+        self.in_current_file(diagnostic)
+    }
+
+    /// `diagnostic` marked as being about a system header if that's what we're reading
+    fn in_current_file(&self, diagnostic: Diagnostic) -> Diagnostic {
+        let system = self.files.last().map(|file| file.system);
+        match system {
+            Some(SystemHeader::Yes | SystemHeader::ExternC) => diagnostic.in_system_header(),
+            Some(SystemHeader::No) | None => diagnostic,
+        }
+    }
+
+    fn error(&self, code: &'static str, at: &PpToken, message: &str) {
+        let diagnostic = self.located(Diagnostic::error(code, message), at);
+        self.diagnostics.report(diagnostic);
+    }
+
+    fn warning(&self, code: &'static str, at: &PpToken, message: &str) {
+        let diagnostic = self.located(Diagnostic::warning(code, message), at);
+        self.diagnostics.report(diagnostic);
+    }
+
+    /// there has been a fatal error, or as many errors as `-fmax-errors` allows
+    fn stopped(&self) -> bool {
+        self.fatal || self.diagnostics.limit_reached()
+    }
+
+    fn skipping(&self) -> bool {
+        self.files
+            .last()
+            .and_then(|file| file.conditionals.last())
+            .is_some_and(|conditional| !conditional.active)
+    }
+
+    /// the next token from the source files, `None` at the end of the current file
+    fn source_token(&mut self) -> Option<PpToken> {
+        loop {
+            if let Some(mut token) = self.line.pop_front() {
+                if token.bol {
+                    match self.parsing {
+                        Parsing::Text => self.output.line_change(&token),
+                        Parsing::Paren => {}
+                        Parsing::Arguments => token.white = true,
+                    }
+                }
+                if token.kind == Kind::Identifier && self.poisoned.contains(&token.text) {
+                    let message = format!("attempt to use poisoned \"{}\"", token.text);
+                    self.error("poisoned-identifier", &token, &message);
+                }
+                return Some(token);
+            }
+            if self.stopped() {
+                return None;
+            }
+
+            let file = self.files.last_mut()?;
+            let mut line = file.lines.pop_front()?;
+            for token in &mut line.tokens {
+                token.file = file.name.clone();
+                token.line = file.presumed(token.line);
+            }
+            let mut problems = vec![];
+            while file.problems.front().is_some_and(|p| p.line <= line.last) {
+                let mut problem = file.problems.pop_front().expect("just looked at it");
+                problem.line = file.presumed(problem.line);
+                problems.push(problem);
+            }
+
+            let skipping = self.skipping();
+            if !skipping || line.is_directive() {
+                for problem in problems {
+                    self.lex_problem(problem);
+                }
+            }
+            if line.is_directive() {
+                self.directive(line);
+            } else if !skipping {
+                self.line = line.tokens.into();
+            }
+        }
+    }
+
+    fn lex_problem(&self, problem: LexProblem) {
+        let span = Span::point(&self.file_name(), problem.line, problem.column as usize);
+        let diagnostic = match problem.error {
+            true => Diagnostic::error(problem.code, &problem.message),
+            false => Diagnostic::warning(problem.code, &problem.message),
+        };
+        let diagnostic = self.in_current_file(diagnostic.with_span(span));
+        self.diagnostics.report(diagnostic);
+    }
+
+    fn directive(&mut self, line: Line) {
+        let mut tokens = line.tokens.into_iter();
+        let hash = tokens.next().expect("directives start with #");
+        let Some(name) = tokens.next() else {
+            // the null directive, C11 6.10.7
+            return;
+        };
+        let rest: Vec<PpToken> = tokens.collect();
+
+        let conditional = matches!(
+            &*name.text,
+            "if" | "ifdef" | "ifndef" | "elif" | "else" | "endif"
+        );
+        if self.skipping() && !conditional {
+            return;
+        }
+
+        // the line after the directive, in case it spans lines
+        let file = self.files.last().expect("directives are read from a file");
+        let next_line = file.presumed(line.last + 1);
+
+        match &*name.text {
+            "if" | "ifdef" | "ifndef" => self.open_conditional(&name, rest),
+            "elif" => self.elif(&name, rest),
+            "else" => self.r#else(&name, &rest),
+            "endif" => self.endif(&name, &rest),
+            "include" | "include_next" => self.include(&hash, &name, rest, next_line),
+            "define" => self.define(&name, &rest),
+            "undef" => self.undef(&name, &rest),
+            "line" => self.line_directive(&name, rest, next_line, true),
+            "error" => {
+                let message = format!("#error {}", spelling(&rest));
+                self.error("error-directive", &name, &message);
+            }
+            "warning" => {
+                // unlike other warnings gcc reports these from system headers too
+                let message = format!("#warning {}", spelling(&rest));
+                let diagnostic =
+                    Diagnostic::warning("warning-directive", &message).with_span(self.span(&name));
+                self.diagnostics.report(diagnostic);
+            }
+            "pragma" => self.pragma(&hash, &rest, false),
+            "ident" | "sccs" => match &rest[..] {
+                [literal] if literal.kind == Kind::StringLit => {
+                    if self.parsing == Parsing::Text {
+                        self.output.ident(hash.line, &rest);
+                    }
+                }
+                _ => {
+                    let message = format!("invalid #{} directive", name.text);
+                    self.error("invalid-directive", &name, &message);
+                }
+            },
+            // the linemarkers gcc writes, `# 33 "file.c" 2`
+            _ if name.kind == Kind::Number => {
+                let mut tokens = vec![name.clone()];
+                tokens.extend(rest);
+                self.line_directive(&name, tokens, next_line, false);
+            }
+            _ => {
+                let message = format!("invalid preprocessing directive #{}", name.text);
+                self.error("invalid-directive", &name, &message);
+            }
+        }
+    }
+
+    fn extra_tokens(&self, directive: &PpToken, rest: &[PpToken]) {
+        if let Some(extra) = rest.first() {
+            let message = format!("extra tokens at end of #{} directive", directive.text);
+            self.warning("extra-tokens", extra, &message);
+        }
+    }
+
+    fn open_conditional(&mut self, directive: &PpToken, rest: Vec<PpToken>) {
+        let active = match self.skipping() {
+            true => None,
+            false => Some(match &*directive.text {
+                "if" => self.condition(directive, rest),
+                _ => self.ifdef(directive, &rest),
+            }),
+        };
+        let file = self
+            .files
+            .last_mut()
+            .expect("directives are read from a file");
+        file.conditionals.push(Conditional {
+            directive: directive.clone(),
+            active: active == Some(true),
+            taken: active != Some(false),
+            seen_else: false,
+        });
+    }
+
+    fn ifdef(&self, directive: &PpToken, rest: &[PpToken]) -> bool {
+        let Some(name) = rest.first().filter(|t| t.kind == Kind::Identifier) else {
+            let message = match rest.first() {
+                Some(_) => "macro names must be identifiers".to_string(),
+                None => format!("no macro name given in #{} directive", directive.text),
+            };
+            self.error(
+                "invalid-directive",
+                rest.first().unwrap_or(directive),
+                &message,
+            );
+            return false;
+        };
+        self.extra_tokens(directive, &rest[1..]);
+        self.macros.contains_key(&name.text) == (&*directive.text == "ifdef")
+    }
+
+    fn elif(&mut self, directive: &PpToken, rest: Vec<PpToken>) {
+        let Some(conditional) = self.files.last().and_then(|f| f.conditionals.last()) else {
+            self.error("unbalanced-conditional", directive, "#elif without #if");
+            return;
+        };
+        if conditional.seen_else {
+            self.error("unbalanced-conditional", directive, "#elif after #else");
+        }
+        let active = !conditional.taken && self.condition(directive, rest);
+        let file = self.files.last_mut().expect("just looked at it");
+        let conditional = file.conditionals.last_mut().expect("just looked at it");
+        conditional.active = active;
+        conditional.taken |= active;
+    }
+
+    fn r#else(&mut self, directive: &PpToken, rest: &[PpToken]) {
+        let Some(file) = self.files.last_mut() else {
+            return;
+        };
+        let Some(conditional) = file.conditionals.last_mut() else {
+            self.error("unbalanced-conditional", directive, "#else without #if");
+            return;
+        };
+        let seen_else = conditional.seen_else;
+        conditional.active = !conditional.taken;
+        conditional.taken = true;
+        conditional.seen_else = true;
+        if seen_else {
+            self.error("unbalanced-conditional", directive, "#else after #else");
+        }
+        if !self.skipping() {
+            self.extra_tokens(directive, rest);
+        }
+    }
+
+    fn endif(&mut self, directive: &PpToken, rest: &[PpToken]) {
+        let file = self
+            .files
+            .last_mut()
+            .expect("directives are read from a file");
+        if file.conditionals.pop().is_none() {
+            self.error("unbalanced-conditional", directive, "#endif without #if");
+        } else if !self.skipping() {
+            self.extra_tokens(directive, rest);
+        }
+    }
+
+    /// evaluate the expression of a `#if` or `#elif`, C11 6.10.1
+    fn condition(&mut self, directive: &PpToken, rest: Vec<PpToken>) -> bool {
+        let mut input = Input::tokens(rest);
+        let mut tokens = vec![];
+        while let Some(token) = self.next_raw(&mut input) {
+            let value = if token.is_identifier("defined") {
+                self.defined(&token, &mut input)
+            } else if token.is_identifier("__has_include")
+                || token.is_identifier("__has_include_next")
+            {
+                self.has_include(&token, &mut input)
+            } else if self.try_expand(&token, &mut input) {
+                continue;
+            } else {
+                tokens.push(token);
+                continue;
+            };
+            let Some(value) = value else {
+                return false;
+            };
+            tokens.push(PpToken::synthesised(Kind::Number, value, &token));
+        }
+
+        match expr::evaluate(&tokens, &directive.text) {
+            Ok(value) => value.is_true(),
+            Err((at, message)) => {
+                self.error("invalid-if", at.as_ref().unwrap_or(directive), &message);
+                false
+            }
+        }
+    }
+
+    /// the value of `defined X` or `defined ( X )`
+    fn defined(&mut self, defined: &PpToken, input: &mut Input) -> Option<&'static str> {
+        let mut next = || loop {
+            match self.next_raw(input) {
+                Some(token) if token.is_padding() => continue,
+                other => return other,
+            }
+        };
+        let mut name = next();
+        let paren = name.as_ref().is_some_and(|t| t.is("("));
+        if paren {
+            name = next();
+        }
+        let Some(name) = name.filter(|t| t.kind == Kind::Identifier) else {
+            let message = "operator \"defined\" requires an identifier";
+            self.error("invalid-if", defined, message);
+            return None;
+        };
+        if paren && !next().is_some_and(|t| t.is(")")) {
+            self.error("invalid-if", &name, "missing ')' after \"defined\"");
+            return None;
+        }
+        let has_include = matches!(&*name.text, "__has_include" | "__has_include_next");
+        match self.macros.contains_key(&name.text) || has_include {
+            true => Some("1"),
+            false => Some("0"),
+        }
+    }
+
+    /// the value of `__has_include ( header-name )`
+    fn has_include(&mut self, operator: &PpToken, input: &mut Input) -> Option<&'static str> {
+        let mut tokens = vec![];
+        while let Some(token) = self.next_raw(input) {
+            if token.is(")") {
+                break;
+            }
+            if !token.is_padding() {
+                tokens.push(token);
+            }
+        }
+        let name = match tokens.split_first() {
+            Some((paren, header)) if paren.is("(") => self.header_name(header),
+            _ => None,
+        };
+        let Some((name, angled)) = name else {
+            let message = format!("operator \"{}\" requires a header-name", operator.text);
+            self.error("invalid-if", operator, &message);
+            return None;
+        };
+        let next = &*operator.text == "__has_include_next";
+        match self.find_header(Path::new(&name), angled, next).is_some() {
+            true => Some("1"),
+            false => Some("0"),
+        }
+    }
+
+    /// the name of the header in `tokens` and whether it was in angle brackets, C11
+    /// 6.10.2p4 has a `<` and what follows it up to `>` make up the name
+    fn header_name(&self, tokens: &[PpToken]) -> Option<(String, bool)> {
+        let first = tokens.first()?;
+        let text = &*first.text;
+        match first.kind {
+            Kind::HeaderName => Some((text[1..text.len() - 1].to_string(), true)),
+            Kind::StringLit if text.starts_with('"') => {
+                Some((text[1..text.len() - 1].to_string(), false))
+            }
+            _ if first.is("<") => {
+                let end = tokens.iter().position(|t| t.is(">"))?;
+                Some((spelling(&tokens[1..end]), true))
+            }
+            _ => None,
+        }
+    }
+
+    fn find_header(&self, name: &Path, angled: bool, next: bool) -> Option<search::Found> {
+        let file = self.files.last()?;
+        let includer = match angled {
+            true => None,
+            false => Some((file.dir.as_path(), file.system)),
+        };
+        let start = match next && self.files.len() > 1 {
+            true => Some(self.search.next_after(file.found_at)),
+            false => None,
+        };
+        self.search.find(name, includer, start)
+    }
+
+    fn include(&mut self, hash: &PpToken, directive: &PpToken, rest: Vec<PpToken>, next_line: u32) {
+        let tokens = match rest.first().map(|t| t.kind) {
+            Some(Kind::HeaderName | Kind::StringLit) => rest,
+            _ => {
+                let mut input = Input::tokens(rest);
+                let mut expanded = vec![];
+                while let Some(token) = self.expand_next(&mut input) {
+                    if !token.is_padding() {
+                        expanded.push(token);
+                    }
+                }
+                expanded
+            }
+        };
+        let Some((name, angled)) = self.header_name(&tokens) else {
+            let message = format!("#{} expects \"FILENAME\" or <FILENAME>", directive.text);
+            self.error(
+                "include-syntax",
+                tokens.first().unwrap_or(directive),
+                &message,
+            );
+            return;
+        };
+        let at = tokens.first().expect("there was a header name");
+        let used = match at.kind {
+            Kind::Punct => tokens.iter().position(|t| t.is(">")).map_or(1, |i| i + 1),
+            _ => 1,
+        };
+        self.extra_tokens(directive, &tokens[used..]);
+        if name.is_empty() {
+            let message = format!("empty filename in #{}", directive.text);
+            self.error("include-syntax", at, &message);
+            return;
+        }
+        if self.parsing != Parsing::Text {
+            let message = format!(
+                "#{} may not be used inside a macro argument",
+                directive.text
+            );
+            self.error("include-syntax", directive, &message);
+            return;
+        }
+        if self.files.len() > MAX_INCLUDE_DEPTH {
+            let message = format!(
+                "#include nested depth {} exceeds maximum of {MAX_INCLUDE_DEPTH}",
+                self.files.len()
+            );
+            self.error("include-depth", directive, &message);
+            return;
+        }
+
+        let next = &*directive.text == "include_next";
+        if next && self.files.len() == 1 {
+            self.warning(
+                "include-next",
+                directive,
+                "#include_next in primary source file",
+            );
+        }
+        let at = self.span(at);
+        self.include_file(Path::new(&name), angled, next, at, hash.line, next_line);
+    }
+
+    /// read the header `name` as `#include` does, `at` is where it was named, the output
+    /// goes back to `return_line` after it
+    fn include_file(
+        &mut self,
+        name: &Path,
+        angled: bool,
+        next: bool,
+        at: Span,
+        include_line: u32,
+        return_line: u32,
+    ) {
+        let Some(found) = self.find_header(name, angled, next) else {
+            let message = format!("{}: No such file or directory", name.display());
+            let diagnostic = Diagnostic::fatal("missing-include", &message).with_span(at);
+            self.diagnostics.report(diagnostic);
+            self.fatal = true;
+            return;
+        };
+        let canonical = fs::canonicalize(&found.path).unwrap_or_else(|_| found.path.clone());
+        let guarded = self.guards.get(&canonical);
+        if self.once.contains(&canonical) || guarded.is_some_and(|g| self.macros.contains_key(g)) {
+            return;
+        }
+        let text = match fs::read(&found.path) {
+            Ok(bytes) => match decode(&found.name, bytes, self.diagnostics) {
+                Some(text) => text,
+                None => return,
+            },
+            Err(e) => {
+                let message = format!("{}: {}", found.name, io_message(&e));
+                let diagnostic = Diagnostic::fatal("missing-include", &message).with_span(at);
+                self.diagnostics.report(diagnostic);
+                self.fatal = true;
+                return;
+            }
+        };
+
+        let name: Rc<str> = found.name.as_str().into();
+        self.output.enter_file(include_line, &name, found.system);
+        self.push_file(
+            name,
+            &text,
+            &found.path,
+            found.system,
+            found.index,
+            return_line,
+        );
+    }
+
+    fn define(&mut self, directive: &PpToken, rest: &[PpToken]) {
+        let file = self.file_name();
+        let (name, defined) = match macros::parse_define(directive, rest, &file) {
+            Ok(defined) => defined,
+            Err((at, message)) => {
+                self.error("invalid-define", &at, &message);
+                return;
+            }
+        };
+        let at = &rest[0];
+        if self.poisoned.contains(&name) {
+            let message = format!("attempt to use poisoned \"{name}\"");
+            self.error("poisoned-identifier", at, &message);
+            return;
+        }
+        if let Some(previous) = self.macros.get(&name) {
+            if previous.builtin.is_some() {
+                let message = format!("redefining builtin macro \"{name}\"");
+                self.warning("macro-redefined", at, &message);
+            } else if !previous.same_definition(&defined) {
+                let message = format!("\"{name}\" redefined");
+                let note = "this is the location of the previous definition";
+                let diagnostic = self
+                    .located(Diagnostic::warning("macro-redefined", &message), at)
+                    .with_label(previous.span(), note);
+                self.diagnostics.report(diagnostic);
+            }
+        }
+        self.macros.insert(name, Rc::new(defined));
+    }
+
+    fn undef(&mut self, directive: &PpToken, rest: &[PpToken]) {
+        let Some(name) = rest.first().filter(|t| t.kind == Kind::Identifier) else {
+            let message = match rest.first() {
+                Some(_) => "macro names must be identifiers",
+                None => "no macro name given in #undef directive",
+            };
+            self.error(
+                "invalid-directive",
+                rest.first().unwrap_or(directive),
+                message,
+            );
+            return;
+        };
+        self.extra_tokens(directive, &rest[1..]);
+        if &*name.text == "defined" {
+            self.error(
+                "invalid-directive",
+                name,
+                "\"defined\" cannot be used as a macro name",
+            );
+            return;
+        }
+        if let Some(previous) = self.macros.remove(&name.text) {
+            if previous.builtin.is_some() {
+                let message = format!("undefining \"{}\"", name.text);
+                self.warning("undef-builtin", name, &message);
+            }
+        }
+    }
+
+    /// `#line` (C11 6.10.4), or the linemarkers gcc writes when `expand` is false
+    fn line_directive(
+        &mut self,
+        directive: &PpToken,
+        rest: Vec<PpToken>,
+        next_line: u32,
+        expand: bool,
+    ) {
+        let tokens = match expand {
+            true => {
+                let mut input = Input::tokens(rest);
+                let mut expanded = vec![];
+                while let Some(token) = self.expand_next(&mut input) {
+                    if !token.is_padding() {
+                        expanded.push(token);
+                    }
+                }
+                expanded
+            }
+            false => rest,
+        };
+
+        let Some(number) = tokens.first() else {
+            self.error(
+                "invalid-line",
+                directive,
+                "unexpected end of file after #line",
+            );
+            return;
+        };
+        let line = match number.text.parse::<u32>() {
+            Ok(line) if number.text.bytes().all(|b| b.is_ascii_digit()) => line,
+            _ => {
+                let message = format!("\"{}\" after #line is not a positive integer", number.text);
+                self.error("invalid-line", number, &message);
+                return;
+            }
+        };
+        let name = match tokens.get(1) {
+            None => None,
+            Some(literal) => match destringize(literal).filter(|_| literal.text.starts_with('"')) {
+                Some(name) => Some(name),
+                None => {
+                    let message = format!("invalid filename \"{}\"", literal.text);
+                    self.error("invalid-line", literal, &message);
+                    return;
+                }
+            },
+        };
+        let mut system = None;
+        match expand {
+            true => self.extra_tokens(directive, tokens.get(2..).unwrap_or_default()),
+            false => {
+                for flag in tokens.iter().skip(2) {
+                    match &*flag.text {
+                        "1" | "2" | "4" => {}
+                        "3" => system = Some(SystemHeader::Yes),
+                        _ => {
+                            let message =
+                                format!("invalid flag \"{}\" in line directive", flag.text);
+                            self.error("invalid-line", flag, &message);
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
+        let file = self
+            .files
+            .last_mut()
+            .expect("directives are read from a file");
+        file.line_delta += line as i64 - next_line as i64;
+        if let Some(name) = name {
+            file.name = name.into();
+        }
+        if let Some(system) = system {
+            file.system = system;
+        }
+        let (name, system) = (file.name.clone(), file.system);
+        if self.parsing == Parsing::Text {
+            self.output.rename(line, &name, system);
+        }
+    }
+
+    /// `_Pragma ( string-literal )`, C11 6.10.9
+    fn pragma_operator(&mut self, operator: &PpToken) {
+        let mut tokens = vec![];
+        while tokens.len() < 3 {
+            match self.expand_next(&mut Input::Source) {
+                Some(token) if token.is_padding() => {}
+                Some(token) => tokens.push(token),
+                None => break,
+            }
+        }
+        let text = match &tokens[..] {
+            [open, literal, close] if open.is("(") && close.is(")") => destringize(literal),
+            _ => None,
+        };
+        let Some(text) = text else {
+            self.error(
+                "invalid-pragma",
+                operator,
+                "_Pragma takes a parenthesized string literal",
+            );
+            return;
+        };
+        let (lines, _) = source::lex(&text, &self.lang);
+        let mut tokens: Vec<PpToken> = lines.into_iter().flat_map(|line| line.tokens).collect();
+        let (line, column) = operator.position();
+        for token in &mut tokens {
+            token.line = line;
+            token.column = column;
+        }
+        self.pragma(operator, &tokens, true);
+    }
+
+    /// the pragmas the preprocessor acts on are consumed, the rest are left in the output
+    /// for the compiler
+    fn pragma(&mut self, at: &PpToken, tokens: &[PpToken], operator: bool) {
+        let names: Vec<&str> = tokens.iter().take(2).map(|t| &*t.text).collect();
+        let consumed = match &names[..] {
+            ["once", ..] => {
+                self.pragma_once(at);
+                true
+            }
+            ["GCC", "poison"] => {
+                self.poison(&tokens[2..]);
+                true
+            }
+            ["GCC", "system_header"] => {
+                self.system_header(at);
+                true
+            }
+            ["GCC", "dependency"] => true,
+            ["GCC", kind @ ("warning" | "error")] => {
+                let message = tokens.get(2).and_then(destringize);
+                let message = message.unwrap_or_else(|| spelling(&tokens[2..]));
+                match *kind {
+                    "warning" => self.warning("pragma-warning", at, &message),
+                    _ => self.error("pragma-error", at, &message),
+                }
+                true
+            }
+            [kind @ ("push_macro" | "pop_macro"), ..] => {
+                self.push_pop_macro(kind == &"push_macro", at, &tokens[1..]);
+                true
+            }
+            _ => false,
+        };
+
+        if self.parsing != Parsing::Text {
+            return;
+        }
+        match (consumed, operator) {
+            (true, true) => {}
+            (true, false) => {
+                if let Some(first) = tokens.first() {
+                    self.output.line_change(first);
+                }
+            }
+            (false, true) => self
+                .output
+                .operator_pragma(at.position().0, &spelling(tokens)),
+            (false, false) => self.output.pragma(at.line, tokens),
+        }
+    }
+
+    fn pragma_once(&mut self, at: &PpToken) {
+        if self.files.len() == 1 {
+            self.warning("pragma-once-main", at, "#pragma once in main file");
+        }
+        let file = self.files.last().expect("pragmas are read from a file");
+        self.once.insert(file.canonical.clone());
+    }
+
+    fn poison(&mut self, tokens: &[PpToken]) {
+        for token in tokens {
+            if token.kind != Kind::Identifier {
+                self.error(
+                    "invalid-pragma",
+                    token,
+                    "invalid #pragma GCC poison directive",
+                );
+                return;
+            }
+            if self.macros.contains_key(&token.text) {
+                let message = format!("poisoning existing macro \"{}\"", token.text);
+                self.warning("poisoned-identifier", token, &message);
+            }
+            self.poisoned.insert(token.text.clone());
+        }
+    }
+
+    fn system_header(&mut self, at: &PpToken) {
+        if self.files.len() == 1 {
+            let message = "#pragma system_header ignored outside include file";
+            self.warning("invalid-pragma", at, message);
+            return;
+        }
+        let file = self.files.last_mut().expect("pragmas are read from a file");
+        file.system = SystemHeader::Yes;
+        let (name, line) = (file.name.clone(), at.line + 1);
+        if self.parsing == Parsing::Text {
+            self.output.rename(line, &name, SystemHeader::Yes);
+        }
+    }
+
+    fn push_pop_macro(&mut self, push: bool, at: &PpToken, tokens: &[PpToken]) {
+        let name = match tokens {
+            [open, literal, close] if open.is("(") && close.is(")") => destringize(literal),
+            _ => None,
+        };
+        let Some(name) = name else {
+            let message = match push {
+                true => "invalid #pragma push_macro directive",
+                false => "invalid #pragma pop_macro directive",
+            };
+            self.error("invalid-pragma", at, message);
+            return;
+        };
+        let name: Rc<str> = name.into();
+        if push {
+            let current = self.macros.get(&name).cloned();
+            self.pushed.entry(name).or_default().push(current);
+        } else if let Some(previous) = self.pushed.get_mut(&name).and_then(Vec::pop) {
+            match previous {
+                Some(defined) => self.macros.insert(name, defined),
+                None => self.macros.remove(&name),
+            };
+        }
+    }
+}
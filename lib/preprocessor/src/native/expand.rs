@@ -0,0 +1,392 @@
+use std::{collections::VecDeque, rc::Rc};
+
+use diagnostics::{Diagnostic, Expansion, Span};
+
+use super::{
+    macros::{self, Builtin, Macro},
+    token::{Kind, PpToken},
+    Preprocessor,
+};
+
+/// where macro expansion takes its tokens from, the source files or a list of tokens such
+/// as a macro argument or the rest of a directive
+pub enum Input {
+    Source,
+    Tokens(VecDeque<PpToken>),
+}
+
+impl Input {
+    pub fn tokens(tokens: impl IntoIterator<Item = PpToken>) -> Self {
+        Input::Tokens(tokens.into_iter().collect())
+    }
+}
+
+/// what the source lexer is doing for a macro invocation, the same states as gcc's
+/// `parsing_args` as they change how a token starting a line is treated
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Parsing {
+    Text,
+    /// looking for the `(` after the name of a function-like macro
+    Paren,
+    /// collecting the arguments, where a newline acts as whitespace
+    Arguments,
+}
+
+fn union(hide: &Rc<[Rc<str>]>, with: &[Rc<str>]) -> Rc<[Rc<str>]> {
+    let mut union = hide.to_vec();
+    union.extend(with.iter().filter(|h| !hide.contains(h)).cloned());
+    union.into()
+}
+
+impl<'d> Preprocessor<'d> {
+    pub(super) fn next_raw(&mut self, input: &mut Input) -> Option<PpToken> {
+        match input {
+            Input::Tokens(tokens) => tokens.pop_front(),
+            Input::Source => match self.pending.pop_front() {
+                // a token starting a line which was looked at to see if it was the `(` of an
+                // invocation starts its line of output when it is read again, as in gcc
+                Some(token) if token.bol && self.parsing == Parsing::Text => {
+                    self.output.line_change(&token);
+                    Some(token)
+                }
+                Some(token) => Some(token),
+                None => self.source_token(),
+            },
+        }
+    }
+
+    fn push_back(&mut self, input: &mut Input, tokens: Vec<PpToken>) {
+        let queue = match input {
+            Input::Tokens(tokens) => tokens,
+            Input::Source => &mut self.pending,
+        };
+        for token in tokens.into_iter().rev() {
+            queue.push_front(token);
+        }
+    }
+
+    /// the next token from `input` once any macros have been expanded
+    pub(super) fn expand_next(&mut self, input: &mut Input) -> Option<PpToken> {
+        loop {
+            let token = self.next_raw(input)?;
+            if !self.try_expand(&token, input) {
+                return Some(token);
+            }
+        }
+    }
+
+    /// expand `name` if it is a macro, putting the result back on `input` to be rescanned
+    /// (C11 6.10.3.4), false if it isn't expanded
+    pub(super) fn try_expand(&mut self, name: &PpToken, input: &mut Input) -> bool {
+        if name.kind != Kind::Identifier || name.hidden(&name.text) {
+            return false;
+        }
+        let Some(defined) = self.macros.get(&name.text).cloned() else {
+            return false;
+        };
+
+        let mut expansion = match defined.builtin {
+            Some(builtin) => vec![self.builtin(builtin, name)],
+            None if defined.is_function_like() => match self.invocation(name, &defined, input) {
+                Some(expansion) => expansion,
+                None => return false,
+            },
+            None => {
+                let hide = union(&name.hide, std::slice::from_ref(&name.text));
+                self.substitute(name, &defined, &[], hide)
+            }
+        };
+
+        // the expansion takes the place of the name, so is spaced from what went before the
+        // way the name was, which `#` sees when the expansion ends up in an argument
+        if let Some(first) = expansion.iter_mut().find(|t| !t.is_padding()) {
+            first.white = name.white;
+        }
+        let mut tokens = Vec::with_capacity(expansion.len() + 2);
+        tokens.push(PpToken::padding(Some(name.white)));
+        tokens.extend(expansion);
+        tokens.push(PpToken::padding(None));
+        self.push_back(input, tokens);
+        true
+    }
+
+    fn builtin(&mut self, builtin: Builtin, name: &PpToken) -> PpToken {
+        let string = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let (kind, text) = match builtin {
+            Builtin::File => (Kind::StringLit, string(&self.file_name())),
+            Builtin::BaseFile => (Kind::StringLit, string(&self.base_file)),
+            Builtin::Line => (Kind::Number, name.position().0.to_string()),
+            Builtin::Counter => {
+                self.counter += 1;
+                (Kind::Number, (self.counter - 1).to_string())
+            }
+            Builtin::IncludeLevel => (Kind::Number, (self.files.len().max(1) - 1).to_string()),
+            Builtin::Date | Builtin::Time => {
+                let (date, time) = self
+                    .date_time
+                    .get_or_insert_with(super::builtins::date_and_time);
+                match builtin {
+                    Builtin::Date => (Kind::StringLit, date.clone()),
+                    _ => (Kind::StringLit, time.clone()),
+                }
+            }
+        };
+        let mut token = PpToken::synthesised(kind, &text, name);
+        token.expanded_at = Some(name.position());
+        token
+    }
+
+    /// collect the arguments of a function-like macro and substitute them, `None` if it
+    /// isn't invoked, having put back whatever we looked at to find that out
+    fn invocation(
+        &mut self,
+        name: &PpToken,
+        defined: &Macro,
+        input: &mut Input,
+    ) -> Option<Vec<PpToken>> {
+        let parsing = self.parsing;
+        self.parsing = Parsing::Paren;
+        let mut padding = None;
+        let next = loop {
+            match self.next_raw(input) {
+                Some(token) if token.is_padding() => {
+                    padding.get_or_insert(token);
+                }
+                other => break other,
+            }
+        };
+        let invoked = next.as_ref().is_some_and(|t| t.is("("));
+        if !invoked {
+            self.parsing = parsing;
+            self.push_back(input, padding.into_iter().chain(next).collect());
+            return None;
+        }
+
+        self.parsing = Parsing::Arguments;
+        let collected = self.collect_arguments(name, defined, input);
+        self.parsing = parsing;
+        let (args, rparen) = collected?;
+
+        // Prosser's algorithm, only what was hidden for both the name and the closing paren
+        // stays hidden along with the macro itself
+        let mut hide: Vec<Rc<str>> = name
+            .hide
+            .iter()
+            .filter(|h| rparen.hide.contains(h))
+            .cloned()
+            .collect();
+        hide.push(name.text.clone());
+        Some(self.substitute(name, defined, &args, hide.into()))
+    }
+
+    /// the arguments of an invocation and the `)` ending it, reporting a wrong number of
+    /// them or running out of tokens first
+    fn collect_arguments(
+        &mut self,
+        name: &PpToken,
+        defined: &Macro,
+        input: &mut Input,
+    ) -> Option<(Vec<Vec<PpToken>>, PpToken)> {
+        let params = defined
+            .params
+            .as_ref()
+            .expect("only called for function-like macros");
+        let mut args: Vec<Vec<PpToken>> = vec![vec![]];
+        let mut depth = 0;
+        let rparen = loop {
+            let Some(token) = self.next_raw(input) else {
+                let message = format!(
+                    "unterminated argument list invoking macro \"{}\"",
+                    name.text
+                );
+                self.error("unterminated-arguments", name, &message);
+                return None;
+            };
+            // the arguments for `...` include the commas between them
+            let splits = !(defined.variadic && args.len() == params.len());
+            let arg = args.last_mut().expect("always at least one argument");
+            match token {
+                _ if token.is_padding() && arg.is_empty() => {}
+                _ if token.is("(") => {
+                    depth += 1;
+                    arg.push(token);
+                }
+                _ if token.is(")") && depth == 0 => break token,
+                _ if token.is(")") => {
+                    depth -= 1;
+                    arg.push(token);
+                }
+                _ if token.is(",") && depth == 0 && splits => args.push(vec![]),
+                _ => arg.push(token),
+            }
+        };
+        for arg in &mut args {
+            while arg.last().is_some_and(|t| t.is_padding()) {
+                arg.pop();
+            }
+        }
+
+        let given = match (params.len(), &args[..]) {
+            // `f()` passes one empty argument, which is no arguments to a macro taking none
+            (0, [arg]) if arg.is_empty() => 0,
+            _ => args.len(),
+        };
+        if given == params.len() || (defined.variadic && given + 1 == params.len()) {
+            args.resize(params.len(), vec![]);
+            return Some((args, rparen));
+        }
+        let message = if given < params.len() {
+            format!(
+                "macro \"{}\" requires {} arguments, but only {} given",
+                name.text,
+                params.len(),
+                given
+            )
+        } else {
+            format!(
+                "macro \"{}\" passed {} arguments, but takes just {}",
+                name.text,
+                given,
+                params.len()
+            )
+        };
+        let note = format!("macro \"{}\" defined here", name.text);
+        let diagnostic = self
+            .located(Diagnostic::error("macro-arguments", &message), &rparen)
+            .with_label(defined.span(), &note);
+        self.diagnostics.report(diagnostic);
+        None
+    }
+
+    /// fully macro expand an argument on its own, C11 6.10.3.1
+    fn expand_argument(&mut self, arg: &[PpToken]) -> Vec<PpToken> {
+        let parsing = self.parsing;
+        self.parsing = Parsing::Text;
+        let mut input = Input::tokens(arg.iter().cloned());
+        let mut expanded = vec![];
+        while let Some(token) = self.expand_next(&mut input) {
+            expanded.push(token);
+        }
+        self.parsing = parsing;
+        expanded
+    }
+
+    /// the replacement list of `defined` with its parameters replaced by `args` and any
+    /// `##` operators applied (C11 6.10.3.1 to 6.10.3.3), with paddings around the
+    /// arguments so the output spaces them the way gcc does
+    fn substitute(
+        &mut self,
+        name: &PpToken,
+        defined: &Macro,
+        args: &[Vec<PpToken>],
+        hide: Rc<[Rc<str>]>,
+    ) -> Vec<PpToken> {
+        let mut expanded: Vec<Option<Vec<PpToken>>> = vec![None; args.len()];
+        let mut out: Vec<PpToken> = vec![];
+        // the previous item was followed by `##` and whether it was empty
+        let mut paste = false;
+        let mut left_empty = false;
+
+        for (i, item) in defined.replacement.iter().enumerate() {
+            let raw = |p: usize| -> Vec<PpToken> {
+                args[p]
+                    .iter()
+                    .filter(|t| !t.is_padding())
+                    .cloned()
+                    .collect()
+            };
+
+            // the GNU extension, `, ## __VA_ARGS__` drops the comma when there are no
+            // variable arguments rather than pasting anything
+            let after_comma = i > 0 && {
+                let previous = &defined.replacement[i - 1];
+                previous.param.is_none() && previous.token.is(",")
+            };
+            if let (true, true, Some(p)) = (paste && after_comma, defined.variadic, item.param) {
+                if p + 1 == args.len() && !item.stringify {
+                    let tokens = raw(p);
+                    if tokens.is_empty() {
+                        out.pop();
+                    }
+                    left_empty = tokens.is_empty();
+                    out.extend(tokens);
+                    paste = item.paste_left;
+                    continue;
+                }
+            }
+
+            let mut tokens = match item.param {
+                Some(p) if item.stringify => vec![macros::stringify(&args[p], &item.token)],
+                Some(p) if paste || item.paste_left => {
+                    let mut tokens = vec![];
+                    if i > 0 && !paste {
+                        tokens.push(PpToken::padding(Some(item.token.white)));
+                    }
+                    tokens.extend(raw(p));
+                    tokens
+                }
+                Some(p) => {
+                    let arg = match &expanded[p] {
+                        Some(arg) => arg.clone(),
+                        None => {
+                            let arg = self.expand_argument(&args[p]);
+                            expanded[p] = Some(arg.clone());
+                            arg
+                        }
+                    };
+                    let mut tokens = vec![];
+                    if i > 0 {
+                        tokens.push(PpToken::padding(Some(item.token.white)));
+                    }
+                    tokens.extend(arg);
+                    tokens.push(PpToken::padding(None));
+                    tokens
+                }
+                None => vec![item.token.clone()],
+            };
+
+            let empty = tokens.iter().all(|t| t.is_padding());
+            if paste && !left_empty && !empty {
+                let left = out.pop().expect("the left operand isn't empty");
+                let first = tokens
+                    .iter()
+                    .position(|t| !t.is_padding())
+                    .expect("not empty");
+                let right = tokens.remove(first);
+                match macros::paste(&left, &right, &self.lang) {
+                    Some(pasted) => out.push(pasted),
+                    None => {
+                        let message = format!(
+                            "pasting \"{}\" and \"{}\" does not give a valid preprocessing token",
+                            left.text, right.text
+                        );
+                        self.error("invalid-paste", name, &message);
+                        out.push(left);
+                        out.push(right);
+                    }
+                }
+                left_empty = false;
+            } else if !paste || !empty {
+                left_empty = empty;
+            }
+            out.extend(tokens);
+            paste = item.paste_left;
+        }
+
+        let at = name.position();
+        let invocation = Span::new(&name.file, name.line, name.column as usize, name.text.len());
+        let expansion = Rc::new(Expansion::new(
+            &name.text,
+            invocation,
+            defined.span(),
+            name.expansion.clone(),
+        ));
+        for token in &mut out {
+            token.hide = union(&token.hide, &hide);
+            token.expanded_at = Some(at);
+            token.expansion = Some(Expansion::within(token.expansion.as_ref(), &expansion));
+            token.bol = false;
+        }
+        out
+    }
+}
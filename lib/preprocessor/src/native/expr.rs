@@ -0,0 +1,399 @@
+use super::token::{Kind, PpToken};
+
+/// a value in a `#if` expression, C11 6.10.1p4 has every integer act as `intmax_t` or
+/// `uintmax_t` which are both 64 bits here
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Value {
+    bits: u64,
+    unsigned: bool,
+}
+
+impl Value {
+    fn signed(v: i64) -> Self {
+        Value {
+            bits: v as u64,
+            unsigned: false,
+        }
+    }
+
+    fn boolean(b: bool) -> Self {
+        Value::signed(b as i64)
+    }
+
+    pub fn is_true(&self) -> bool {
+        self.bits != 0
+    }
+}
+
+/// a problem with an expression, at the token it was found at
+pub type ExprError = (Option<PpToken>, String);
+
+/// evaluate the controlling expression of a `#if` or `#elif`, the tokens have already been
+/// macro expanded with `defined` replaced by its value, any identifiers left are 0
+pub fn evaluate(tokens: &[PpToken], directive: &str) -> Result<Value, ExprError> {
+    let tokens: Vec<&PpToken> = tokens.iter().filter(|t| !t.is_padding()).collect();
+    if tokens.is_empty() {
+        return Err((None, format!("#{directive} with no expression")));
+    }
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        evaluating: true,
+    };
+    let value = parser.comma()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(t) if t.is(")") => Err((Some(t.clone()), "missing '(' in expression".to_string())),
+        Some(t) => {
+            let message = format!("missing binary operator before token \"{}\"", t.text);
+            Err((Some(t.clone()), message))
+        }
+    }
+}
+
+struct Parser<'t> {
+    tokens: Vec<&'t PpToken>,
+    pos: usize,
+    /// false in the operands which short circuiting skips, where dividing by zero is fine
+    evaluating: bool,
+}
+
+/// the binary operators by precedence, loosest first
+const BINARY: [&[&str]; 8] = [
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+impl<'t> Parser<'t> {
+    fn peek(&self) -> Option<&'t PpToken> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, punct: &str) -> Option<&'t PpToken> {
+        match self.peek() {
+            Some(t) if t.is(punct) => {
+                self.pos += 1;
+                Some(t)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, punct: &str, message: &str) -> Result<(), ExprError> {
+        match self.eat(punct) {
+            Some(_) => Ok(()),
+            None => Err((self.peek().cloned(), message.to_string())),
+        }
+    }
+
+    /// evaluate the operand with `evaluating` set to `evaluate`, for short circuiting
+    fn operand(
+        &mut self,
+        evaluate: bool,
+        parse: impl FnOnce(&mut Self) -> Result<Value, ExprError>,
+    ) -> Result<Value, ExprError> {
+        let was = self.evaluating;
+        self.evaluating = was && evaluate;
+        let value = parse(self);
+        self.evaluating = was;
+        value
+    }
+
+    fn comma(&mut self) -> Result<Value, ExprError> {
+        let mut value = self.conditional()?;
+        while self.eat(",").is_some() {
+            value = self.conditional()?;
+        }
+        Ok(value)
+    }
+
+    fn conditional(&mut self) -> Result<Value, ExprError> {
+        let condition = self.logical_or()?;
+        if self.eat("?").is_none() {
+            return Ok(condition);
+        }
+        let taken = condition.is_true();
+        let then = self.operand(taken, Self::comma)?;
+        self.expect(":", "'?' without following ':'")?;
+        let otherwise = self.operand(!taken, Self::conditional)?;
+        let unsigned = then.unsigned || otherwise.unsigned;
+        let bits = if taken { then.bits } else { otherwise.bits };
+        Ok(Value { bits, unsigned })
+    }
+
+    fn logical_or(&mut self) -> Result<Value, ExprError> {
+        let mut value = self.logical_and()?;
+        while self.eat("||").is_some() {
+            let skip = value.is_true();
+            let right = self.operand(!skip, Self::logical_and)?;
+            value = Value::boolean(skip || right.is_true());
+        }
+        Ok(value)
+    }
+
+    fn logical_and(&mut self) -> Result<Value, ExprError> {
+        let mut value = self.binary(0)?;
+        while self.eat("&&").is_some() {
+            let evaluate = value.is_true();
+            let right = self.operand(evaluate, |p| p.binary(0))?;
+            value = Value::boolean(evaluate && right.is_true());
+        }
+        Ok(value)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Value, ExprError> {
+        if level == BINARY.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        loop {
+            let Some(op) = BINARY[level].iter().find_map(|op| self.eat(op)) else {
+                return Ok(left);
+            };
+            let right = self.binary(level + 1)?;
+            left = self.apply(op, left, right)?;
+        }
+    }
+
+    fn apply(&self, op: &PpToken, left: Value, right: Value) -> Result<Value, ExprError> {
+        let unsigned = left.unsigned || right.unsigned;
+        let (l, r) = (left.bits, right.bits);
+        let arithmetic = |bits| Value { bits, unsigned };
+        let compare = |ordering: std::cmp::Ordering| match unsigned {
+            true => ordering,
+            false => (l as i64).cmp(&(r as i64)),
+        };
+        let value = match &*op.text {
+            "|" => arithmetic(l | r),
+            "^" => arithmetic(l ^ r),
+            "&" => arithmetic(l & r),
+            "==" => Value::boolean(l == r),
+            "!=" => Value::boolean(l != r),
+            "<" => Value::boolean(compare(l.cmp(&r)).is_lt()),
+            ">" => Value::boolean(compare(l.cmp(&r)).is_gt()),
+            "<=" => Value::boolean(compare(l.cmp(&r)).is_le()),
+            ">=" => Value::boolean(compare(l.cmp(&r)).is_ge()),
+            "<<" | ">>" => {
+                // shifting by a negative amount shifts the other way, as gcc does
+                let left_shift = (&*op.text == "<<") != (!right.unsigned && (r as i64) < 0);
+                let by = match right.unsigned {
+                    true => r,
+                    false => (r as i64).unsigned_abs(),
+                };
+                let bits = match (left_shift, left.unsigned) {
+                    _ if by >= 64 && !left_shift && !left.unsigned && (l as i64) < 0 => u64::MAX,
+                    _ if by >= 64 => 0,
+                    (true, _) => l << by,
+                    (false, true) => l >> by,
+                    (false, false) => ((l as i64) >> by) as u64,
+                };
+                Value {
+                    bits,
+                    unsigned: left.unsigned,
+                }
+            }
+            "+" => arithmetic(l.wrapping_add(r)),
+            "-" => arithmetic(l.wrapping_sub(r)),
+            "*" => arithmetic(l.wrapping_mul(r)),
+            "/" | "%" if r == 0 => match self.evaluating {
+                true => return Err((Some(op.clone()), "division by zero in #if".to_string())),
+                false => arithmetic(0),
+            },
+            "/" if unsigned => arithmetic(l / r),
+            "/" => arithmetic((l as i64).wrapping_div(r as i64) as u64),
+            "%" if unsigned => arithmetic(l % r),
+            "%" => arithmetic((l as i64).wrapping_rem(r as i64) as u64),
+            _ => unreachable!("only called with the operators in BINARY"),
+        };
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Value, ExprError> {
+        if self.eat("+").is_some() {
+            return self.unary();
+        }
+        if self.eat("-").is_some() {
+            let value = self.unary()?;
+            return Ok(Value {
+                bits: value.bits.wrapping_neg(),
+                ..value
+            });
+        }
+        if self.eat("~").is_some() {
+            let value = self.unary()?;
+            return Ok(Value {
+                bits: !value.bits,
+                ..value
+            });
+        }
+        if self.eat("!").is_some() {
+            let value = self.unary()?;
+            return Ok(Value::boolean(!value.is_true()));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Value, ExprError> {
+        let Some(token) = self.peek() else {
+            let at = self.tokens.last().map(|t| (*t).clone());
+            return Err((at, "#if with no expression".to_string()));
+        };
+        self.pos += 1;
+        match token.kind {
+            _ if token.is("(") => {
+                let value = self.comma()?;
+                self.expect(")", "missing ')' in expression")?;
+                Ok(value)
+            }
+            Kind::Number => number(token),
+            Kind::CharConst => character(token),
+            Kind::Identifier => Ok(Value::signed(0)),
+            Kind::Punct => {
+                let message = format!("operator '{}' has no left operand", token.text);
+                Err((Some(token.clone()), message))
+            }
+            _ => {
+                let message = format!(
+                    "token \"{}\" is not valid in preprocessor expressions",
+                    token.text
+                );
+                Err((Some(token.clone()), message))
+            }
+        }
+    }
+}
+
+/// an integer constant (C11 6.4.4.1), floating constants aren't allowed
+fn number(token: &PpToken) -> Result<Value, ExprError> {
+    let text = token.text.to_ascii_lowercase();
+    let error = |message: &str| Err((Some(token.clone()), message.to_string()));
+
+    let digits_end = text.find(['u', 'l']).unwrap_or(text.len());
+    let (digits, suffix) = text.split_at(digits_end);
+    let unsigned_suffix = match suffix {
+        "" | "l" | "ll" => false,
+        "u" | "ul" | "lu" | "ull" | "llu" => true,
+        _ => {
+            let message = format!("invalid suffix \"{suffix}\" on integer constant");
+            return error(&message);
+        }
+    };
+
+    let (radix, digits) = match digits {
+        _ if digits.starts_with("0x") => (16, &digits[2..]),
+        _ if digits.starts_with("0b") => (2, &digits[2..]),
+        _ if digits.starts_with('0') && digits.len() > 1 => (8, &digits[1..]),
+        _ => (10, digits),
+    };
+    if radix != 16 && digits.contains(['.', 'e']) || radix == 16 && digits.contains(['.', 'p']) {
+        return error("floating constant in preprocessor expression");
+    }
+    if digits.is_empty() && radix != 8 {
+        let message = format!("invalid integer constant \"{}\" in #if", token.text);
+        return error(&message);
+    }
+    match u64::from_str_radix(digits, radix) {
+        // C11 6.4.4.1p5, a decimal constant without a `u` is signed or doesn't fit, gcc
+        // treats the octal and hex ones which don't fit in `intmax_t` as unsigned
+        Ok(bits) => Ok(Value {
+            bits,
+            unsigned: unsigned_suffix || (radix != 10 && bits > i64::MAX as u64),
+        }),
+        Err(e) if *e.kind() == std::num::IntErrorKind::PosOverflow => {
+            error("integer constant is too large for its type")
+        }
+        Err(_) => {
+            let message = format!("invalid integer constant \"{}\" in #if", token.text);
+            error(&message)
+        }
+    }
+}
+
+/// a character constant (C11 6.4.4.4), as an `int` for plain ones or the element type for
+/// prefixed ones, multiple characters combine as gcc does
+fn character(token: &PpToken) -> Result<Value, ExprError> {
+    let text = &*token.text;
+    let quote = text
+        .find('\'')
+        .expect("character constants contain a quote");
+    let (prefix, body) = text.split_at(quote);
+    let body = &body[1..body.len() - 1];
+    let width = match prefix {
+        "" => 8,
+        "u" => 16,
+        _ => 32,
+    };
+
+    let mut chars = body.chars().peekable();
+    let mut values = vec![];
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            values.push(c as u64);
+            continue;
+        }
+        let escaped = chars.next().unwrap_or('\\');
+        let value = match escaped {
+            'n' => 10,
+            't' => 9,
+            'r' => 13,
+            'a' => 7,
+            'b' => 8,
+            'f' => 12,
+            'v' => 11,
+            'e' | 'E' => 27,
+            'x' => {
+                let mut value = 0u64;
+                while let Some(d) = chars.peek().and_then(|c| c.to_digit(16)) {
+                    value = value.wrapping_mul(16) + d as u64;
+                    chars.next();
+                }
+                value
+            }
+            'u' | 'U' => {
+                let n = if escaped == 'u' { 4 } else { 8 };
+                let hex: String = chars.by_ref().take(n).collect();
+                u64::from_str_radix(&hex, 16).unwrap_or(0)
+            }
+            '0'..='7' => {
+                let mut value = escaped.to_digit(8).unwrap_or(0) as u64;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => {
+                            value = value * 8 + d as u64;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                value
+            }
+            other => other as u64,
+        };
+        values.push(value);
+    }
+
+    if values.is_empty() {
+        return Err((Some(token.clone()), "empty character constant".to_string()));
+    }
+    let mask = (1u64 << width) - 1;
+    let value = match prefix {
+        // plain `char` is signed here and a multi-character constant packs the characters
+        // into an `int`, both as gcc does
+        "" => {
+            let packed = values.iter().fold(0u64, |acc, v| (acc << 8) | (v & 0xff));
+            match values.len() {
+                1 => packed as u8 as i8 as i64,
+                _ => packed as u32 as i32 as i64,
+            }
+        }
+        "L" => *values.last().expect("not empty") as u32 as i32 as i64,
+        _ => (*values.last().expect("not empty") & mask) as i64,
+    };
+    Ok(Value::signed(value))
+}
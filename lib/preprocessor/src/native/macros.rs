@@ -0,0 +1,292 @@
+use std::rc::Rc;
+
+use diagnostics::Span;
+use lang::LangOptions;
+
+use super::token::{Kind, PpToken};
+
+/// the macros whose value depends on where they are used
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Builtin {
+    File,
+    Line,
+    Date,
+    Time,
+    Counter,
+    IncludeLevel,
+    BaseFile,
+}
+
+/// a token of a replacement list, C11 6.10.3
+#[derive(Debug, PartialEq, Clone)]
+pub struct Replacement {
+    pub token: PpToken,
+    /// which parameter this token names, if it names one
+    pub param: Option<usize>,
+    /// the token was preceded by `#` so is replaced by its argument as a string literal,
+    /// `token` is then the `#` itself
+    pub stringify: bool,
+    /// the token is followed by `##` so is pasted onto the one after it
+    pub paste_left: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Macro {
+    /// `None` for object-like macros
+    pub params: Option<Vec<Rc<str>>>,
+    /// the last parameter is `...`, named `__VA_ARGS__` in `params`
+    pub variadic: bool,
+    pub replacement: Vec<Replacement>,
+    pub builtin: Option<Builtin>,
+    /// where it was defined, for diagnostics
+    pub file: Rc<str>,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Macro {
+    pub fn builtin(builtin: Builtin) -> Self {
+        Macro {
+            params: None,
+            variadic: false,
+            replacement: vec![],
+            builtin: Some(builtin),
+            file: "<built-in>".into(),
+            line: 0,
+            column: 0,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        Span::point(&self.file, self.line, self.column as usize)
+    }
+
+    pub fn is_function_like(&self) -> bool {
+        self.params.is_some()
+    }
+
+    /// C11 6.10.3p2, two definitions are the same if they have the same parameters and
+    /// replacement lists, whitespace only matters as to whether there is some
+    pub fn same_definition(&self, other: &Macro) -> bool {
+        let same_tokens = |a: &Replacement, b: &Replacement| {
+            a.token.text == b.token.text
+                && a.token.white == b.token.white
+                && a.param == b.param
+                && a.stringify == b.stringify
+                && a.paste_left == b.paste_left
+        };
+        self.params == other.params
+            && self.variadic == other.variadic
+            && self.builtin == other.builtin
+            && self.replacement.len() == other.replacement.len()
+            && self
+                .replacement
+                .iter()
+                .zip(&other.replacement)
+                .all(|(a, b)| same_tokens(a, b))
+    }
+}
+
+/// a problem with a `#define`, at the token it was found at
+pub type DefineError = (PpToken, String);
+
+/// parse the tokens of a `#define` following the `define` itself
+pub fn parse_define(
+    define: &PpToken,
+    tokens: &[PpToken],
+    file: &Rc<str>,
+) -> Result<(Rc<str>, Macro), DefineError> {
+    let Some(name) = tokens.first() else {
+        return Err((
+            define.clone(),
+            "no macro name given in #define directive".to_string(),
+        ));
+    };
+    if name.kind != Kind::Identifier {
+        return Err((name.clone(), "macro names must be identifiers".to_string()));
+    }
+    if &*name.text == "defined" {
+        return Err((
+            name.clone(),
+            "\"defined\" cannot be used as a macro name".to_string(),
+        ));
+    }
+
+    let mut rest = &tokens[1..];
+    let mut params = None;
+    let mut variadic = false;
+    if rest.first().is_some_and(|t| t.is("(") && !t.white) {
+        let (names, is_variadic, after) = parse_params(name, &rest[1..])?;
+        params = Some(names);
+        variadic = is_variadic;
+        rest = after;
+    }
+
+    let replacement = parse_replacement(rest, params.as_deref(), variadic)?;
+
+    let defined = Macro {
+        params,
+        variadic,
+        replacement,
+        builtin: None,
+        file: file.clone(),
+        line: name.line,
+        column: name.column,
+    };
+    Ok((name.text.clone(), defined))
+}
+
+/// the parameter names, whether the last is `...` and the tokens after the closing paren
+type Params<'t> = (Vec<Rc<str>>, bool, &'t [PpToken]);
+
+fn parse_params<'t>(name: &PpToken, tokens: &'t [PpToken]) -> Result<Params<'t>, DefineError> {
+    let mut names: Vec<Rc<str>> = vec![];
+    let mut i = 0;
+    let missing = |at: &PpToken| {
+        (
+            at.clone(),
+            "missing ')' in macro parameter list".to_string(),
+        )
+    };
+    if tokens.first().is_some_and(|t| t.is(")")) {
+        return Ok((names, false, &tokens[1..]));
+    }
+    loop {
+        let Some(param) = tokens.get(i) else {
+            return Err(missing(tokens.last().unwrap_or(name)));
+        };
+        if param.is("...") {
+            names.push("__VA_ARGS__".into());
+            return match tokens.get(i + 1) {
+                Some(close) if close.is(")") => Ok((names, true, &tokens[i + 2..])),
+                Some(other) => Err(missing(other)),
+                None => Err(missing(param)),
+            };
+        }
+        if param.kind != Kind::Identifier {
+            let message = format!("expected parameter name, found \"{}\"", param.text);
+            return Err((param.clone(), message));
+        }
+        if &*param.text == "__VA_ARGS__" {
+            let message = "__VA_ARGS__ can not be used as a parameter name".to_string();
+            return Err((param.clone(), message));
+        }
+        if names.contains(&param.text) {
+            let message = format!("duplicate macro parameter \"{}\"", param.text);
+            return Err((param.clone(), message));
+        }
+        names.push(param.text.clone());
+        match tokens.get(i + 1) {
+            Some(t) if t.is(",") => i += 2,
+            Some(t) if t.is(")") => return Ok((names, false, &tokens[i + 2..])),
+            Some(t) => {
+                let message = format!("expected ',' or ')', found \"{}\"", t.text);
+                return Err((t.clone(), message));
+            }
+            None => return Err(missing(param)),
+        }
+    }
+}
+
+fn parse_replacement(
+    tokens: &[PpToken],
+    params: Option<&[Rc<str>]>,
+    variadic: bool,
+) -> Result<Vec<Replacement>, DefineError> {
+    let param_index = |t: &PpToken| match (t.kind, params) {
+        (Kind::Identifier, Some(params)) => params.iter().position(|p| *p == t.text),
+        _ => None,
+    };
+
+    let mut replacement: Vec<Replacement> = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut token = tokens[i].clone();
+        token.bol = false;
+        if replacement.is_empty() {
+            token.white = false;
+        }
+
+        if token.is("##") {
+            let at_end = i + 1 == tokens.len();
+            match replacement.last_mut() {
+                Some(previous) if !at_end => previous.paste_left = true,
+                _ => {
+                    let message = "'##' cannot appear at either end of a macro expansion";
+                    return Err((token, message.to_string()));
+                }
+            }
+            i += 1;
+            continue;
+        }
+
+        if token.is("#") && params.is_some() {
+            match tokens.get(i + 1).and_then(param_index) {
+                Some(param) => {
+                    replacement.push(Replacement {
+                        token,
+                        param: Some(param),
+                        stringify: true,
+                        paste_left: false,
+                    });
+                    i += 2;
+                    continue;
+                }
+                None => {
+                    let message = "'#' is not followed by a macro parameter";
+                    return Err((token, message.to_string()));
+                }
+            }
+        }
+
+        if token.is_identifier("__VA_ARGS__") && !variadic {
+            let message = "__VA_ARGS__ can only appear in the expansion of a C99 variadic macro";
+            return Err((token, message.to_string()));
+        }
+
+        replacement.push(Replacement {
+            param: param_index(&token),
+            token,
+            stringify: false,
+            paste_left: false,
+        });
+        i += 1;
+    }
+    Ok(replacement)
+}
+
+/// C11 6.10.3.2, the spelling of `arg` as a string literal
+pub fn stringify(arg: &[PpToken], at: &PpToken) -> PpToken {
+    let mut spelling = String::from("\"");
+    for (i, token) in arg.iter().filter(|t| !t.is_padding()).enumerate() {
+        if i > 0 && token.white {
+            spelling.push(' ');
+        }
+        match token.kind {
+            Kind::StringLit | Kind::CharConst => {
+                for c in token.text.chars() {
+                    if c == '"' || c == '\\' {
+                        spelling.push('\\');
+                    }
+                    spelling.push(c);
+                }
+            }
+            _ => spelling.push_str(&token.text),
+        }
+    }
+    spelling.push('"');
+    PpToken::synthesised(Kind::StringLit, &spelling, at)
+}
+
+/// C11 6.10.3.3, `left ## right` as a single token, `None` if that isn't a valid token
+pub fn paste(left: &PpToken, right: &PpToken, lang: &LangOptions) -> Option<PpToken> {
+    let spelling = format!("{}{}", left.text, right.text);
+    let (lines, problems) = super::source::lex(&spelling, lang);
+    match (&lines[..], &problems[..]) {
+        ([line], []) if line.tokens.len() == 1 => {
+            let pasted = &line.tokens[0];
+            Some(PpToken::synthesised(pasted.kind, &pasted.text, left))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,266 @@
+use std::rc::Rc;
+
+use diagnostics::Expansions;
+
+use crate::Preprocessed;
+
+use super::{
+    token::{avoid_paste, Kind, PpToken},
+    COMMAND_LINE,
+};
+
+/// the flags gcc puts on a linemarker to say the file is a system header, `ExternC` is what
+/// the default system directories get
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SystemHeader {
+    No,
+    Yes,
+    ExternC,
+}
+
+impl SystemHeader {
+    fn flags(&self) -> &'static str {
+        match self {
+            SystemHeader::No => "",
+            SystemHeader::Yes => " 3",
+            SystemHeader::ExternC => " 3 4",
+        }
+    }
+}
+
+/// writes the preprocessed tokens out in the same layout `gcc -E` does, so each token keeps
+/// its line (using blank lines or linemarkers to get there) and spaces only go where the
+/// source had them or where two tokens would otherwise lex as one
+pub struct Output {
+    text: String,
+    newline: &'static str,
+    file: Rc<str>,
+    system: SystemHeader,
+    /// the line the next thing written is on
+    src_line: u32,
+    /// something has been written on the current line
+    printed: bool,
+    /// a padding token has been seen since the last token, so check whether a space is needed
+    avoid_paste: bool,
+    /// whether the source of a padding token had whitespace before it
+    source: Option<bool>,
+    prev: Option<PpToken>,
+    /// the next token starts a line of its own, after a `_Pragma` took the line it was on
+    line_change_next: bool,
+    /// the last token written came from a system header
+    prev_was_system: bool,
+    /// the expansions of the tokens written so far which came from macros
+    expansions: Expansions,
+}
+
+impl Output {
+    pub fn new(file: &Rc<str>, newline: &'static str) -> Self {
+        let mut output = Output {
+            text: String::new(),
+            newline,
+            file: COMMAND_LINE.into(),
+            system: SystemHeader::No,
+            src_line: 0,
+            printed: false,
+            avoid_paste: false,
+            source: None,
+            prev: None,
+            line_change_next: false,
+            prev_was_system: false,
+            expansions: Expansions::new(),
+        };
+        output.marker(0, file, "");
+        output.marker(0, &"<built-in>".into(), "");
+        output.marker(0, &COMMAND_LINE.into(), "");
+        output
+    }
+
+    /// start on the main file, after the directives from the command line
+    pub fn main_file(&mut self, file: &Rc<str>) {
+        self.file = file.clone();
+        self.system = SystemHeader::No;
+        self.print_line(1, "");
+    }
+
+    pub fn finish(mut self) -> Preprocessed {
+        if self.printed {
+            self.newline();
+        }
+        Preprocessed {
+            text: self.text,
+            expansions: self.expansions,
+            dependencies: vec![],
+        }
+    }
+
+    fn newline(&mut self) {
+        self.text.push_str(self.newline);
+    }
+
+    fn marker(&mut self, line: u32, file: &Rc<str>, flags: &str) {
+        let escaped = file.replace('\\', "\\\\").replace('"', "\\\"");
+        self.text
+            .push_str(&format!("# {line} \"{escaped}\"{flags}"));
+        self.newline();
+    }
+
+    /// a linemarker putting the output at `line` of the current file
+    fn print_line(&mut self, line: u32, flags: &str) {
+        if self.printed {
+            self.newline();
+        }
+        self.printed = false;
+        let file = self.file.clone();
+        let flags = format!("{flags}{}", self.system.flags());
+        self.marker(line, &file, &flags);
+        self.src_line = line;
+    }
+
+    /// move the output to `line`, with blank lines if it isn't far off or a linemarker if so
+    fn maybe_print_line(&mut self, line: u32) {
+        if self.printed {
+            self.newline();
+            self.src_line += 1;
+            self.printed = false;
+        }
+        if line >= self.src_line && line < self.src_line + 8 {
+            while self.src_line < line {
+                self.newline();
+                self.src_line += 1;
+            }
+        } else {
+            self.print_line(line, "");
+        }
+    }
+
+    /// start a line of output for the first token of a source line, indenting it to about
+    /// where it was
+    pub fn line_change(&mut self, token: &PpToken) {
+        let (line, column) = token.position();
+        self.maybe_print_line(line);
+        self.prev = None;
+        self.source = None;
+        for _ in 2..column {
+            self.text.push(' ');
+        }
+        self.printed = true;
+    }
+
+    pub fn enter_file(&mut self, include_line: u32, file: &Rc<str>, system: SystemHeader) {
+        self.maybe_print_line(include_line);
+        self.file = file.clone();
+        self.system = system;
+        self.print_line(1, " 1");
+    }
+
+    pub fn leave_file(&mut self, line: u32, file: &Rc<str>, system: SystemHeader) {
+        self.file = file.clone();
+        self.system = system;
+        self.print_line(line, " 2");
+    }
+
+    /// a `#line` directive or `#pragma GCC system_header` changed the presumed position
+    pub fn rename(&mut self, line: u32, file: &Rc<str>, system: SystemHeader) {
+        self.file = file.clone();
+        self.system = system;
+        self.print_line(line, "");
+    }
+
+    /// the output is on `line` of the file after the writing something which ended a line
+    /// itself, like a pragma
+    fn line_written(&mut self) {
+        self.newline();
+        self.printed = false;
+        self.src_line += 1;
+    }
+
+    /// a `#pragma` we leave for the compiler, on its own line
+    pub fn pragma(&mut self, line: u32, tokens: &[PpToken]) {
+        self.maybe_print_line(line);
+        self.text.push_str("#pragma ");
+        self.spelling(tokens);
+        self.line_written();
+    }
+
+    /// the pragma from a `_Pragma` operator on `line`, which goes on its own line between
+    /// linemarkers putting the output back where it was
+    pub fn operator_pragma(&mut self, line: u32, text: &str) {
+        self.print_line(line, "");
+        self.text.push_str("#pragma ");
+        self.text.push_str(text);
+        self.newline();
+        self.print_line(line, "");
+        self.line_change_next = true;
+    }
+
+    pub fn ident(&mut self, line: u32, tokens: &[PpToken]) {
+        self.maybe_print_line(line);
+        self.text.push_str("#ident ");
+        self.spelling(tokens);
+        self.line_written();
+    }
+
+    fn spelling(&mut self, tokens: &[PpToken]) {
+        for (i, token) in tokens.iter().filter(|t| !t.is_padding()).enumerate() {
+            if i > 0 && token.white {
+                self.text.push(' ');
+            }
+            self.text.push_str(&token.text);
+        }
+    }
+
+    pub fn token(&mut self, token: &PpToken) {
+        if token.kind == Kind::Padding {
+            self.avoid_paste = true;
+            if self.source.is_none()
+                || (self.source == Some(false) && token.padding_white.is_none())
+            {
+                self.source = token.padding_white;
+            }
+            return;
+        }
+
+        if self.line_change_next {
+            self.line_change_next = false;
+            self.line_change(token);
+        }
+
+        let (line, _) = token.position();
+        let mut line_changed = false;
+        if line != self.src_line {
+            // the token didn't start its line, like those after a macro invocation which
+            // spanned lines, so it starts its own
+            self.line_change(token);
+            self.text.push(' ');
+            line_changed = true;
+        } else if self.avoid_paste {
+            let white = self.source.unwrap_or(token.white);
+            let pastes = match &self.prev {
+                Some(prev) => avoid_paste(prev, token),
+                None => token.is("#"),
+            };
+            if white || pastes {
+                self.text.push(' ');
+            }
+        } else if token.white {
+            self.text.push(' ');
+        }
+
+        // going between system headers and the rest gets a linemarker so the compiler
+        // knows whether to warn about the tokens which follow
+        let system = self.system != SystemHeader::No;
+        if !line_changed && self.prev_was_system != system {
+            self.line_change(token);
+        }
+        self.prev_was_system = system;
+
+        self.avoid_paste = false;
+        self.source = None;
+        self.prev = Some(token.clone());
+        if let Some(expansion) = &token.expansion {
+            self.expansions.insert(self.text.len(), expansion.clone());
+        }
+        self.text.push_str(&token.text);
+        self.printed = true;
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::output::SystemHeader;
+
+/// a directory `#include` looks in
+#[derive(Debug, PartialEq, Clone)]
+pub struct SearchDir {
+    pub path: PathBuf,
+    pub system: SystemHeader,
+}
+
+/// the directories searched by `#include`, `quote` only for `#include "..."` and then
+/// `bracket` for both forms
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SearchPath {
+    pub quote: Vec<SearchDir>,
+    pub bracket: Vec<SearchDir>,
+}
+
+/// a header found by searching for it
+#[derive(Debug, PartialEq, Clone)]
+pub struct Found {
+    pub path: PathBuf,
+    /// the name it is known by in linemarkers and diagnostics
+    pub name: String,
+    pub system: SystemHeader,
+    /// where in the search path it was found, `#include_next` carries on after that
+    pub index: Option<usize>,
+}
+
+impl SearchPath {
+    /// the directories gcc searches by default, with the highest version of gcc's own
+    /// headers (for `stddef.h` and friends) as we don't know which gcc we'd be run with
+    pub fn system_default() -> Self {
+        let gcc = Path::new("/usr/lib/gcc/x86_64-linux-gnu");
+        let mut bracket = vec![];
+        if let Some(version) = highest_version(gcc) {
+            bracket.push(gcc.join(version).join("include"));
+        }
+        bracket.push(PathBuf::from("/usr/local/include"));
+        bracket.push(PathBuf::from("/usr/include/x86_64-linux-gnu"));
+        bracket.push(PathBuf::from("/usr/include"));
+        SearchPath {
+            quote: vec![],
+            bracket: bracket
+                .into_iter()
+                .map(|path| SearchDir {
+                    path,
+                    system: SystemHeader::ExternC,
+                })
+                .collect(),
+        }
+    }
+
+    fn dirs(&self) -> impl Iterator<Item = &SearchDir> {
+        self.quote.iter().chain(&self.bracket)
+    }
+
+    /// look for `name` from `#include`, `includer` is the directory of the file doing the
+    /// including for quoted names (C11 6.10.2p3), `start` is where in the search path to
+    /// start for `#include_next`
+    pub fn find(
+        &self,
+        name: &Path,
+        includer: Option<(&Path, SystemHeader)>,
+        start: Option<usize>,
+    ) -> Option<Found> {
+        if name.is_absolute() {
+            let path = name.to_path_buf();
+            return path.is_file().then(|| Found {
+                path,
+                name: name.to_string_lossy().into_owned(),
+                system: SystemHeader::No,
+                index: None,
+            });
+        }
+
+        if let Some((dir, system)) = includer.filter(|_| start.is_none()) {
+            let path = dir.join(name);
+            if path.is_file() {
+                return Some(Found {
+                    name: display(dir, name),
+                    path,
+                    system,
+                    index: None,
+                });
+            }
+        }
+
+        let skip = match (includer, start) {
+            (_, Some(start)) => start,
+            (Some(_), None) => 0,
+            (None, None) => self.quote.len(),
+        };
+        self.dirs().enumerate().skip(skip).find_map(|(index, dir)| {
+            let path = dir.path.join(name);
+            path.is_file().then(|| Found {
+                name: display(&dir.path, name),
+                path,
+                system: dir.system,
+                index: Some(index),
+            })
+        })
+    }
+
+    /// where `#include_next` starts searching from a file found at `index`, a file which
+    /// wasn't found through the search path starts at the bracket directories
+    pub fn next_after(&self, index: Option<usize>) -> usize {
+        index.map_or(self.quote.len(), |index| index + 1)
+    }
+}
+
+fn display(dir: &Path, name: &Path) -> String {
+    let name = name.to_string_lossy();
+    match dir.to_str() {
+        Some("") | None => name.to_string(),
+        Some(dir) if dir.ends_with('/') => format!("{dir}{name}"),
+        Some(dir) => format!("{dir}/{name}"),
+    }
+}
+
+/// the largest of the numerically named directories in `dir`
+fn highest_version(dir: &Path) -> Option<String> {
+    let version = |name: &str| -> Option<Vec<u32>> {
+        name.split('.').map(|part| part.parse().ok()).collect()
+    };
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| Some((version(&name)?, name)))
+        .max()
+        .map(|(_, name)| name)
+}
@@ -0,0 +1,378 @@
+use std::rc::Rc;
+
+use lang::{LangOptions, Standard};
+
+use super::token::{Kind, PpToken};
+
+/// a logical line (C11 5.1.1.2 phases 1 to 3), a block comment spanning physical lines
+/// joins them into one logical line just as a backslash-newline does
+#[derive(Debug, PartialEq, Clone)]
+pub struct Line {
+    pub tokens: Vec<PpToken>,
+    /// the physical lines the logical line spans
+    pub first: u32,
+    pub last: u32,
+}
+
+impl Line {
+    pub fn is_directive(&self) -> bool {
+        self.tokens.first().is_some_and(|t| t.is("#"))
+    }
+}
+
+/// a problem found while splitting a file into tokens, reported when the line is used so
+/// nothing is reported from groups skipped by `#if`
+#[derive(Debug, PartialEq, Clone)]
+pub struct LexProblem {
+    pub line: u32,
+    pub column: u32,
+    pub error: bool,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// a character of a file after line splicing, with where it physically was
+#[derive(Debug, Clone, Copy)]
+struct Spliced {
+    c: char,
+    line: u32,
+    column: u32,
+}
+
+/// phases 1 and 2: drop the carriage returns from CRLF line endings and splice lines ending
+/// in a backslash, like gcc we allow whitespace between the backslash and the newline
+fn splice(text: &str, problems: &mut Vec<LexProblem>) -> Vec<Spliced> {
+    let mut chars = vec![];
+    for (i, physical) in text.split('\n').enumerate() {
+        let line = i as u32 + 1;
+        let physical = physical.strip_suffix('\r').unwrap_or(physical);
+        let mut column = 1;
+        for c in physical.chars() {
+            chars.push(Spliced { c, line, column });
+            column += c.len_utf8() as u32;
+        }
+        chars.push(Spliced {
+            c: '\n',
+            line,
+            column,
+        });
+
+        // a backslash, then maybe some whitespace, then the newline we just pushed
+        let ws = chars
+            .iter()
+            .rev()
+            .skip(1)
+            .take_while(|s| s.line == line && matches!(s.c, ' ' | '\t'))
+            .count();
+        let Some(backslash) = chars.len().checked_sub(ws + 2) else {
+            continue;
+        };
+        if chars[backslash].c == '\\' && chars[backslash].line == line {
+            if ws > 0 {
+                problems.push(LexProblem {
+                    line,
+                    column: chars[backslash].column,
+                    error: false,
+                    code: "backslash-newline-space",
+                    message: "backslash and newline separated by space".to_string(),
+                });
+            }
+            chars.truncate(backslash);
+        }
+    }
+    // split gives us a newline after the last line even when the file doesn't end in one
+    if text.ends_with('\n') {
+        chars.pop();
+    }
+    chars
+}
+
+const PUNCTUATORS: [&str; 54] = [
+    "%:%:", "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+    "*=", "/=", "%=", "+=", "-=", "&=", "^=", "|=", "##", "<:", ":>", "<%", "%>", "%:", "[", "]",
+    "(", ")", "{", "}", ".", "&", "*", "+", "-", "~", "!", "/", "%", "<", ">", "^", "|", "?", ":",
+    ";", "=", ",", "#",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$' || !c.is_ascii()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    is_ident_start(c) || c.is_ascii_digit()
+}
+
+struct Lexer {
+    chars: Vec<Spliced>,
+    pos: usize,
+    problems: Vec<LexProblem>,
+    lang: LangOptions,
+    /// a `//` comment has been reported as not allowed, as gcc does they are still
+    /// comments and it is only reported once
+    reported_line_comment: bool,
+}
+
+impl Lexer {
+    fn peek(&self, n: usize) -> Option<char> {
+        self.chars.get(self.pos + n).map(|s| s.c)
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars().enumerate().all(|(i, c)| self.peek(i) == Some(c))
+    }
+
+    fn take(&mut self, n: usize) -> String {
+        let taken = self.chars[self.pos..self.pos + n]
+            .iter()
+            .map(|s| s.c)
+            .collect();
+        self.pos += n;
+        taken
+    }
+
+    fn problem(&mut self, at: Spliced, error: bool, code: &'static str, message: &str) {
+        self.problems.push(LexProblem {
+            line: at.line,
+            column: at.column,
+            error,
+            code,
+            message: message.to_string(),
+        });
+    }
+
+    /// skips whitespace and comments up to the next token or newline, true if any was skipped
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some(' ' | '\t' | '\x0b' | '\x0c' | '\r'), _) => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    if !self.lang.line_comments() && !self.reported_line_comment {
+                        let at = self.chars[self.pos];
+                        self.problem(
+                            at,
+                            true,
+                            "line-comment",
+                            "C++ style comments are not allowed in ISO C90",
+                        );
+                        self.reported_line_comment = true;
+                    }
+                    while !matches!(self.peek(0), Some('\n') | None) {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let open = self.chars[self.pos];
+                    self.pos += 2;
+                    loop {
+                        match (self.peek(0), self.peek(1)) {
+                            (Some('*'), Some('/')) => {
+                                self.pos += 2;
+                                break;
+                            }
+                            (Some(_), _) => self.pos += 1,
+                            (None, _) => {
+                                self.problem(
+                                    open,
+                                    true,
+                                    "unterminated-comment",
+                                    "unterminated comment",
+                                );
+                                break;
+                            }
+                        }
+                    }
+                }
+                _ => return self.pos != start,
+            }
+        }
+    }
+
+    fn quoted(&mut self, prefix_len: usize, quote: char) -> (Kind, usize) {
+        let kind = match quote {
+            '"' => Kind::StringLit,
+            _ => Kind::CharConst,
+        };
+        let mut n = prefix_len + 1;
+        loop {
+            match self.peek(n) {
+                Some(c) if c == quote => return (kind, n + 1),
+                Some('\\') if !matches!(self.peek(n + 1), Some('\n') | None) => n += 2,
+                Some('\n') | None => break,
+                Some(_) => n += 1,
+            }
+        }
+        // unterminated, gcc makes the rest of the line a single token and complains about
+        // it if the line turns out to be used. A character constant is left to the lexer,
+        // which reports it as an error, so that it isn't reported twice
+        if quote == '"' {
+            let at = self.chars[self.pos + prefix_len];
+            let message = "missing terminating \" character";
+            self.problem(at, true, "missing-terminating-quote", message);
+        }
+        (Kind::Other, n)
+    }
+
+    fn header_name(&mut self) -> Option<usize> {
+        let mut n = 1;
+        loop {
+            match self.peek(n) {
+                Some('>') => return Some(n + 1),
+                Some('\n') | None => return None,
+                Some(_) => n += 1,
+            }
+        }
+    }
+
+    fn pp_number(&self) -> usize {
+        let separators = self.lang.at_least(Standard::C23);
+        let mut n = 1;
+        loop {
+            match (self.peek(n), self.peek(n + 1)) {
+                (Some('e' | 'E' | 'p' | 'P'), Some('+' | '-')) => n += 2,
+                // C23 digit separators
+                (Some('\''), Some(c)) if separators && is_ident_continue(c) => n += 2,
+                (Some(c), _) if is_ident_continue(c) || c == '.' => n += 1,
+                _ => return n,
+            }
+        }
+    }
+
+    /// the kind and length of the token at the current position, `in_include` says whether
+    /// this is the operand of an `#include` which allows a header name
+    fn token(&mut self, in_include: bool) -> (Kind, usize) {
+        let c = self.peek(0).expect("only called before the end of input");
+        let utf8_char = self.lang.at_least(Standard::C23);
+        let prefix = ["u8\"", "u8'", "u\"", "U\"", "L\"", "u'", "U'", "L'"]
+            .iter()
+            .filter(|p| utf8_char || **p != "u8'")
+            .find(|p| self.starts_with(p));
+        match c {
+            _ if prefix.is_some() => {
+                let prefix = prefix.expect("just checked");
+                let quote = prefix.chars().last().expect("prefixes end in a quote");
+                self.quoted(prefix.len() - 1, quote)
+            }
+            '"' | '\'' => self.quoted(0, c),
+            '<' if in_include => match self.header_name() {
+                Some(n) => (Kind::HeaderName, n),
+                None => (Kind::Punct, 1),
+            },
+            '.' if self.peek(1).is_some_and(|c| c.is_ascii_digit()) => {
+                (Kind::Number, self.pp_number())
+            }
+            _ if c.is_ascii_digit() => (Kind::Number, self.pp_number()),
+            _ if is_ident_start(c) => {
+                let mut n = 1;
+                while self.peek(n).is_some_and(is_ident_continue) {
+                    n += 1;
+                }
+                (Kind::Identifier, n)
+            }
+            _ => match PUNCTUATORS.iter().find(|p| self.starts_with(p)) {
+                Some(p) => (Kind::Punct, p.len()),
+                None => (Kind::Other, 1),
+            },
+        }
+    }
+}
+
+/// phases 1 to 3, split a file's text into logical lines of preprocessing tokens, along with
+/// any problems found in doing so. `lang` decides whether `//` comments, digit separators
+/// and `u8` character constants are allowed
+pub fn lex(text: &str, lang: &LangOptions) -> (Vec<Line>, Vec<LexProblem>) {
+    let mut problems = vec![];
+    let chars = splice(text, &mut problems);
+    let mut lexer = Lexer {
+        chars,
+        pos: 0,
+        problems,
+        lang: *lang,
+        reported_line_comment: false,
+    };
+
+    let mut lines = vec![];
+    let mut tokens: Vec<PpToken> = vec![];
+    let mut first = 1;
+    loop {
+        let white = lexer.skip_whitespace();
+        let at_end = lexer.pos == lexer.chars.len();
+        if at_end && tokens.is_empty() && lexer.pos > 0 && lexer.chars[lexer.pos - 1].c == '\n' {
+            // the newline ending the last line has already ended it
+            break;
+        }
+        match lexer.peek(0) {
+            None | Some('\n') => {
+                let last = match lexer.chars.get(lexer.pos) {
+                    Some(s) => s.line,
+                    None => lexer.chars.last().map_or(first, |s| s.line),
+                };
+                lines.push(Line {
+                    tokens: std::mem::take(&mut tokens),
+                    first,
+                    last,
+                });
+                if lexer.peek(0).is_none() {
+                    break;
+                }
+                lexer.pos += 1;
+                first = last + 1;
+            }
+            Some(_) => {
+                let in_include = matches!(
+                    &tokens[..],
+                    [hash, name] if hash.is("#") && matches!(&*name.text, "include" | "include_next")
+                );
+                let at = lexer.chars[lexer.pos];
+                let (kind, n) = lexer.token(in_include);
+                let text = lexer.take(n);
+                let mut token = PpToken::new(kind, &text, at.line, at.column);
+                token.white = white;
+                token.bol = tokens.is_empty();
+                tokens.push(token);
+            }
+        }
+    }
+
+    (lines, lexer.problems)
+}
+
+/// the macro guarding a header against being included twice, when all of its lines are
+/// inside `#ifndef X` (or `#if !defined X`) and the `#endif` matching it, so a later
+/// `#include` of it can be skipped while `X` is defined as gcc does
+pub fn include_guard(lines: &[Line]) -> Option<Rc<str>> {
+    let mut lines = lines.iter().filter(|l| !l.tokens.is_empty());
+    let first = lines.next()?;
+    let name = match &first.tokens[..] {
+        [hash, ifndef, name] if hash.is("#") && ifndef.is_identifier("ifndef") => name,
+        [hash, r#if, not, defined, name] | [hash, r#if, not, defined, _, name, _]
+            if hash.is("#")
+                && r#if.is_identifier("if")
+                && not.is("!")
+                && defined.is_identifier("defined") =>
+        {
+            name
+        }
+        _ => return None,
+    };
+    if name.kind != Kind::Identifier {
+        return None;
+    }
+
+    let mut depth = 1;
+    for line in lines {
+        if depth == 0 {
+            return None;
+        }
+        if !line.is_directive() {
+            continue;
+        }
+        match line.tokens.get(1).map(|t| &*t.text) {
+            Some("if" | "ifdef" | "ifndef") => depth += 1,
+            Some("else" | "elif") if depth == 1 => return None,
+            Some("endif") => depth -= 1,
+            _ => {}
+        }
+    }
+    (depth == 0).then(|| name.text.clone())
+}
@@ -0,0 +1,936 @@
+use std::fs;
+
+use diagnostics::{Diagnostics, Severity};
+use tempfile::TempDir;
+
+use lang::{LangOptions, Standard};
+
+use super::*;
+use crate::MacroOption;
+
+/// the preprocessed `main.c` made of `source`, with `headers` alongside it, and the codes
+/// of anything reported
+fn preprocess_files(source: &str, headers: &[(&str, &str)]) -> (String, Vec<&'static str>) {
+    let (output, codes) = preprocess_options(source, headers, |_| Options::default());
+
+    // drop the linemarkers every output starts with
+    let body = output.lines().skip(4).collect::<Vec<_>>().join("\n");
+    (body, codes)
+}
+
+/// as `preprocess_files` with the options `options` gives for the temp dir the files are
+/// in, all of the output is kept with that dir replaced by `DIR`
+fn preprocess_options(
+    source: &str,
+    headers: &[(&str, &str)],
+    options: impl FnOnce(&Path) -> Options,
+) -> (String, Vec<&'static str>) {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    for (name, text) in headers {
+        let path = dir.path().join(name);
+        fs::create_dir_all(dir_of(&path)).expect("Unable to create header dir for test");
+        fs::write(path, text).expect("Unable to write header for test");
+    }
+    let path = dir.path().join("main.c");
+    fs::write(&path, source).expect("Unable to write source for test");
+
+    let diagnostics = Diagnostics::new();
+    let options = options(dir.path());
+    let output = preprocess(&path, &options, SearchPath::default(), &diagnostics);
+    let codes = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+
+    let dir = dir.path().to_string_lossy();
+    (output.text().replace(&*dir, "DIR"), codes)
+}
+
+fn preprocess_str(source: &str) -> (String, Vec<&'static str>) {
+    preprocess_files(source, &[])
+}
+
+#[test]
+fn test_no_directives() {
+    let (actual, codes) = preprocess_str("int main(void) {\n    return 0;\n}\n");
+    assert_eq!("int main(void) {\n    return 0;\n}", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_comments_and_splices() {
+    let (actual, codes) = preprocess_str("int /* a */ x; // b\nint\\\n y;\n");
+    assert_eq!("int x;\nint\n y;", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_object_like() {
+    let (actual, _) = preprocess_str("#define X 1 + 2\nint x = X;\n");
+    assert_eq!("\nint x = 1 + 2;", actual);
+}
+
+#[test]
+fn test_function_like() {
+    let (actual, _) = preprocess_str("#define F(a, b) a * b\nint x = F(1 + 2, 3);\n");
+    assert_eq!("\nint x = 1 + 2 * 3;", actual);
+}
+
+#[test]
+fn test_function_like_without_parens() {
+    let (actual, _) = preprocess_str("#define F(a) a\nint F;\n");
+    assert_eq!("\nint F;", actual);
+}
+
+#[test]
+fn test_self_reference() {
+    let source = "#define foo foo + 1\n#define f(x) f(x)\nfoo f(2)\n";
+    let (actual, _) = preprocess_str(source);
+    assert_eq!("\n\nfoo + 1 f(2)", actual);
+}
+
+#[test]
+fn test_stringify() {
+    let (actual, _) = preprocess_str("#define S(a) #a\nS(  \"a\\n\"   'b'  c )\n");
+    assert_eq!("\n\"\\\"a\\\\n\\\" 'b' c\"", actual);
+}
+
+#[test]
+fn test_paste() {
+    let (actual, codes) =
+        preprocess_str("#define C(a, b) a ## b\nC(x, y) C(1, 2) C(, z) C(<, <)\n");
+    assert_eq!("\nxy 12 z <<", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_paste_invalid() {
+    let (actual, codes) = preprocess_str("#define C(a, b) a ## b\nC(+, -)\n");
+    assert_eq!("\n+ -", actual);
+    assert_eq!(vec!["invalid-paste"], codes);
+}
+
+#[test]
+fn test_rescan_after_argument_expansion() {
+    let source = "#define S(a) #a\n#define X(a) S(a)\n#define N 42\nS(N) X(N)\n";
+    let (actual, _) = preprocess_str(source);
+    assert_eq!("\n\n\n\"N\" \"42\"", actual);
+}
+
+#[test]
+fn test_variadic() {
+    let source = "#define V(...) [__VA_ARGS__]\n#define W(a, ...) a, ## __VA_ARGS__\nV() V(1, 2) W(1) W(1, 2)\n";
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!("\n\n[] [1, 2] 1 1, 2", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_wrong_argument_count() {
+    let (actual, codes) = preprocess_str("#define F(a, b) a\nF(1) F(1, 2, 3)\n");
+    assert_eq!("\nF F", actual);
+    assert_eq!(vec!["macro-arguments", "macro-arguments"], codes);
+}
+
+#[test]
+fn test_unterminated_arguments() {
+    let (_, codes) = preprocess_str("#define F(a) a\nF(1\n");
+    assert_eq!(vec!["unterminated-arguments"], codes);
+}
+
+#[test]
+fn test_avoid_paste() {
+    let (actual, _) = preprocess_str("#define M -1\n#define E\n-M +E+ x E\n");
+    assert_eq!("\n\n- -1 + + x", actual);
+}
+
+#[test]
+fn test_invocation_over_lines() {
+    let (actual, _) = preprocess_str("#define F(a, b) a + b\nint z = F(1,\n          2);\n");
+    assert_eq!("\nint z = 1 + 2\n            ;", actual);
+}
+
+#[test]
+fn test_conditionals() {
+    let source = "\
+#define A 2
+#if A * 2 == 4 && defined(A) && !defined B
+yes
+#elif 1
+no
+#else
+no
+#endif
+#ifdef B
+no
+#else
+else
+#endif
+";
+    let (actual, codes) = preprocess_str(source);
+    let lines: Vec<&str> = actual.lines().collect();
+    assert_eq!(["", "", "yes"], lines[..3]);
+    assert!(lines[3].starts_with("# 12 \""));
+    assert_eq!("else", lines[4]);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_if_arithmetic() {
+    let source = "\
+#if -1 < 0u
+no
+#endif
+#if (1 ? 2 : 3) == 2 && 'a' == 97 && 0x10 == 16 && (7 >> 1) == 3 && (1, 2) == 2
+yes
+#endif
+#if 0 && 1 / 0
+no
+#endif
+";
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!("\n\n\n\nyes", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_if_errors() {
+    let (_, codes) = preprocess_str("#if 1 / 0\n#endif\n#if\n#endif\n#if 1.0\n#endif\n");
+    assert_eq!(vec!["invalid-if", "invalid-if", "invalid-if"], codes);
+}
+
+#[test]
+fn test_long_skipped_group_gets_linemarker() {
+    let source = "#if 0\n\n\n\n\n\n\n\n\n\n#endif\nint x;\n";
+    let (actual, _) = preprocess_str(source);
+    let lines: Vec<&str> = actual.lines().collect();
+    assert!(lines[0].starts_with("# 12 \"") && lines[0].ends_with("main.c\""));
+    assert_eq!("int x;", lines[1]);
+}
+
+#[test]
+fn test_unbalanced_conditionals() {
+    let (_, codes) = preprocess_str("#endif\n#else\n#if 1\n");
+    assert_eq!(
+        vec![
+            "unbalanced-conditional",
+            "unbalanced-conditional",
+            "unterminated-conditional"
+        ],
+        codes
+    );
+}
+
+#[test]
+fn test_skipped_groups_are_not_checked() {
+    let source = "#if 0\n#bogus\n'unterminated\n#error no\n#endif\n";
+    let (_, codes) = preprocess_str(source);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_error_and_warning_directives() {
+    let diagnostics = Diagnostics::new();
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let path = dir.path().join("main.c");
+    fs::write(&path, "#warning careful\n#error stop here\n").expect("Unable to write source");
+    preprocess(
+        &path,
+        &Options::default(),
+        SearchPath::default(),
+        &diagnostics,
+    );
+
+    let reported = diagnostics.diagnostics();
+    assert_eq!(2, reported.len());
+    assert_eq!(Severity::Warning, reported[0].severity());
+    assert_eq!("#warning careful", reported[0].message());
+    assert_eq!(Severity::Error, reported[1].severity());
+    assert_eq!("#error stop here", reported[1].message());
+    assert_eq!(2, reported[1].primary().expect("has a span").line());
+}
+
+#[test]
+fn test_invalid_directive() {
+    let (_, codes) = preprocess_str("#bogus\n");
+    assert_eq!(vec!["invalid-directive"], codes);
+}
+
+#[test]
+fn test_redefinition() {
+    let source = "#define X 1\n#define X  1\n#define X 2\n";
+    let (_, codes) = preprocess_str(source);
+    assert_eq!(vec!["macro-redefined"], codes);
+}
+
+#[test]
+fn test_undef() {
+    let (actual, _) = preprocess_str("#define X 1\n#undef X\nX\n");
+    assert_eq!("\n\nX", actual);
+}
+
+#[test]
+fn test_line_directive() {
+    let (actual, _) =
+        preprocess_str("#line 100 \"other.c\"\nint l = __LINE__;\nconst char *f = __FILE__;\n");
+    assert_eq!(
+        "# 100 \"other.c\"\nint l = 100;\nconst char *f = \"other.c\";",
+        actual
+    );
+}
+
+#[test]
+fn test_include() {
+    let headers = [
+        ("a.h", "#pragma once\nint a;\n#include \"b.h\"\n"),
+        ("b.h", "int b;"),
+    ];
+    let (actual, codes) =
+        preprocess_files("#include \"a.h\"\n#include \"a.h\"\nint main;\n", &headers);
+    let lines: Vec<&str> = actual.lines().collect();
+    assert!(lines[0].starts_with("# 1 \"") && lines[0].ends_with("a.h\" 1"));
+    assert_eq!("       ", lines[1]);
+    assert_eq!("int a;", lines[2]);
+    assert!(lines[3].ends_with("b.h\" 1"));
+    assert_eq!("int b;", lines[4]);
+    assert!(lines[5].starts_with("# 4 \"") && lines[5].ends_with("a.h\" 2"));
+    assert!(lines[6].starts_with("# 2 \"") && lines[6].ends_with("main.c\" 2"));
+    assert_eq!(["", "int main;"], lines[7..]);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_include_guard() {
+    let headers = [("g.h", "#ifndef G\n#define G\nint g;\n#endif\n")];
+    let (actual, _) = preprocess_files("#include \"g.h\"\n#include \"g.h\"\n", &headers);
+    assert_eq!(1, actual.matches("g.h\" 1").count());
+    assert_eq!(1, actual.matches("int g;").count());
+}
+
+#[test]
+fn test_computed_include() {
+    let headers = [("c.h", "int c;")];
+    let (actual, codes) = preprocess_files("#define H \"c.h\"\n#include H\n", &headers);
+    assert!(actual.contains("int c;"));
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_missing_include_is_fatal() {
+    let (actual, codes) = preprocess_str("#include \"missing.h\"\nint after;\n");
+    assert_eq!(vec!["missing-include"], codes);
+    assert!(!actual.contains("int after;"));
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let path = dir.path().join("main.c");
+    fs::write(&path, b"int a;\nint \xe9\xff;\n").expect("Unable to write source");
+    let diagnostics = Diagnostics::new();
+    preprocess(&path, &Options::default(), SearchPath::default(), &diagnostics);
+
+    let reported = diagnostics.diagnostics();
+    let [invalid] = &reported[..] else {
+        panic!("expected one diagnostic, got {reported:?}");
+    };
+    assert_eq!(Severity::Error, invalid.severity());
+    let message = format!("{}: invalid UTF-8 at byte offset 11", path.display());
+    assert_eq!(message, invalid.message());
+    let span = invalid.primary().expect("has a span");
+    assert_eq!((2, 5), (span.line(), span.column()));
+
+    // nor from a header, which is then skipped
+    fs::write(&path, "#include \"a.h\"\nint after;\n").expect("Unable to write source");
+    fs::write(dir.path().join("a.h"), b"int e\xff;\n").expect("Unable to write header");
+    let diagnostics = Diagnostics::new();
+    let output = preprocess(&path, &Options::default(), SearchPath::default(), &diagnostics);
+    let codes: Vec<_> = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+    assert_eq!(vec!["invalid-utf8"], codes);
+    assert!(!output.text().contains("int e"));
+    assert!(output.text().contains("int after;"));
+}
+
+#[test]
+fn test_has_include() {
+    let headers = [("here.h", "")];
+    let source = "#if __has_include(\"here.h\") && !__has_include(<not_here.h>)\nyes\n#endif\n";
+    let (actual, codes) = preprocess_files(source, &headers);
+    assert_eq!("\nyes", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_pragmas() {
+    let source = "#pragma omp parallel\n_Pragma(\"message(\\\"hi\\\")\") int w;\n#pragma once\n";
+    let (actual, codes) = preprocess_str(source);
+    let lines: Vec<&str> = actual.lines().collect();
+    assert_eq!("#pragma omp parallel", lines[0]);
+    assert_eq!("", lines[1]);
+    assert!(lines[2].starts_with("# 2 \""));
+    assert_eq!("#pragma message(\"hi\")", lines[3]);
+    assert!(lines[4].starts_with("# 2 \""));
+    assert_eq!(format!("{}int w;", " ".repeat(27)), lines[5]);
+    assert_eq!(vec!["pragma-once-main"], codes);
+}
+
+#[test]
+fn test_poison() {
+    let (_, codes) = preprocess_str("#pragma GCC poison bad\nint bad;\n");
+    assert_eq!(vec!["poisoned-identifier"], codes);
+}
+
+#[test]
+fn test_push_pop_macro() {
+    let source = "#define X 1\n#pragma push_macro(\"X\")\n#undef X\n#define X 2\nX\n#pragma pop_macro(\"X\")\nX\n";
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!("\n\n\n\n2\n\n1", actual.replace("       ", ""));
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_predefined() {
+    let source =
+        "#if __STDC_VERSION__ == 201112L && __x86_64__ && !defined __GNUC__\nyes\n#endif\n";
+    let (actual, _) = preprocess_str(source);
+    assert_eq!("\nyes", actual);
+}
+
+/// the output for `source` as `standard`, without the linemarkers
+fn preprocess_standard(source: &str, standard: Standard) -> (String, Vec<&'static str>) {
+    let options = |_: &Path| Options {
+        lang: LangOptions::new(standard),
+        ..Options::default()
+    };
+    let (output, codes) = preprocess_options(source, &[], options);
+    let body = output.lines().skip(4).collect::<Vec<_>>().join("\n");
+    (body, codes)
+}
+
+#[test]
+fn test_stdc_version() {
+    let source = "#ifdef __STDC_VERSION__\n__STDC_VERSION__\n#else\nnone\n#endif\n";
+    for (standard, exp) in [
+        (Standard::C89, "none"),
+        (Standard::C99, "199901L"),
+        (Standard::C11, "201112L"),
+        (Standard::C17, "201710L"),
+        (Standard::C23, "202311L"),
+    ] {
+        let (actual, codes) = preprocess_standard(source, standard);
+        assert_eq!(exp, actual.trim());
+        assert!(codes.is_empty());
+    }
+}
+
+#[test]
+fn test_line_comments_in_c89() {
+    let source = "int a; // one\nint b; // two\n";
+    let (actual, codes) = preprocess_standard(source, Standard::C89);
+    // still comments, but reported the first time
+    assert_eq!("int a;\nint b;", actual);
+    assert_eq!(vec!["line-comment"], codes);
+
+    let (actual, codes) = preprocess_standard(source, Standard::C99);
+    assert_eq!("int a;\nint b;", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_digit_separators() {
+    let source = "int a = 1'000;\n";
+    let (actual, codes) = preprocess_standard(source, Standard::C23);
+    assert_eq!("int a = 1'000;", actual);
+    assert!(codes.is_empty());
+
+    // before C23 the separator starts a character constant, which the lexer reports as
+    // unterminated
+    let (actual, codes) = preprocess_standard(source, Standard::C17);
+    assert_eq!("int a = 1'000;", actual);
+    assert!(codes.is_empty());
+
+    let (_, codes) = preprocess_standard("char *s = \"abc;\n", Standard::C17);
+    assert_eq!(vec!["missing-terminating-quote"], codes);
+}
+
+#[test]
+fn test_utf8_source() {
+    let source = "#define CAF\u{c9} \"caf\u{e9}\"\nchar *\u{3b1} = CAF\u{c9};\n";
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!("\nchar *\u{3b1} = \"caf\u{e9}\";", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_counter() {
+    let (actual, _) = preprocess_str("__COUNTER__ __COUNTER__ __INCLUDE_LEVEL__\n");
+    assert_eq!("0 1 0", actual);
+}
+
+#[test]
+fn test_crlf_output() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let path = dir.path().join("main.c");
+    fs::write(&path, "int x;\r\nint y;\r\n").expect("Unable to write source");
+    let output = preprocess(
+        &path,
+        &Options::default(),
+        SearchPath::default(),
+        &Diagnostics::new(),
+    );
+    assert!(output.text().ends_with("int x;\r\nint y;\r\n"));
+}
+
+#[test]
+fn test_unterminated_comment() {
+    let (_, codes) = preprocess_str("int x; /* never closed\n");
+    assert_eq!(vec!["unterminated-comment"], codes);
+}
+
+/// the preprocessed text of `main.c` made of `source` with `headers` alongside it, its
+/// expansions and what was reported
+fn preprocess_expansions(source: &str, headers: &[(&str, &str)]) -> (Preprocessed, Diagnostics) {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    for (name, text) in headers {
+        fs::write(dir.path().join(name), text).expect("Unable to write header for test");
+    }
+    let path = dir.path().join("main.c");
+    fs::write(&path, source).expect("Unable to write source for test");
+
+    let diagnostics = Diagnostics::new();
+    let output = preprocess(
+        &path,
+        &Options::default(),
+        SearchPath::default(),
+        &diagnostics,
+    );
+    (output, diagnostics)
+}
+
+/// the (name, invocation line and column, definition line) of each expansion the token
+/// starting with `spelling` came out of, innermost first
+fn expansion_of(output: &Preprocessed, spelling: &str) -> Vec<(String, u32, usize, u32)> {
+    let offset = output
+        .text()
+        .rfind(spelling)
+        .expect("the token is in the output");
+    let Some(expansion) = output.expansions().get(offset) else {
+        return vec![];
+    };
+    expansion
+        .chain()
+        .map(|e| {
+            let invocation = e.invocation();
+            let at = (invocation.line(), invocation.column());
+            (e.name().to_string(), at.0, at.1, e.definition().line())
+        })
+        .collect()
+}
+
+#[test]
+fn test_expansion_nested() {
+    let header = "#define G y\n#define F G\n";
+    let (output, _) = preprocess_expansions("#include \"x.h\"\nint F;\n", &[("x.h", header)]);
+
+    let exp = vec![("G".to_string(), 2, 11, 1), ("F".to_string(), 2, 5, 2)];
+    assert_eq!(exp, expansion_of(&output, "y"));
+    assert_eq!(
+        Vec::<(String, u32, usize, u32)>::new(),
+        expansion_of(&output, "int")
+    );
+
+    let expansion = output.expansions().get(output.text().rfind('y').unwrap());
+    let files: Vec<&str> = expansion
+        .unwrap()
+        .chain()
+        .map(|e| e.definition().file())
+        .collect();
+    assert!(files.iter().all(|f| f.ends_with("x.h")));
+}
+
+#[test]
+fn test_expansion_arguments() {
+    let source = "#define F(a) [a]\n#define G F(x)\nF(y) G\n";
+    let (output, _) = preprocess_expansions(source, &[]);
+
+    // written in the source but passed through `F`
+    assert_eq!(vec![("F".to_string(), 3, 1, 1)], expansion_of(&output, "y"));
+    // from `G`'s replacement list then through `F`
+    let exp = vec![("F".to_string(), 2, 11, 1), ("G".to_string(), 3, 6, 2)];
+    assert_eq!(exp, expansion_of(&output, "x"));
+}
+
+#[test]
+fn test_expansion_diagnostics() {
+    let source = "#define C(a, b) a ## b\n#define D C(+, -)\nD\n";
+    let (_, diagnostics) = preprocess_expansions(source, &[]);
+
+    let reported = diagnostics.diagnostics();
+    let [invalid] = &reported[..] else {
+        panic!("expected one diagnostic, got {reported:?}");
+    };
+    assert_eq!("invalid-paste", invalid.code());
+    let labels: Vec<&str> = invalid.secondary().iter().map(|l| l.message()).collect();
+    assert_eq!(1, labels.len());
+    assert!(labels[0].starts_with("in expansion of macro \"D\" defined at "));
+    assert!(labels[0].ends_with("main.c:2"));
+}
+
+#[test]
+fn test_command_line_macros() {
+    let options = |_: &Path| Options {
+        macros: vec![
+            MacroOption::Define("X=2".to_string()),
+            MacroOption::Define("Y".to_string()),
+            MacroOption::Define("F(a)=a+1".to_string()),
+            MacroOption::Define("U".to_string()),
+            MacroOption::Undefine("U".to_string()),
+            MacroOption::Define("E=".to_string()),
+        ],
+        ..Options::default()
+    };
+    let (actual, codes) = preprocess_options("X Y F(X) U E;\n", &[], options);
+    let exp = concat!(
+        "# 0 \"DIR/main.c\"\n",
+        "# 0 \"<built-in>\"\n",
+        "# 0 \"<command-line>\"\n",
+        "# 1 \"DIR/main.c\"\n",
+        "2 1 2 +1 U ;\n",
+    );
+    assert_eq!(exp, actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_command_line_include() {
+    let options = |dir: &Path| Options {
+        macros: vec![MacroOption::Define("X".to_string())],
+        includes: vec![dir.join("first.h"), dir.join("first.h")],
+        ..Options::default()
+    };
+    let headers = [("first.h", "int first = X;\n")];
+    let (actual, codes) = preprocess_options("int main;\n", &headers, options);
+    let exp = concat!(
+        "# 0 \"DIR/main.c\"\n",
+        "# 0 \"<built-in>\"\n",
+        "# 0 \"<command-line>\"\n",
+        "# 1 \"DIR/first.h\" 1\n",
+        "int first = 1;\n",
+        "# 0 \"<command-line>\" 2\n",
+        "# 1 \"DIR/first.h\" 1\n",
+        "int first = 1;\n",
+        "# 0 \"<command-line>\" 2\n",
+        "# 1 \"DIR/main.c\"\n",
+        "int main;\n",
+    );
+    assert_eq!(exp, actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_command_line_include_quotes() {
+    // read by path, not spelt as a directive, so quotes and backslashes are just characters
+    let options = |dir: &Path| Options {
+        includes: vec![dir.join("a\"b\\c.h")],
+        ..Options::default()
+    };
+    let headers = [("a\"b\\c.h", "int quoted;\n")];
+    let (actual, codes) = preprocess_options("int main;\n", &headers, options);
+    assert!(actual.contains("int quoted;\n"), "{actual}");
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_command_line_errors() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let path = dir.path().join("main.c");
+    fs::write(&path, "int x;\n").expect("Unable to write source for test");
+    let options = Options {
+        macros: vec![MacroOption::Define("3x".to_string())],
+        includes: vec![PathBuf::from("does_not_exist.h")],
+        ..Options::default()
+    };
+
+    let diagnostics = Diagnostics::new();
+    preprocess(&path, &options, SearchPath::default(), &diagnostics);
+    let reported: Vec<(&str, String, u32)> = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|d| {
+            let span = d.primary().expect("reported at the command line");
+            (d.code(), span.file().to_string(), span.line())
+        })
+        .collect();
+    let exp = vec![
+        ("invalid-define", "<command-line>".to_string(), 0),
+        ("missing-include", "<command-line>".to_string(), 0),
+    ];
+    assert_eq!(exp, reported);
+}
+
+#[test]
+fn test_include_dirs() {
+    let options = |dir: &Path| Options {
+        include_dirs: vec![dir.join("user")],
+        system_include_dirs: vec![dir.join("sys")],
+        ..Options::default()
+    };
+    let headers = [("user/user.h", "int user;\n"), ("sys/sys.h", "int sys;\n")];
+    let source = "#include <user.h>\n#include \"sys.h\"\n";
+    let (actual, codes) = preprocess_options(source, &headers, options);
+    let exp = concat!(
+        "# 0 \"DIR/main.c\"\n",
+        "# 0 \"<built-in>\"\n",
+        "# 0 \"<command-line>\"\n",
+        "# 1 \"DIR/main.c\"\n",
+        "# 1 \"DIR/user/user.h\" 1\n",
+        "int user;\n",
+        "# 2 \"DIR/main.c\" 2\n",
+        "# 1 \"DIR/sys/sys.h\" 1 3 4\n",
+        "\n",
+        "# 1 \"DIR/sys/sys.h\" 3 4\n",
+        "int sys;\n",
+        "# 3 \"DIR/main.c\" 2\n",
+    );
+    assert_eq!(exp, actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_search_path_duplicates() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let (a, b) = (dir.path().join("a"), dir.path().join("b"));
+    let options = Options {
+        include_dirs: vec![a.clone(), b.clone(), a.clone()],
+        system_include_dirs: vec![b.clone()],
+        ..Options::default()
+    };
+    let system = SearchPath {
+        quote: vec![],
+        bracket: vec![SearchDir {
+            path: a.clone(),
+            system: SystemHeader::ExternC,
+        }],
+    };
+
+    // `a` is a system directory so the `-I` for it goes, as does the `-I` for `b` which
+    // is given with `-isystem` too
+    let exp = vec![
+        SearchDir {
+            path: b,
+            system: SystemHeader::ExternC,
+        },
+        SearchDir {
+            path: a,
+            system: SystemHeader::ExternC,
+        },
+    ];
+    assert_eq!(exp, options.search_path(system).bracket);
+}
+
+#[test]
+fn test_dependencies() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let headers = [
+        ("a.h", "#include \"b.h\"\n"),
+        ("b.h", "int b;\n"),
+        ("c.h", "int c;\n"),
+    ];
+    for (name, text) in headers {
+        fs::write(dir.path().join(name), text).expect("Unable to write header for test");
+    }
+    let path = dir.path().join("main.c");
+    let source = "#include \"a.h\"\n#include \"b.h\"\n#include \"a.h\"\n";
+    fs::write(&path, source).expect("Unable to write source for test");
+    let options = Options {
+        includes: vec![dir.path().join("c.h")],
+        ..Options::default()
+    };
+
+    let output = preprocess(&path, &options, SearchPath::default(), &Diagnostics::new());
+    let dir = dir.path().to_string_lossy();
+    let dependencies: Vec<String> = output
+        .dependencies()
+        .iter()
+        .map(|d| d.replace(&*dir, "DIR"))
+        .collect();
+    assert_eq!(
+        vec!["DIR/main.c", "DIR/c.h", "DIR/a.h", "DIR/b.h"],
+        dependencies
+    );
+}
+
+#[test]
+fn test_system_header_warnings() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let headers = [
+        ("sys/sys.h", "#define X 1\n#define X 2\n#warning from sys\n#bogus\n"),
+        ("pragma.h", "#define Y 1\n#pragma GCC system_header\n#define Y 2\n"),
+    ];
+    for (name, text) in headers {
+        let path = dir.path().join(name);
+        fs::create_dir_all(dir_of(&path)).expect("Unable to create header dir for test");
+        fs::write(path, text).expect("Unable to write header for test");
+    }
+    let path = dir.path().join("main.c");
+    let source = "#include <sys.h>\n#include \"pragma.h\"\n#define Z 1\n#define Z 2\n";
+    fs::write(&path, source).expect("Unable to write source for test");
+    let options = Options {
+        system_include_dirs: vec![dir.path().join("sys")],
+        ..Options::default()
+    };
+    let reported = |diagnostics: Diagnostics| {
+        preprocess(&path, &options, SearchPath::default(), &diagnostics);
+        diagnostics
+            .diagnostics()
+            .iter()
+            .map(|d| (d.code(), d.is_in_system_header()))
+            .collect::<Vec<_>>()
+    };
+
+    let exp = vec![
+        ("warning-directive", false),
+        ("invalid-directive", true),
+        ("macro-redefined", false),
+    ];
+    assert_eq!(exp, reported(Diagnostics::new()));
+
+    let exp = vec![
+        ("macro-redefined", true),
+        ("warning-directive", false),
+        ("invalid-directive", true),
+        ("macro-redefined", true),
+        ("macro-redefined", false),
+    ];
+    assert_eq!(exp, reported(Diagnostics::new().with_system_headers(true)));
+}
+
+#[test]
+fn test_max_errors() {
+    let dir = TempDir::new().expect("Unable to create temp dir for test");
+    let path = dir.path().join("main.c");
+    let source = "#error one\n#error two\n#error three\nint x;\n";
+    fs::write(&path, source).expect("Unable to write source for test");
+
+    let diagnostics = Diagnostics::new().with_max_errors(2);
+    let output = preprocess(&path, &Options::default(), SearchPath::default(), &diagnostics);
+    let codes: Vec<&str> = diagnostics.diagnostics().iter().map(|d| d.code()).collect();
+    assert_eq!(vec!["error-directive", "error-directive", "max-errors"], codes);
+    assert!(!output.text().contains("int x;"));
+}
+
+// the examples of C11 6.10.3.3 and 6.10.3.5, checked against what gcc gives for them
+
+#[test]
+fn test_standard_hash_hash() {
+    let source = r#"#define hash_hash # ## #
+#define mkstr(a) # a
+#define in_between(a) mkstr(a)
+#define join(c, d) in_between(c hash_hash d)
+char p[] = join(x, y);
+char q[] = join(2, y);
+"#;
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!(
+        "\n\n\n\nchar p[] = \"x ## y\";\nchar q[] = \"2 ## y\";",
+        actual
+    );
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_standard_example_3() {
+    let source = r#"#define x 3
+#define f(a) f(x * (a))
+#undef x
+#define x 2
+#define g f
+#define z z[0]
+#define h g(~
+#define m(a) a(w)
+#define w 0,1
+#define t(a) a
+#define p() int
+#define q(x) x
+#define r(x,y) x ## y
+#define str(x) # x
+f(y+1) + f(f(z)) % t(t(g)(0) + t)(1);
+g(x+(3,4)-w) | h 5) & m
+(f)^m(m);
+p() i[q()] = { q(1), r(2,3), r(4,), r(,5), r(,) };
+char c[2][6] = { str(hello), str() };
+"#;
+    let expected = r#"# 15 "DIR/main.c"
+f(2 * (y+1)) + f(2 * (f(2 * (z[0])))) % f(2 * (0)) + t(1);
+f(2 * (2 +(3,4)-0,1)) | f(2 * (~ 5)) & f(2 * (0,1))
+   ^m(0,1);
+int i[] = { 1, 23, 4, 5, };
+char c[2][6] = { "hello", "" };"#;
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!(expected, actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_standard_example_4() {
+    let source = r#"#define str(s) # s
+#define xstr(s) str(s)
+#define debug(s, t) printf("x" # s "= %d, x" # t "= %s", \
+ x ## s, x ## t)
+#define INCFILE(n) vers ## n
+#define glue(a, b) a ## b
+#define xglue(a, b) glue(a, b)
+#define HIGHLOW "hello"
+#define LOW LOW ", world"
+debug(1, 2);
+fputs(str(strncmp("abc\0d", "abc", '\4') // this goes away
+ == 0) str(: @\n), s);
+#include xstr(INCFILE(2).h)
+glue(HIGH, LOW);
+xglue(HIGH, LOW)
+"#;
+    let expected = r#"# 10 "DIR/main.c"
+printf("x" "1" "= %d, x" "2" "= %s", x1, x2);
+fputs("strncmp(\"abc\\0d\", \"abc\", '\\4') == 0"
+       ": @\n", s);
+# 1 "DIR/vers2.h" 1
+int v2;
+# 14 "DIR/main.c" 2
+"hello";
+"hello" ", world""#;
+    let (actual, codes) = preprocess_files(source, &[("vers2.h", "int v2;\n")]);
+    assert_eq!(expected, actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_standard_example_5() {
+    let source = r#"#define t(x,y,z) x ## y ## z
+int j[] = { t(1,2,3), t(,4,5), t(6,,7), t(8,9,),
+ t(10,,), t(,11,), t(,,12), t(,,) };
+"#;
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!("\nint j[] = { 123, 45, 67, 89,\n 10, 11, 12, };", actual);
+    assert!(codes.is_empty());
+}
+
+#[test]
+fn test_standard_example_7() {
+    let source = r#"#define debug(...) fprintf(stderr, __VA_ARGS__)
+#define showlist(...) puts(#__VA_ARGS__)
+#define report(test, ...) ((test)?puts(#test):\
+ printf(__VA_ARGS__))
+debug("Flag");
+debug("X = %d\n", x);
+showlist(The first, second, and third items.);
+report(x>y, "x is %d but y is %d", x, y);
+"#;
+    let expected = r#"
+
+
+
+fprintf(stderr, "Flag");
+fprintf(stderr, "X = %d\n", x);
+puts("The first, second, and third items.");
+((x>y)?puts("x>y"): printf("x is %d but y is %d", x, y));"#;
+    let (actual, codes) = preprocess_str(source);
+    assert_eq!(expected, actual);
+    assert!(codes.is_empty());
+}
@@ -0,0 +1,161 @@
+use std::rc::Rc;
+
+use diagnostics::Expansion;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Kind {
+    Identifier,
+    Number,
+    CharConst,
+    StringLit,
+    /// `<stdio.h>`, only lexed as the operand of `#include`
+    HeaderName,
+    Punct,
+    /// a character which can't start any other token (including an unmatched quote)
+    Other,
+    /// marks the edges of a macro expansion so the output knows where tokens may need a
+    /// space between them to stop them pasting together, carries nothing itself
+    Padding,
+}
+
+/// a preprocessing token (C11 6.4), `line` is the presumed line so it already accounts
+/// for any `#line` directives
+#[derive(Debug, PartialEq, Clone)]
+pub struct PpToken {
+    pub kind: Kind,
+    pub text: Rc<str>,
+    /// the (presumed) file the token was spelled in, for a replacement list token that is
+    /// where the macro was defined
+    pub file: Rc<str>,
+    pub line: u32,
+    pub column: u32,
+    /// whitespace (or a comment) came before the token on its line
+    pub white: bool,
+    /// the first token of a line
+    pub bol: bool,
+    /// the macros which must not expand this token (Prosser's hide set)
+    pub hide: Rc<[Rc<str>]>,
+    /// where the outermost macro invocation which produced this token was, if any
+    pub expanded_at: Option<(u32, u32)>,
+    /// the innermost macro expansion which produced this token, if any
+    pub expansion: Option<Rc<Expansion>>,
+    /// for `Padding` only, whether the token it stands in for had whitespace before it,
+    /// `None` when it doesn't stand in for any token
+    pub padding_white: Option<bool>,
+}
+
+impl PpToken {
+    pub fn new(kind: Kind, text: &str, line: u32, column: u32) -> Self {
+        PpToken {
+            kind,
+            text: text.into(),
+            file: "".into(),
+            line,
+            column,
+            white: false,
+            bol: false,
+            hide: Rc::new([]),
+            expanded_at: None,
+            expansion: None,
+            padding_white: None,
+        }
+    }
+
+    pub fn padding(padding_white: Option<bool>) -> Self {
+        PpToken {
+            padding_white,
+            ..PpToken::new(Kind::Padding, "", 0, 0)
+        }
+    }
+
+    /// a token made up by the preprocessor in place of `at`, e.g. the result of `##`
+    pub fn synthesised(kind: Kind, text: &str, at: &PpToken) -> Self {
+        PpToken {
+            kind,
+            text: text.into(),
+            file: at.file.clone(),
+            line: at.line,
+            column: at.column,
+            white: at.white,
+            bol: false,
+            hide: at.hide.clone(),
+            expanded_at: at.expanded_at,
+            expansion: at.expansion.clone(),
+            padding_white: None,
+        }
+    }
+
+    pub fn is(&self, punct: &str) -> bool {
+        match self.kind {
+            Kind::Punct => {
+                &*self.text == punct || matches!((&*self.text, punct), ("%:", "#") | ("%:%:", "##"))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_identifier(&self, name: &str) -> bool {
+        self.kind == Kind::Identifier && &*self.text == name
+    }
+
+    pub fn is_padding(&self) -> bool {
+        self.kind == Kind::Padding
+    }
+
+    pub fn hidden(&self, name: &str) -> bool {
+        self.hide.iter().any(|h| &**h == name)
+    }
+
+    /// where the token is printed and reported, the invocation for expanded tokens
+    pub fn position(&self) -> (u32, u32) {
+        self.expanded_at.unwrap_or((self.line, self.column))
+    }
+}
+
+/// whether printing `next` straight after `prev` would lex as something else, this is
+/// `cpp_avoid_paste` from gcc so our output spaces tokens the same way
+pub fn avoid_paste(prev: &PpToken, next: &PpToken) -> bool {
+    let c = match next.kind {
+        Kind::Punct => next.text.chars().next(),
+        _ => None,
+    };
+    let number_or_ident = |t: &PpToken| matches!(t.kind, Kind::Number | Kind::Identifier);
+
+    match prev.kind {
+        Kind::Identifier => matches!(
+            next.kind,
+            Kind::Identifier | Kind::Number | Kind::CharConst | Kind::StringLit
+        ),
+        Kind::Number => {
+            number_or_ident(next)
+                || next.kind == Kind::CharConst
+                || matches!(c, Some('.' | '+' | '-'))
+        }
+        Kind::Other => prev.text.starts_with('\\') && next.kind == Kind::Identifier,
+        Kind::Punct => {
+            let can_take_eq = matches!(
+                &*prev.text,
+                "=" | "!" | ">" | "<" | "+" | "-" | "*" | "/" | "%" | "&" | "|" | "^" | ">>" | "<<"
+            );
+            if can_take_eq && c == Some('=') {
+                return true;
+            }
+            match &*prev.text {
+                ">" => c == Some('>'),
+                "<" => matches!(c, Some('<' | '%' | ':')),
+                "+" => c == Some('+'),
+                "-" => matches!(c, Some('-' | '>')),
+                "/" => matches!(c, Some('/' | '*')),
+                "%" => matches!(c, Some(':' | '%')),
+                "&" => c == Some('&'),
+                "|" => c == Some('|'),
+                ":" => matches!(c, Some(':' | '>')),
+                "->" => c == Some('*'),
+                "." => matches!(c, Some('.' | '%')) || next.kind == Kind::Number,
+                "#" | "%:" => matches!(c, Some('#' | '%')),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
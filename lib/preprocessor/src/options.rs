@@ -0,0 +1,112 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use lang::{LangOptions, Pedantic};
+
+use crate::{SearchDir, SearchPath, SystemHeader};
+
+/// a `-D` or `-U` option, which are applied in the order they were given
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MacroOption {
+    /// `NAME`, `NAME=DEFINITION` or `NAME(PARAMS)=DEFINITION`
+    Define(String),
+    Undefine(String),
+}
+
+impl MacroOption {
+    /// the directive the option stands for, gcc's `-D NAME` defines it as 1
+    pub fn directive(&self) -> String {
+        match self {
+            MacroOption::Define(define) => match define.split_once('=') {
+                Some((name, definition)) => format!("#define {name} {definition}"),
+                None => format!("#define {define} 1"),
+            },
+            MacroOption::Undefine(name) => format!("#undef {name}"),
+        }
+    }
+}
+
+/// the options from the command line which change how a file is preprocessed
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Options {
+    /// `-I`, searched by both forms of `#include` ahead of the system directories
+    pub include_dirs: Vec<PathBuf>,
+    /// `-isystem`, searched after the `-I` directories, the headers in them are system
+    /// headers
+    pub system_include_dirs: Vec<PathBuf>,
+    /// `-D` and `-U`
+    pub macros: Vec<MacroOption>,
+    /// `-include`, each read as if the source file started by `#include`ing it
+    pub includes: Vec<PathBuf>,
+    /// `-std=` and `-pedantic`, for `__STDC_VERSION__` and whether `//` comments are allowed
+    pub lang: LangOptions,
+}
+
+impl Options {
+    /// the search path with the `-I` and `-isystem` directories ahead of `system`. As gcc
+    /// does, a directory only appears once, and where it would be both a system directory
+    /// and not it is the system one which is kept
+    pub fn search_path(&self, system: SearchPath) -> SearchPath {
+        let user = self.include_dirs.iter().map(|path| SearchDir {
+            path: path.clone(),
+            system: SystemHeader::No,
+        });
+        let isystem = self.system_include_dirs.iter().map(|path| SearchDir {
+            path: path.clone(),
+            system: SystemHeader::ExternC,
+        });
+        let dirs: Vec<SearchDir> = user.chain(isystem).chain(system.bracket).collect();
+
+        let same = |a: &SearchDir, b: &SearchDir| same_dir(&a.path, &b.path);
+        let mut bracket: Vec<SearchDir> = vec![];
+        for (i, dir) in dirs.iter().enumerate() {
+            let duplicate = bracket.iter().any(|seen| same(seen, dir));
+            let shadows_system = dir.system == SystemHeader::No
+                && dirs[i + 1..]
+                    .iter()
+                    .any(|later| later.system != SystemHeader::No && same(later, dir));
+            if !duplicate && !shadows_system {
+                bracket.push(dir.clone());
+            }
+        }
+        SearchPath {
+            quote: system.quote,
+            bracket,
+        }
+    }
+
+    /// the same options for `gcc`
+    pub fn gcc_args(&self) -> Vec<String> {
+        let mut args = vec![format!("-std={}", self.lang.standard.option())];
+        match self.lang.pedantic {
+            Pedantic::Off => (),
+            Pedantic::Warn => args.push("-pedantic".to_string()),
+            Pedantic::Error => args.push("-pedantic-errors".to_string()),
+        }
+        for dir in &self.include_dirs {
+            args.push(format!("-I{}", dir.display()));
+        }
+        for dir in &self.system_include_dirs {
+            args.extend(["-isystem".to_string(), dir.display().to_string()]);
+        }
+        for option in &self.macros {
+            args.push(match option {
+                MacroOption::Define(define) => format!("-D{define}"),
+                MacroOption::Undefine(name) => format!("-U{name}"),
+            });
+        }
+        for include in &self.includes {
+            args.extend(["-include".to_string(), include.display().to_string()]);
+        }
+        args
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
@@ -0,0 +1,132 @@
+use std::{error, fmt, io, path::PathBuf};
+
+use diagnostics::{Diagnostic, ErrorKind, StageError};
+
+/// everything that can stop the driver, either its own failures or a stage's
+#[derive(Debug)]
+pub enum Error {
+    /// we name intermediate files by swapping the input's extension so it must have one
+    NoExtension(PathBuf),
+    /// an intermediate file couldn't be cleaned up
+    Remove {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// the output asked for by `-o` couldn't be written
+    Write {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// the make rule asked for by `-M` or `-MD` couldn't be written
+    WriteDependencies {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Preprocess(preprocessor::Error),
+    Lex(lexer::Error),
+    Parse(parser::Error),
+    /// code generation or emission, which are yet to be written
+    Unimplemented(diagnostics::Unimplemented),
+    Assemble(assembler::Error),
+}
+
+impl Error {
+    fn stage(&self) -> Option<&dyn StageError> {
+        match self {
+            Error::NoExtension(_)
+            | Error::Remove { .. }
+            | Error::Write { .. }
+            | Error::WriteDependencies { .. } => None,
+            Error::Preprocess(e) => Some(e),
+            Error::Lex(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Unimplemented(e) => Some(e),
+            Error::Assemble(e) => Some(e),
+        }
+    }
+
+    /// gcc style: 1 for errors in the input, 4 for an internal compiler error, and 2 for
+    /// the environment letting us down so scripts can tell those apart
+    pub fn exit_code(&self) -> u8 {
+        match self.kind() {
+            ErrorKind::User => 1,
+            ErrorKind::Environment => 2,
+            ErrorKind::Internal => 4,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoExtension(path) => {
+                write!(
+                    f,
+                    "input file {} does not have an extension",
+                    path.display()
+                )
+            }
+            Error::Remove { path, .. } => write!(f, "unable to remove {}", path.display()),
+            Error::Write { path, .. } => write!(f, "unable to write {}", path.display()),
+            Error::WriteDependencies { path, .. } => {
+                write!(f, "unable to write dependencies to {}", path.display())
+            }
+            _ => write!(
+                f,
+                "{}",
+                self.stage().expect("every other variant is a stage")
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::NoExtension(_) => None,
+            Error::Remove { source, .. }
+            | Error::Write { source, .. }
+            | Error::WriteDependencies { source, .. } => Some(source),
+            Error::Preprocess(e) => e.source(),
+            Error::Lex(e) => e.source(),
+            Error::Parse(e) => e.source(),
+            Error::Unimplemented(e) => e.source(),
+            Error::Assemble(e) => e.source(),
+        }
+    }
+}
+
+impl StageError for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::NoExtension(_) => ErrorKind::User,
+            Error::Remove { .. } | Error::Write { .. } | Error::WriteDependencies { .. } => {
+                ErrorKind::Environment
+            }
+            _ => self.stage().expect("every other variant is a stage").kind(),
+        }
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        match self.stage() {
+            Some(e) => e.diagnostics(),
+            None => &[],
+        }
+    }
+}
+
+macro_rules! from_stage {
+    ($variant: ident, $error: ty) => {
+        impl From<$error> for Error {
+            fn from(e: $error) -> Self {
+                Error::$variant(e)
+            }
+        }
+    };
+}
+
+from_stage!(Preprocess, preprocessor::Error);
+from_stage!(Lex, lexer::Error);
+from_stage!(Parse, parser::Error);
+from_stage!(Unimplemented, diagnostics::Unimplemented);
+from_stage!(Assemble, assembler::Error);
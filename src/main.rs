@@ -1,12 +1,19 @@
-use std::{fs, io, path::PathBuf, str::FromStr};
+use std::{
+    error::Error as _,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
-use clap::{Args, Parser};
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, ValueEnum};
 
-use assembler;
-use generator;
-use lexer;
-use parser;
-use preprocessor;
+use diagnostics::{Diagnostics, ErrorKind, FileSources, StageError};
+use lang::{LangOptions, Pedantic, Standard};
+use preprocessor::{MacroOption, Preprocessed};
+
+mod error;
+
+use error::Error;
 
 #[derive(Parser, Debug)]
 #[command(version, about)]
@@ -14,6 +21,36 @@ struct Cli {
     #[command(flatten)]
     output_control: OutputControl,
 
+    #[command(flatten)]
+    language: LanguageOptions,
+
+    #[command(flatten)]
+    preprocessor: PreprocessorOptions,
+
+    #[command(flatten)]
+    dependencies: DependencyOptions,
+
+    /// write the output to FILE: the preprocessed source with -E, the assembly with -S,
+    /// otherwise the executable
+    #[arg(short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// keep the .i and .s files written along the way rather than removing them
+    #[arg(long = "save-temps")]
+    save_temps: bool,
+
+    /// how diagnostics are written to stderr
+    #[arg(long, value_enum, default_value_t = DiagnosticsFormat::Caret)]
+    diagnostics_format: DiagnosticsFormat,
+
+    /// report warnings from system headers rather than suppressing them
+    #[arg(long = "Wsystem-headers")]
+    system_headers: bool,
+
+    /// stop after N errors have been reported, 0 for no limit
+    #[arg(long = "fmax-errors", value_name = "N", default_value_t = 0)]
+    max_errors: usize,
+
     /// path to file to compile
     file: String,
 }
@@ -21,10 +58,19 @@ struct Cli {
 #[derive(Args, Debug)]
 #[group(multiple = false)]
 struct OutputControl {
+    /// stop after the preprocessor, writing the preprocessed source to stdout unless -o
+    /// says otherwise
+    #[arg(short = 'E')]
+    preprocess: bool,
+
     /// we should stop after the lexer
     #[arg(long)]
     lex: bool,
 
+    /// stop after the lexer, printing each token with where it came from
+    #[arg(long = "dump-tokens")]
+    dump_tokens: bool,
+
     /// we should stop after the parser
     #[arg(long)]
     parse: bool,
@@ -38,55 +84,350 @@ struct OutputControl {
     skip_assembly: bool,
 }
 
-fn change_extension(orig_path: &PathBuf, extension: &str) -> PathBuf {
-    let mut copy_path = orig_path.clone();
+// the standard the source is written in and how strictly it is held to it
+#[derive(Args, Debug)]
+struct LanguageOptions {
+    /// the C standard the source is written in: c89 (or c90), c99, c11, c17 (or c18) or
+    /// c23 (or c2x)
+    #[arg(long = "std", value_name = "STANDARD", default_value = "c11")]
+    standard: Standard,
+
+    /// warn about anything the standard doesn't have which we accept anyway
+    #[arg(long)]
+    pedantic: bool,
+
+    /// as -pedantic, but make them errors
+    #[arg(long = "pedantic-errors")]
+    pedantic_errors: bool,
+}
+
+impl LanguageOptions {
+    fn options(&self) -> LangOptions {
+        let pedantic = match (self.pedantic, self.pedantic_errors) {
+            (_, true) => Pedantic::Error,
+            (true, false) => Pedantic::Warn,
+            (false, false) => Pedantic::Off,
+        };
+        LangOptions {
+            standard: self.standard,
+            pedantic,
+        }
+    }
+}
+
+// the options passed on to the preprocessor, spelt as gcc spells them (a doc comment
+// here would become the help text of the whole command)
+#[derive(Args, Debug)]
+struct PreprocessorOptions {
+    /// add DIR to the directories searched by `#include`
+    #[arg(short = 'I', value_name = "DIR")]
+    include_dirs: Vec<PathBuf>,
+
+    /// add DIR to the directories searched by `#include` after those from -I, treating
+    /// the headers in it as system headers
+    #[arg(long = "isystem", value_name = "DIR")]
+    system_include_dirs: Vec<PathBuf>,
+
+    /// define NAME as DEFINITION, or as 1 if there isn't one
+    #[arg(short = 'D', value_name = "NAME[=DEFINITION]")]
+    defines: Vec<String>,
+
+    /// undefine NAME, -D and -U are applied in the order they are given
+    #[arg(short = 'U', value_name = "NAME")]
+    undefines: Vec<String>,
+
+    /// read FILE before the source file, as if the source file started by including it
+    #[arg(long = "include", value_name = "FILE")]
+    includes: Vec<PathBuf>,
+}
+
+impl PreprocessorOptions {
+    /// the options for the preprocessor, `matches` gives the order of the -D and -U
+    fn options(&self, matches: &ArgMatches, lang: LangOptions) -> preprocessor::Options {
+        let indices = |id: &str| matches.indices_of(id).into_iter().flatten();
+        let defines = indices("defines")
+            .zip(&self.defines)
+            .map(|(i, define)| (i, MacroOption::Define(define.clone())));
+        let undefines = indices("undefines")
+            .zip(&self.undefines)
+            .map(|(i, name)| (i, MacroOption::Undefine(name.clone())));
+        let mut macros: Vec<(usize, MacroOption)> = defines.chain(undefines).collect();
+        macros.sort_by_key(|(i, _)| *i);
+
+        preprocessor::Options {
+            include_dirs: self.include_dirs.clone(),
+            system_include_dirs: self.system_include_dirs.clone(),
+            macros: macros.into_iter().map(|(_, option)| option).collect(),
+            includes: self.includes.clone(),
+            lang,
+        }
+    }
+}
+
+// the options for writing a make rule with the files the source file depends on
+#[derive(Args, Debug)]
+struct DependencyOptions {
+    /// write a make rule for the source file and every header it includes rather than
+    /// compiling it, to stdout unless -MF says otherwise
+    #[arg(long = "M")]
+    only: bool,
+
+    /// write the make rule as well as compiling, to the source file with a .d extension
+    /// unless -MF says otherwise
+    #[arg(long = "MD")]
+    also: bool,
+
+    /// the file to write the make rule to
+    #[arg(long = "MF", value_name = "FILE")]
+    rule_path: Option<PathBuf>,
+
+    /// the target of the make rule, written as it is, rather than the source file's name
+    /// with a .o extension and without its directory
+    #[arg(long = "MT", value_name = "TARGET")]
+    targets: Vec<String>,
+
+    /// add a rule with no dependencies for each header so make doesn't fail when one is
+    /// removed
+    #[arg(long = "MP")]
+    phony: bool,
+}
+
+impl DependencyOptions {
+    fn targets(&self, src_path: &Path) -> Vec<String> {
+        if !self.targets.is_empty() {
+            return self.targets.clone();
+        }
+        let name = src_path.file_name().map(Path::new).unwrap_or(src_path);
+        let object = change_extension(name, "o");
+        vec![preprocessor::make_quote(&object.to_string_lossy())]
+    }
+
+    /// write the make rule for `preprocessed` if we've been asked to
+    fn write(&self, src_path: &Path, preprocessed: &Preprocessed) -> Result<(), Error> {
+        if !self.only && !self.also {
+            return Ok(());
+        }
+        let rule = preprocessor::make_rule(
+            &self.targets(src_path),
+            preprocessed.dependencies(),
+            self.phony,
+        );
+        let path = match (&self.rule_path, self.only) {
+            (Some(path), _) => path.clone(),
+            (None, true) => {
+                print!("{rule}");
+                return Ok(());
+            }
+            (None, false) => change_extension(src_path, "d"),
+        };
+        fs::write(&path, rule).map_err(|source| Error::WriteDependencies { path, source })
+    }
+}
+
+/// gcc's options which are more than one letter but take a single dash, rewritten to
+/// the double dash clap expects. Those which take a value may have it joined on
+/// (`-isystemdir`), or after an `=` for `-std` and `-fmax-errors`
+fn normalise_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    const FLAGS: [&str; 7] = [
+        "-M",
+        "-MD",
+        "-MP",
+        "-save-temps",
+        "-pedantic",
+        "-pedantic-errors",
+        "-Wsystem-headers",
+    ];
+    const WITH_VALUE: [&str; 4] = ["-isystem", "-include", "-MF", "-MT"];
+    args.into_iter()
+        .map(|arg| {
+            if FLAGS.contains(&arg.as_str()) || arg.starts_with("-std=") || arg.starts_with("-fmax-errors=") {
+                return format!("-{arg}");
+            }
+            match WITH_VALUE.iter().find(|option| arg.starts_with(*option)) {
+                Some(option) if arg.len() == option.len() => format!("-{arg}"),
+                Some(option) => format!("-{option}={}", &arg[option.len()..]),
+                None => arg,
+            }
+        })
+        .collect()
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum DiagnosticsFormat {
+    /// gcc style `file:line:col: severity: message` lines
+    Text,
+    /// as text, with the source line and a caret under the location
+    Caret,
+    /// a JSON array of diagnostics
+    Json,
+}
+
+impl From<DiagnosticsFormat> for diagnostics::Format {
+    fn from(format: DiagnosticsFormat) -> Self {
+        match format {
+            DiagnosticsFormat::Text => diagnostics::Format::Text,
+            DiagnosticsFormat::Caret => diagnostics::Format::Caret,
+            DiagnosticsFormat::Json => diagnostics::Format::Json,
+        }
+    }
+}
+
+fn change_extension(orig_path: &Path, extension: &str) -> PathBuf {
+    let mut copy_path = orig_path.to_path_buf();
     copy_path.set_extension(extension);
     copy_path
 }
 
-fn main() {
-    let cli: Cli = Cli::parse();
-    let output_control = cli.output_control;
+/// remove an intermediate file unless we've been asked to keep them
+fn discard(cli: &Cli, path: &Path) -> Result<(), Error> {
+    if cli.save_temps {
+        return Ok(());
+    }
+    remove(path)
+}
 
-    let src_path = PathBuf::from_str(&cli.file)
-        .expect("Unable to get PathBuf from file argument: {&cli.file}");
+fn remove(path: &Path) -> Result<(), Error> {
+    fs::remove_file(path).map_err(|source| Error::Remove {
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
-    src_path
-        .extension()
-        .expect("Input file argument does not have an extension: {&cli.file}");
+// the later stages are placeholders which produce `()` for now
+#[allow(clippy::let_unit_value)]
+fn run(cli: &Cli, options: &preprocessor::Options, diagnostics: &Diagnostics) -> Result<(), Error> {
+    let output_control = &cli.output_control;
+
+    let src_path = PathBuf::from(&cli.file);
+    if src_path.extension().is_none() {
+        return Err(Error::NoExtension(src_path));
+    }
 
     let preprocessed_path = change_extension(&src_path, "i");
-    let assembly_path = change_extension(&src_path, "s");
-    let binary_path = change_extension(&src_path, "");
+    let assembly_path = match &cli.output {
+        Some(path) if output_control.skip_assembly => path.clone(),
+        _ => change_extension(&src_path, "s"),
+    };
+    let binary_path = match &cli.output {
+        Some(path) => path.clone(),
+        None => change_extension(&src_path, ""),
+    };
 
     let preprocessed =
-        preprocessor::preprocess(&src_path, &preprocessed_path).expect("Error in preprocessing");
+        preprocessor::preprocess(&src_path, &preprocessed_path, options, diagnostics)?;
+    let dependencies = cli.dependencies.write(&src_path, &preprocessed);
+    if cli.dependencies.only || dependencies.is_err() {
+        discard(cli, &preprocessed_path)?;
+        return dependencies;
+    }
+
+    if output_control.preprocess {
+        discard(cli, &preprocessed_path)?;
+        return match &cli.output {
+            Some(path) => fs::write(path, preprocessed.text()).map_err(|source| Error::Write {
+                path: path.clone(),
+                source,
+            }),
+            None => {
+                print!("{}", preprocessed.text());
+                Ok(())
+            }
+        };
+    }
 
-    let tokens = lexer::lex(&preprocessed).expect("Failed to lex input");
-    fs::remove_file(&preprocessed_path).expect("Failed to remove preprocessed input");
+    // the lexer carries on past bad input so that every error in the unit is reported at
+    // once, the tokens are still worth dumping when it has had to
+    let (lexed, lexing) = lexer::lex_recovering(
+        preprocessed.text(),
+        preprocessed.expansions(),
+        &options.lang,
+        diagnostics,
+    );
+    discard(cli, &preprocessed_path)?;
+    if output_control.dump_tokens {
+        print!("{}", lexed.dump());
+        return Ok(lexing?);
+    }
     if output_control.lex {
+        lexing?;
         println!("Terminating after lex");
-        return;
+        return Ok(());
     }
 
-    let ast = parser::parse(&tokens).expect("Failed to parse");
+    // the parser reports its own errors in whatever the lexer recovered, the run still
+    // fails with the lexer's if it had any
+    let parsing = parser::parse(
+        lexed.tokens(),
+        lexed.source_map(),
+        &options.lang,
+        diagnostics,
+    );
+    lexing?;
+    let ast = parsing?;
     if output_control.parse {
         println!("Terminating after parse");
-        return;
+        return Ok(());
     }
 
-    let generated = generator::generate(&ast).expect("Failed code generation");
+    let generated = generator::generate(&ast)?;
     if output_control.codegen {
         println!("Terminating after codegen");
-        return;
+        return Ok(());
     }
 
-    emitter::emit(&generated, &assembly_path).expect("Failed code emission");
+    emitter::emit(&generated, &assembly_path)?;
     if output_control.skip_assembly {
         println!("Terminating after code emission");
+        return Ok(());
+    }
+
+    assembler::assemble(&assembly_path, &binary_path)?;
+    discard(cli, &assembly_path)
+}
+
+/// the diagnostics go first, then if that doesn't already explain the failure the error
+/// itself along with whatever caused it
+fn report(diagnostics: &Diagnostics, format: DiagnosticsFormat, result: &Result<(), Error>) {
+    let reported = diagnostics.take();
+    if !reported.is_empty() {
+        let sources = FileSources::new();
+        eprint!(
+            "{}",
+            diagnostics::render(&reported, format.into(), &sources)
+        );
+    }
+
+    let Err(e) = result else {
         return;
+    };
+    if !e.diagnostics().is_empty() {
+        return;
+    }
+    let severity = match e.kind() {
+        ErrorKind::User | ErrorKind::Environment => "error",
+        ErrorKind::Internal => "internal compiler error",
+    };
+    eprintln!("compiler: {severity}: {e}");
+    let mut source = e.source();
+    while let Some(cause) = source {
+        eprintln!("  caused by: {cause}");
+        source = cause.source();
     }
+}
+
+fn main() -> ExitCode {
+    let matches = Cli::command().get_matches_from(normalise_args(std::env::args()));
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let options = cli.preprocessor.options(&matches, cli.language.options());
+    let diagnostics = Diagnostics::new()
+        .with_system_headers(cli.system_headers)
+        .with_max_errors(cli.max_errors);
 
-    assembler::assemble(&assembly_path, &binary_path).expect("Failed assembly");
-    fs::remove_file(assembly_path).expect("Failed to remove assembly file");
+    let result = run(&cli, &options, &diagnostics);
+    report(&diagnostics, cli.diagnostics_format, &result);
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => ExitCode::from(e.exit_code()),
+    }
 }